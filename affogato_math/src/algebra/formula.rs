@@ -78,7 +78,7 @@ fn solve_cubic_normed<T: Real>(a: T, b: T, c: T) -> Option<PolynomialSolutions<T
     if r2 < q3 {
         let mut t = r/q3.sqrt();
         if t < -T::ONE { t = -T::ONE };
-        if t < -T::ONE { t = -T::ONE };
+        if t > T::ONE { t = T::ONE };
         t = t.acos();
         q = T::from_f64(-2.0)*q.sqrt();
         let solution1 = q*(T::ONE/T::from_f64(3.0)*t).cos() -a;
@@ -110,4 +110,66 @@ pub fn solve_cubic<T: Real>(a: T, b: T, c: T, d: T) -> Option<PolynomialSolution
         }
     }
     return Some(PolynomialSolutions::from_solution(solve_quadratic(b, c, d)?));
-}
\ No newline at end of file
+}
+/// Gets all real roots of a quartic equation in the form ax^4 + bx^3 + cx^2 + dx + e
+/// with Ferrari's method, polishing each root with a couple of Newton steps since
+/// going through the resolvent cubic loses some precision.
+pub fn solve_quartic<T: Real>(a: T, b: T, c: T, d: T, e: T) -> Option<PolynomialSolutions<T, 4>> {
+    if a == T::ZERO {
+        return Some(PolynomialSolutions::from_solution(solve_cubic(b, c, d, e)?));
+    }
+    let (b, c, d, e) = (b/a, c/a, d/a, e/a);
+    // substituting x = y - b/4 leaves y^4 + py^2 + qy + r
+    let b2 = b*b;
+    let p = c - T::from_f64(3.0/8.0)*b2;
+    let q = d - T::from_f64(0.5)*b*c + T::from_f64(0.125)*b2*b;
+    let r = e - T::from_f64(0.25)*b*d + T::from_f64(1.0/16.0)*b2*c - T::from_f64(3.0/256.0)*b2*b2;
+    let mut roots = [T::ZERO; 4];
+    let mut total = 0;
+    let mut push = |y: T| {
+        roots[total] = y;
+        total += 1;
+    };
+    // the largest root of the resolvent cubic splits the quartic into two quadratics
+    let m = if q == T::ZERO {
+        T::ZERO
+    } else {
+        solve_cubic(T::ONE, p, T::from_f64(0.25)*p*p - r, T::from_f64(-0.125)*q*q)?.fold(T::ZERO, |m, root| m.max(root))
+    };
+    if m > T::ZERO {
+        let s = (T::from_f64(2.0)*m).sqrt();
+        let half = T::from_f64(0.5)*p + m;
+        let offset = q/(T::from_f64(2.0)*s);
+        for (linear, constant) in [(-s, half + offset), (s, half - offset)] {
+            for y in solve_quadratic(T::ONE, linear, constant).into_iter().flatten() {
+                push(y);
+            }
+        }
+    } else {
+        // biquadratic, y^4 + py^2 + r
+        for z in solve_quadratic(T::ONE, p, r).into_iter().flatten() {
+            if z > T::ZERO {
+                push(z.sqrt());
+                push(-z.sqrt());
+            } else if z == T::ZERO {
+                push(T::ZERO);
+            }
+        }
+    }
+    if total == 0 {
+        return None;
+    }
+    for root in roots[..total].iter_mut() {
+        let mut x = *root - T::from_f64(0.25)*b;
+        for _ in 0..2 {
+            let value = (((x + b)*x + c)*x + d)*x + e;
+            let slope = ((T::from_f64(4.0)*x + T::from_f64(3.0)*b)*x + T::from_f64(2.0)*c)*x + d;
+            if slope == T::ZERO {
+                break;
+            }
+            x -= value/slope;
+        }
+        *root = x;
+    }
+    Some(PolynomialSolutions::new(roots, total))
+}
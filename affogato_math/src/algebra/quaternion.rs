@@ -12,8 +12,8 @@ use crate::{matrix::{Matrix3, Matrix4}, vector::{Vector3, Vector4}};
 /// represent rotations in 3d space.
 #[repr(C)]
 #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Copy, Debug)]
-pub struct Quaternion<T: Real> {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion<T: Number> {
     pub w: T,
     pub i: T,
     pub j: T,
@@ -144,6 +144,28 @@ impl<T: Real> Quaternion<T>  {
         let euler_ = Quaternion::from_euler(euler);
         *self = *self * euler_;
     }
+    /// The rotation turning the x, y and z axes onto `right`, `up` and
+    /// `forward`, which have to be unit length, at right angles to each other
+    /// and right handed. The inverse of [`Quaternion::right`],
+    /// [`Quaternion::up`] and [`Quaternion::forward`].
+    pub fn from_axes(right: Vector3<T>, up: Vector3<T>, forward: Vector3<T>) -> Self {
+        // Shepperd's method, dividing by the largest of the four components
+        let trace = right.x() + up.y() + forward.z();
+        let quarter = T::from_f64(0.25);
+        if trace > T::ZERO {
+            let s = (trace + T::ONE).sqrt()*T::from_f64(2.0);
+            Self::new(quarter*s, (up.z() - forward.y())/s, (forward.x() - right.z())/s, (right.y() - up.x())/s)
+        } else if right.x() > up.y() && right.x() > forward.z() {
+            let s = (T::ONE + right.x() - up.y() - forward.z()).sqrt()*T::from_f64(2.0);
+            Self::new((up.z() - forward.y())/s, quarter*s, (up.x() + right.y())/s, (forward.x() + right.z())/s)
+        } else if up.y() > forward.z() {
+            let s = (T::ONE + up.y() - right.x() - forward.z()).sqrt()*T::from_f64(2.0);
+            Self::new((forward.x() - right.z())/s, (up.x() + right.y())/s, quarter*s, (forward.y() + up.z())/s)
+        } else {
+            let s = (T::ONE + forward.z() - right.x() - up.y()).sqrt()*T::from_f64(2.0);
+            Self::new((right.y() - up.x())/s, (forward.x() + right.z())/s, (forward.y() + up.z())/s, quarter*s)
+        }
+    }
     pub fn right(self) -> Vector3<T>
         where T: Number {
         self * Vector3::new(T::ONE, T::ZERO,T::ZERO)
@@ -248,10 +270,10 @@ impl<T: Real> core::ops::Mul<T> for Quaternion<T>  {
 impl<T: Real> core::ops::Add for Quaternion<T>  {
     fn add(self, rhs: Self) -> Self::Output {
         Self::new(
-            self.w*rhs.w, 
-            self.i*rhs.i, 
-            self.j*rhs.j, 
-            self.k*rhs.k
+            self.w+rhs.w, 
+            self.i+rhs.i, 
+            self.j+rhs.j, 
+            self.k+rhs.k
         )
     }
     type Output = Self;
@@ -317,4 +339,15 @@ mod alloc_feature {
             f.write_str(alloc::format!("{w} {i} {j} {k}").as_str())
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::Quaternion;
+
+    #[test]
+    fn addition() {
+        // adding used to multiply the components
+        let sum = Quaternion::new(1.0, 2.0, 3.0, 4.0) + Quaternion::new(0.5, -2.0, 3.0, 1.0);
+        assert_eq!([sum.w, sum.i, sum.j, sum.k], [1.5, 0.0, 6.0, 5.0]);
+    }
+}
@@ -0,0 +1,416 @@
+use core::fmt::Debug;
+
+use affogato_core::{groups::vector_spaces::VectorSpace, num::{Bounds, FloatConsts, FloatingPoint, Number, Zero}, sets::Real};
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{geometry::CalculateCentroid, matrix::Matrix3, vector::{Vector2, Vector3}};
+
+use super::{CubicSegment2D, Rect, Segment};
+#[cfg(feature="alloc")]
+extern crate alloc;
+
+/// The most cubic segments [`ArcSegment2D::to_cubics`] will split an arc into.
+const MAX_CUBIC_PIECES: usize = 64;
+
+/// A circular or elliptical arc stored in center parameterization. A point on
+/// the arc at some angle θ is `center + R(rotation) * (radii.x*cos θ, radii.y*sin θ)`,
+/// where θ goes from `start_angle` to `start_angle+sweep_angle`. A positive
+/// sweep goes counter clockwise.
+///
+/// The arc is evaluated in [`f64`] so it can be stored with any [`Number`].
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy)]
+pub struct ArcSegment2D<T: Number> {
+    pub center: Vector2<T>,
+    pub radii: Vector2<T>,
+    /// The rotation of the x axis of the ellipse in radians.
+    pub rotation: T,
+    pub start_angle: T,
+    pub sweep_angle: T,
+}
+impl<T: Debug + Number> Debug for ArcSegment2D<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ArcSegment2D")
+            .field("center", &self.center)
+            .field("radii", &self.radii)
+            .field("rotation", &self.rotation)
+            .field("start_angle", &self.start_angle)
+            .field("sweep_angle", &self.sweep_angle)
+            .finish()
+    }
+}
+impl<T: Number> ArcSegment2D<T> {
+    pub const fn new(center: Vector2<T>, radii: Vector2<T>, rotation: T, start_angle: T, sweep_angle: T) -> Self {
+        Self { center, radii, rotation, start_angle, sweep_angle }
+    }
+    pub fn circular(center: Vector2<T>, radius: T, start_angle: T, sweep_angle: T) -> Self {
+        Self::new(center, Vector2::new(radius, radius), T::ZERO, start_angle, sweep_angle)
+    }
+    pub fn end_angle(&self) -> T {
+        self.start_angle+self.sweep_angle
+    }
+    pub fn is_circular(&self) -> bool {
+        self.radii.x() == self.radii.y()
+    }
+    /// The large arc flag of the SVG endpoint parameterization.
+    pub fn large_arc(&self) -> bool {
+        self.sweep_angle.to_f64().abs() > f64::PI
+    }
+    /// The sweep flag of the SVG endpoint parameterization.
+    pub fn sweep(&self) -> bool {
+        self.sweep_angle > T::ZERO
+    }
+    /// Maps a point on the unit circle onto the ellipse, relative to `center`.
+    fn ellipse_offset(&self, x: f64, y: f64) -> [f64; 2] {
+        let (sin, cos) = self.rotation.to_f64().sin_cos();
+        let x = x*self.radii.x().to_f64();
+        let y = y*self.radii.y().to_f64();
+        [cos*x - sin*y, sin*x + cos*y]
+    }
+    fn angle_at(&self, t: f64) -> f64 {
+        self.start_angle.to_f64() + self.sweep_angle.to_f64()*t
+    }
+    /// The angle in the frame of the ellipse that `point` lies at.
+    fn local_angle(&self, point: Vector2<T>) -> f64 {
+        let (sin, cos) = self.rotation.to_f64().sin_cos();
+        let x = point.x().to_f64() - self.center.x().to_f64();
+        let y = point.y().to_f64() - self.center.y().to_f64();
+        let local_x = (cos*x + sin*y)/self.radii.x().to_f64();
+        let local_y = (-sin*x + cos*y)/self.radii.y().to_f64();
+        local_y.atan2(local_x)
+    }
+    /// Wraps `sweep` into a full turn going in the same direction as `self.sweep_angle`.
+    fn wrap_sweep(&self, sweep: f64) -> f64 {
+        let sweep = sweep - f64::TAU*(sweep/f64::TAU).floor();
+        if self.sweep_angle < T::ZERO && sweep > 0.0 {
+            sweep - f64::TAU
+        } else {
+            sweep
+        }
+    }
+    /// The point on the ellipse at `angle` radians.
+    pub fn point_at_angle(&self, angle: f64) -> Vector2<T> {
+        let (sin, cos) = angle.sin_cos();
+        let [x, y] = self.ellipse_offset(cos, sin);
+        Vector2::new(T::from_f64(self.center.x().to_f64() + x), T::from_f64(self.center.y().to_f64() + y))
+    }
+    /// The derivative of the arc with respect to t.
+    pub fn derivative(&self, t: f64) -> Vector2<T> {
+        let (sin, cos) = self.angle_at(t).sin_cos();
+        let sweep = self.sweep_angle.to_f64();
+        let [x, y] = self.ellipse_offset(-sin*sweep, cos*sweep);
+        Vector2::new(T::from_f64(x), T::from_f64(y))
+    }
+}
+impl<T: Real> ArcSegment2D<T> {
+    /// Converts the endpoint parameterization used by SVG into the center
+    /// parameterization. The radii are scaled up when they are too small to
+    /// reach from `start` to `end`. Gives [`None`] when `start` and `end` are
+    /// the same point or either radius is zero, in which case SVG omits the
+    /// arc or draws a line respectively.
+    pub fn from_endpoints(start: Vector2<T>, radii: Vector2<T>, rotation: T, large_arc: bool, sweep: bool, end: Vector2<T>) -> Option<Self> {
+        let (mut rx, mut ry) = (radii.x().abs(), radii.y().abs());
+        if start == end || rx == T::ZERO || ry == T::ZERO {
+            return None;
+        }
+        // https://www.w3.org/TR/SVG11/implnote.html#ArcConversionEndpointToCenter
+        let two = T::from_f64(2.0);
+        let (sin, cos) = rotation.sin_cos();
+        let half = (start-end)/two;
+        let x1 = cos*half.x() + sin*half.y();
+        let y1 = -sin*half.x() + cos*half.y();
+        let lambda = (x1*x1)/(rx*rx) + (y1*y1)/(ry*ry);
+        if lambda > T::ONE {
+            let scale = lambda.sqrt();
+            rx *= scale;
+            ry *= scale;
+        }
+        let numerator = rx*rx*ry*ry - rx*rx*y1*y1 - ry*ry*x1*x1;
+        let denominator = rx*rx*y1*y1 + ry*ry*x1*x1;
+        let mut coefficient = (numerator/denominator).max(T::ZERO).sqrt();
+        if large_arc == sweep {
+            coefficient = -coefficient;
+        }
+        let cx1 = coefficient*rx*y1/ry;
+        let cy1 = -coefficient*ry*x1/rx;
+        let midpoint = (start+end)/two;
+        let center = Vector2::new(cos*cx1 - sin*cy1 + midpoint.x(), sin*cx1 + cos*cy1 + midpoint.y());
+        let u = Vector2::new((x1-cx1)/rx, (y1-cy1)/ry);
+        let v = Vector2::new((-x1-cx1)/rx, (-y1-cy1)/ry);
+        let start_angle = u.y().atan2(u.x());
+        let mut sweep_angle = u.cross(&v).atan2(u.dot(&v));
+        if !sweep && sweep_angle > T::ZERO {
+            sweep_angle -= T::TAU;
+        } else if sweep && sweep_angle < T::ZERO {
+            sweep_angle += T::TAU;
+        }
+        Some(Self::new(center, Vector2::new(rx, ry), rotation, start_angle, sweep_angle))
+    }
+    /// The value of t on the arc closest in angle to `point`, clamped between
+    /// 0.0 and 1.0.
+    pub fn parameter_of(&self, point: Vector2<T>) -> T {
+        if self.sweep_angle == T::ZERO {
+            return T::ZERO;
+        }
+        let delta = T::from_f64(self.wrap_sweep(self.local_angle(point) - self.start_angle.to_f64()));
+        let t = delta/self.sweep_angle;
+        if t <= T::ONE {
+            return t;
+        }
+        // past the end of the arc, it may be closer to the start going the other way
+        let before = (delta - T::TAU*self.sweep_angle.signum())/self.sweep_angle;
+        if -before < t-T::ONE {
+            T::ZERO
+        } else {
+            T::ONE
+        }
+    }
+    /// The values of t between 0.0 and 1.0 where the arc reaches an extrema
+    /// along `axis`, gives how many were found.
+    pub fn axis_extrema(&self, axis: usize) -> ([T; 4], usize) {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (rx, ry) = (self.radii.x(), self.radii.y());
+        let angle = if axis == 0 {
+            (-ry*sin).atan2(rx*cos)
+        } else {
+            (ry*cos).atan2(rx*sin)
+        };
+        let (low, high) = if self.sweep_angle < T::ZERO {
+            (self.end_angle(), self.start_angle)
+        } else {
+            (self.start_angle, self.end_angle())
+        };
+        let mut extrema = ([T::ZERO; 4], 0);
+        let mut k = ((low-angle)/T::PI).ceil();
+        while extrema.1 < 4 {
+            let candidate = angle + T::PI*k;
+            if candidate >= high {
+                break;
+            }
+            if candidate > low {
+                extrema.0[extrema.1] = (candidate-self.start_angle)/self.sweep_angle;
+                extrema.1 += 1;
+            }
+            k += T::ONE;
+        }
+        extrema
+    }
+    /// The smallest axis aligned [`Rect`] containing the arc.
+    pub fn bounds(&self) -> Rect<T> {
+        let (start, end) = (self.start(), self.end());
+        let mut min = start.min(end);
+        let mut max = start.max(end);
+        for axis in 0..2 {
+            let (extrema, total) = self.axis_extrema(axis);
+            for t in &extrema[..total] {
+                let point = self.get(t.to_f64());
+                min = min.min(point);
+                max = max.max(point);
+            }
+        }
+        Rect::new(min, max)
+    }
+    /// The amount of cubic segments needed for [`ArcSegment2D::to_cubics`]
+    /// to stay within `tolerance` of the arc.
+    pub fn cubic_count(&self, tolerance: T) -> usize {
+        let radius = self.radii.x().abs().max(self.radii.y().abs());
+        let sweep = self.sweep_angle.abs();
+        let minimum = (sweep/T::FRAC_PI_2).ceil().max(T::ONE).to_usize();
+        (minimum..MAX_CUBIC_PIECES).find(|&count| {
+            // the radial error of a cubic approximating a circular arc of a given angle
+            let (sin, cos) = (sweep/T::from_usize(count*4)).sin_cos();
+            radius*T::from_f64(4.0/27.0)*sin.powi(6)/(cos*cos) <= tolerance
+        }).unwrap_or(Ord::max(MAX_CUBIC_PIECES, minimum))
+    }
+    /// The cubic approximating the part of the arc at `index` when split into
+    /// `count` pieces of equal angle.
+    pub fn cubic_piece(&self, index: usize, count: usize) -> CubicSegment2D<T> {
+        let step = self.sweep_angle.to_f64()/count as f64;
+        let k = 4.0/3.0*(step/4.0).tan();
+        let from = self.angle_at(index as f64/count as f64);
+        let to = from + step;
+        let (sin0, cos0) = from.sin_cos();
+        let (sin1, cos1) = to.sin_cos();
+        let offset = |[x, y]: [f64; 2]| self.center + Vector2::new(T::from_f64(x), T::from_f64(y));
+        CubicSegment2D::new(
+            self.point_at_angle(from),
+            offset(self.ellipse_offset(cos0 - k*sin0, sin0 + k*cos0)),
+            offset(self.ellipse_offset(cos1 + k*sin1, sin1 - k*cos1)),
+            self.point_at_angle(to),
+        )
+    }
+    /// Approximates the arc with cubic segments that stay within `tolerance`
+    /// of the arc.
+    pub fn to_cubics(&self, tolerance: T) -> impl Iterator<Item = CubicSegment2D<T>> + '_ {
+        let count = self.cubic_count(tolerance);
+        (0..count).map(move |index| self.cubic_piece(index, count))
+    }
+    /// Applies the affine transformation in `matrix` to the arc. The image of
+    /// an ellipse under an affine transformation is another ellipse, whose
+    /// axes are found through the singular value decomposition of the
+    /// linear part of the transformation.
+    pub fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        let center = *matrix*Vector3::new(self.center.x(), self.center.y(), T::ONE);
+        let (sin, cos) = self.rotation.sin_cos();
+        let (rx, ry) = (self.radii.x(), self.radii.y());
+        // the linear part of the transformation applied to the axes of the ellipse
+        let a = matrix.x.x()*cos*rx + matrix.y.x()*sin*rx;
+        let b = -matrix.x.x()*sin*ry + matrix.y.x()*cos*ry;
+        let c = matrix.x.y()*cos*rx + matrix.y.y()*sin*rx;
+        let d = -matrix.x.y()*sin*ry + matrix.y.y()*cos*ry;
+        let two = T::from_f64(2.0);
+        let (e, f, g, h) = ((a+d)/two, (a-d)/two, (c+b)/two, (c-b)/two);
+        let (q, r) = (e.hypot(h), f.hypot(g));
+        let (a1, a2) = (g.atan2(f), h.atan2(e));
+        let phase = (a2-a1)/two;
+        let rotation = (a2+a1)/two;
+        let (sx, sy) = (q+r, q-r);
+        let center = Vector2::new(center.x(), center.y());
+        if sy < T::ZERO {
+            // the transformation mirrors the ellipse
+            Self::new(center, Vector2::new(sx, -sy), rotation, -(self.start_angle+phase), -self.sweep_angle)
+        } else {
+            Self::new(center, Vector2::new(sx, sy), rotation, self.start_angle+phase, self.sweep_angle)
+        }
+    }
+}
+impl<T: Number> Segment for ArcSegment2D<T> {
+    type VectorType = Vector2<T>;
+    fn start(&self) -> Self::VectorType {
+        self.point_at_angle(self.start_angle.to_f64())
+    }
+    fn end(&self) -> Self::VectorType {
+        self.point_at_angle(self.end_angle().to_f64())
+    }
+    fn get(&self, t: f64) -> Self::VectorType
+            where <Self::VectorType as VectorSpace>::Scalar: Real {
+        self.point_at_angle(self.angle_at(t))
+    }
+    /// An arc only has its endpoints as control points.
+    fn control_point(&self, idx: usize) -> Vector2<T> {
+        [self.start(), self.end()][idx]
+    }
+    fn order(&self) -> usize { 2 }
+    fn direction_at_start(&self) -> Self::VectorType {
+        self.derivative(0.0)
+    }
+    fn direction_at_end(&self) -> Self::VectorType {
+        self.derivative(1.0)
+    }
+    fn direction_at(&self, t: f64) -> Self::VectorType
+        where <Self::VectorType as VectorSpace>::Scalar: Real {
+        self.derivative(t)
+    }
+    /// Moves the start of the arc to the point on the ellipse at the angle
+    /// of `to`, keeping the end where it is.
+    fn adjust_start_point(&mut self, to: Self::VectorType) {
+        let start = self.local_angle(to);
+        let sweep = self.wrap_sweep(self.end_angle().to_f64() - start);
+        self.start_angle = T::from_f64(start);
+        self.sweep_angle = T::from_f64(sweep);
+    }
+    /// Moves the end of the arc to the point on the ellipse at the angle
+    /// of `to`, keeping the start where it is.
+    fn adjust_end_point(&mut self, to: Self::VectorType) {
+        let sweep = self.wrap_sweep(self.local_angle(to) - self.start_angle.to_f64());
+        self.sweep_angle = T::from_f64(sweep);
+    }
+    #[cfg(feature="alloc")]
+    fn split_in_thirds(&self) -> [alloc::boxed::Box<dyn Segment<VectorType = Self::VectorType>>; 3]
+            where <Self::VectorType as VectorSpace>::Scalar: Real,
+            Self: 'static {
+        let [part1, part2, part3] = self.split_in_thirds_static();
+        [
+            alloc::boxed::Box::new(part1),
+            alloc::boxed::Box::new(part2),
+            alloc::boxed::Box::new(part3),
+        ]
+    }
+}
+impl<T: Number> ArcSegment2D<T> {
+    pub fn split_in_thirds_static(&self) -> [Self; 3] {
+        let third = T::from_f64(self.sweep_angle.to_f64()/3.0);
+        let part = |i: f64| Self { start_angle: T::from_f64(self.angle_at(i/3.0)), sweep_angle: third, ..*self };
+        [part(0.0), part(1.0), part(2.0)]
+    }
+}
+
+impl<T: Number> CalculateCentroid for ArcSegment2D<T> {
+    type Vector = Vector2<T>;
+    /// The centroid of the curve of the arc, found by integrating along its
+    /// length with gauss-legendre quadrature.
+    fn centroid(&self) -> Self::Vector {
+        const PIECES: usize = 16;
+        const NODES: [f64; 3] = [0.11270166537925831, 0.5, 0.8872983346207417];
+        const WEIGHTS: [f64; 3] = [5.0/18.0, 8.0/18.0, 5.0/18.0];
+        let (mut x, mut y, mut length) = (0.0, 0.0, 0.0);
+        for piece in 0..PIECES {
+            for (node, weight) in NODES.iter().zip(WEIGHTS) {
+                let t = (piece as f64 + node)/PIECES as f64;
+                let (sin, cos) = self.angle_at(t).sin_cos();
+                let [dx, dy] = self.ellipse_offset(-sin, cos);
+                let [px, py] = self.ellipse_offset(cos, sin);
+                let speed = (dx*dx + dy*dy).sqrt()*weight;
+                x += px*speed;
+                y += py*speed;
+                length += speed;
+            }
+        }
+        if length == 0.0 {
+            return self.center;
+        }
+        self.center + Vector2::new(T::from_f64(x/length), T::from_f64(y/length))
+    }
+}
+
+impl<T: Number> core::ops::Add<Vector2<T>> for ArcSegment2D<T> {
+    type Output = Self;
+    fn add(self, rhs: Vector2<T>) -> Self::Output {
+        Self { center: self.center+rhs, ..self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::num::{FloatConsts, Zero};
+
+    use crate::{geometry::{ArcSegment2D, CalculateCentroid, Segment}, matrix::Matrix3, vector::{DVec2, DVec3}};
+
+    #[test]
+    fn endpoint_conversion() {
+        let start = DVec2::new(1.0, 0.0);
+        let end = DVec2::new(0.0, 1.0);
+        let small = ArcSegment2D::from_endpoints(start, DVec2::new(1.0, 1.0), 0.0, false, true, end).unwrap();
+        assert!(small.center.epsilon_eq(DVec2::ZERO, 1e-12));
+        assert!((small.sweep_angle - f64::FRAC_PI_2).abs() < 1e-12);
+        let large = ArcSegment2D::from_endpoints(start, DVec2::new(1.0, 1.0), 0.0, true, true, end).unwrap();
+        assert!(large.center.epsilon_eq(DVec2::new(1.0, 1.0), 1e-12));
+        assert!((large.sweep_angle - 3.0*f64::FRAC_PI_2).abs() < 1e-12);
+        assert!(large.start().epsilon_eq(start, 1e-12) && large.end().epsilon_eq(end, 1e-12));
+        assert!(ArcSegment2D::from_endpoints(start, DVec2::ZERO, 0.0, false, true, end).is_none());
+
+        // a semicircle's centroid is 2r/π from the center
+        let half = ArcSegment2D::circular(DVec2::ZERO, 2.0, 0.0, f64::PI);
+        assert!(half.centroid().epsilon_eq(DVec2::new(0.0, 4.0/f64::PI), 1e-9));
+        assert_eq!(half.parameter_of(DVec2::new(0.0, 5.0)), 0.5);
+        assert_eq!(half.parameter_of(DVec2::new(1.0, -0.1)), 0.0);
+    }
+    #[test]
+    fn cubic_approximation() {
+        let arc = ArcSegment2D::new(DVec2::new(1.0, 2.0), DVec2::new(3.0, 1.0), 0.3, 0.2, 4.0);
+        for tolerance in [1e-2, 1e-4, 1e-8] {
+            let count = arc.cubic_count(tolerance);
+            for (i, cubic) in arc.to_cubics(tolerance).enumerate() {
+                let t = (i as f64 + 0.5)/count as f64;
+                assert!(cubic.get(0.5).epsilon_eq(arc.get(t), tolerance));
+            }
+        }
+        let transform = Matrix3::from_transform(DVec2::new(2.0, -1.0), DVec2::new(0.5, 2.0), 1.1);
+        let transformed = arc.transform_by(&transform);
+        for t in [0.0, 0.3, 0.7, 1.0] {
+            let point = transform*DVec3::new(arc.get(t).x(), arc.get(t).y(), 1.0);
+            assert!(transformed.get(t).epsilon_eq(DVec2::new(point.x(), point.y()), 1e-9));
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use core::ops::{Deref, DerefMut};
+
+use affogato_core::{groups::vector_spaces::VectorSpace, num::{FromPrimitive, Number, One, Signed, Zero}, sets::Real};
+
+use crate::{geometry::CalculateCentroid, lerp, vector::Vector2};
+
+use super::{CubicSegment2D, QuadraticSegment2D, Segment};
+#[cfg(feature="alloc")]
+extern crate alloc;
+
+/// The binomial coefficient n choose k.
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |coefficient, i| coefficient*(n - i) as f64/(i + 1) as f64)
+}
+
+/// A bezier curve of any degree, made of `N` control points in any
+/// [`VectorSpace`], so its degree is `N - 1`. The fixed degree segments, like
+/// [`CubicSegment2D`], convert to and from it.
+#[derive(Clone, Copy, Debug)]
+pub struct BezierCurve<V: VectorSpace, const N: usize> {
+    pub points: [V; N],
+}
+impl<V: VectorSpace, const N: usize> BezierCurve<V, N> {
+    pub const fn new(points: [V; N]) -> Self {
+        const { assert!(N > 0, "a bezier curve needs at least one control point") };
+        Self { points }
+    }
+    /// The degree of the polynomial describing the curve.
+    pub const fn degree(&self) -> usize {
+        N - 1
+    }
+    /// The derivative of the curve with respect to t, which is a curve of
+    /// one degree lower, so `M` must be `N - 1`.
+    pub fn derivative<const M: usize>(&self) -> BezierCurve<V, M> {
+        const { assert!(M + 1 == N, "the derivative has one less control point than the curve") };
+        let degree = V::Scalar::from_u32(M as u32);
+        let mut points = [V::ZERO; M];
+        for (i, point) in points.iter_mut().enumerate() {
+            *point = (self.points[i+1] - self.points[i])*degree;
+        }
+        BezierCurve { points }
+    }
+}
+impl<V: VectorSpace, const N: usize> BezierCurve<V, N>
+    where V::Scalar: Real {
+    /// Evaluates the curve at t using de casteljau's algorithm.
+    pub fn evaluate(&self, t: f64) -> V {
+        let t = V::Scalar::from_f64(t);
+        let mut points = self.points;
+        for level in 1..N {
+            for i in 0..N-level {
+                points[i] = lerp(points[i], points[i+1], t);
+            }
+        }
+        points[0]
+    }
+    /// The derivative of the curve at t, without building the whole
+    /// derivative curve.
+    pub fn derivative_at(&self, t: f64) -> V {
+        if N < 2 {
+            return V::ZERO;
+        }
+        let t = V::Scalar::from_f64(t);
+        let mut points = self.points;
+        for level in 1..N-1 {
+            for i in 0..N-level {
+                points[i] = lerp(points[i], points[i+1], t);
+            }
+        }
+        (points[1] - points[0])*V::Scalar::from_u32((N - 1) as u32)
+    }
+    /// The value of each Bernstein basis polynomial of the curve's degree at
+    /// t. The curve is the sum of its control points weighted by these.
+    pub fn bernstein_basis(t: f64) -> [V::Scalar; N] {
+        let t = V::Scalar::from_f64(t);
+        let mut basis = [V::Scalar::ZERO; N];
+        basis[0] = V::Scalar::ONE;
+        // raise the degree one step at a time, the same as de casteljau
+        for degree in 1..N {
+            for i in (0..=degree).rev() {
+                let previous = if i > 0 { basis[i-1]*t } else { V::Scalar::ZERO };
+                let current = if i < degree { basis[i]*(V::Scalar::ONE - t) } else { V::Scalar::ZERO };
+                basis[i] = previous + current;
+            }
+        }
+        basis
+    }
+    /// Splits the curve at t into two curves of the same degree using de
+    /// casteljau's algorithm.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        let t = V::Scalar::from_f64(t);
+        let mut points = self.points;
+        let mut first = [V::ZERO; N];
+        let mut second = [V::ZERO; N];
+        for level in 0..N {
+            first[level] = points[0];
+            second[N-1-level] = points[N-1-level];
+            for i in 0..N-1-level {
+                points[i] = lerp(points[i], points[i+1], t);
+            }
+        }
+        (Self::new(first), Self::new(second))
+    }
+    /// The same curve described with one more control point, so `M` must be
+    /// `N + 1`.
+    pub fn elevate<const M: usize>(&self) -> BezierCurve<V, M> {
+        const { assert!(M == N + 1, "degree elevation adds exactly one control point") };
+        let mut points = [V::ZERO; M];
+        points[0] = self.points[0];
+        points[M-1] = self.points[N-1];
+        for (i, point) in points.iter_mut().enumerate().take(N).skip(1) {
+            let ratio = V::Scalar::from_f64(i as f64/N as f64);
+            *point = self.points[i-1]*ratio + self.points[i]*(V::Scalar::ONE - ratio);
+        }
+        BezierCurve { points }
+    }
+    /// The curve with `M` control points closest to this one in the least
+    /// squares sense, integrated over the whole curve. The endpoints are kept
+    /// so reduced curves still connect, unless `M` is 1 where the single point
+    /// is the average of the curve.
+    pub fn reduce<const M: usize>(&self) -> BezierCurve<V, M> {
+        const { assert!(M > 0 && M <= N, "degree reduction can't add control points") };
+        let (m, n) = (M - 1, N - 1);
+        // the normal equations, where each entry is the integral of the
+        // product of two bernstein polynomials
+        let mut gram = [[V::Scalar::ZERO; M]; M];
+        let mut rhs = [V::ZERO; M];
+        for (i, row) in gram.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry = V::Scalar::from_f64(binomial(m, i)*binomial(m, j)/((2*m + 1) as f64*binomial(2*m, i + j)));
+            }
+            for k in 0..N {
+                let weight = binomial(m, i)*binomial(n, k)/((m + n + 1) as f64*binomial(m + n, i + k));
+                rhs[i] += self.points[k]*V::Scalar::from_f64(weight);
+            }
+        }
+        if M > 1 {
+            for (row, point) in [(0, self.points[0]), (M-1, self.points[N-1])] {
+                gram[row] = [V::Scalar::ZERO; M];
+                gram[row][row] = V::Scalar::ONE;
+                rhs[row] = point;
+            }
+        }
+        // gaussian elimination with partial pivoting
+        for column in 0..M {
+            let pivot = (column..M).fold(column, |pivot, row| {
+                if gram[row][column].abs() > gram[pivot][column].abs() { row } else { pivot }
+            });
+            gram.swap(column, pivot);
+            rhs.swap(column, pivot);
+            for row in column+1..M {
+                let pivot_row = gram[column];
+                let factor = gram[row][column]/pivot_row[column];
+                for (entry, &value) in gram[row].iter_mut().zip(pivot_row.iter()).skip(column) {
+                    *entry -= factor*value;
+                }
+                let value = rhs[column];
+                rhs[row] -= value*factor;
+            }
+        }
+        let mut points = [V::ZERO; M];
+        for row in (0..M).rev() {
+            let mut value = rhs[row];
+            for k in row+1..M {
+                value -= points[k]*gram[row][k];
+            }
+            points[row] = value/gram[row][row];
+        }
+        BezierCurve { points }
+    }
+    /// The coefficients of the curve as a polynomial in t, starting with the
+    /// constant term, so the curve is the sum of `coefficients[k]*t^k`.
+    pub fn to_power_basis(&self) -> [V; N] {
+        let degree = N - 1;
+        let mut coefficients = [V::ZERO; N];
+        for (k, coefficient) in coefficients.iter_mut().enumerate() {
+            let mut sum = V::ZERO;
+            for i in 0..=k {
+                let weight = V::Scalar::from_f64(binomial(k, i));
+                if (k - i) % 2 == 0 {
+                    sum += self.points[i]*weight;
+                } else {
+                    sum -= self.points[i]*weight;
+                }
+            }
+            *coefficient = sum*V::Scalar::from_f64(binomial(degree, k));
+        }
+        coefficients
+    }
+    /// The curve described by the polynomial with the given coefficients,
+    /// starting with the constant term.
+    pub fn from_power_basis(coefficients: [V; N]) -> Self {
+        let degree = N - 1;
+        let mut points = [V::ZERO; N];
+        for (i, point) in points.iter_mut().enumerate() {
+            for (k, &coefficient) in coefficients.iter().enumerate().take(i + 1) {
+                *point += coefficient*V::Scalar::from_f64(binomial(i, k)/binomial(degree, k));
+            }
+        }
+        Self::new(points)
+    }
+}
+impl<V: VectorSpace, const N: usize> Segment for BezierCurve<V, N> {
+    type VectorType = V;
+    fn start(&self) -> V {
+        self.points[0]
+    }
+    fn end(&self) -> V {
+        self.points[N-1]
+    }
+    fn get(&self, t: f64) -> V
+        where <Self::VectorType as VectorSpace>::Scalar: Real {
+        self.evaluate(t)
+    }
+    fn control_point(&self, idx: usize) -> V {
+        self.points[idx]
+    }
+    fn order(&self) -> usize { N }
+    fn direction_at(&self, t: f64) -> V
+        where <Self::VectorType as VectorSpace>::Scalar: Real {
+        let tangent = self.derivative_at(t);
+        if tangent.is_zero() {
+            return self.end() - self.start();
+        }
+        tangent
+    }
+    fn adjust_start_point(&mut self, to: V) {
+        if N > 2 {
+            self.points[1] += to - self.points[0];
+        }
+        self.points[0] = to;
+    }
+    fn adjust_end_point(&mut self, to: V) {
+        if N > 2 {
+            self.points[N-2] += to - self.points[N-1];
+        }
+        self.points[N-1] = to;
+    }
+    #[cfg(feature="alloc")]
+    fn split_in_thirds(&self) -> [alloc::boxed::Box<dyn Segment<VectorType = Self::VectorType>>; 3]
+        where <Self::VectorType as VectorSpace>::Scalar: Real,
+        Self: 'static {
+        let (first, rest) = self.split(1.0/3.0);
+        let (second, third) = rest.split(0.5);
+        [alloc::boxed::Box::new(first), alloc::boxed::Box::new(second), alloc::boxed::Box::new(third)]
+    }
+}
+impl<V: VectorSpace, const N: usize> Deref for BezierCurve<V, N> {
+    type Target = [V; N];
+    fn deref(&self) -> &Self::Target {
+        &self.points
+    }
+}
+impl<V: VectorSpace, const N: usize> DerefMut for BezierCurve<V, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.points
+    }
+}
+impl<V: VectorSpace, const N: usize> CalculateCentroid for BezierCurve<V, N> {
+    type Vector = V;
+    fn centroid(&self) -> Self::Vector {
+        self.points.iter().fold(V::ZERO, |sum, &point| sum + point)/V::Scalar::from_u32(N as u32)
+    }
+}
+impl<V: VectorSpace, const N: usize> core::ops::Add<V> for BezierCurve<V, N> {
+    type Output = Self;
+    fn add(self, rhs: V) -> Self::Output {
+        Self::new(self.points.map(|point| point + rhs))
+    }
+}
+
+impl<T: Number> From<QuadraticSegment2D<T>> for BezierCurve<Vector2<T>, 3> {
+    fn from(value: QuadraticSegment2D<T>) -> Self {
+        Self::new([value.start, value.control, value.end])
+    }
+}
+impl<T: Number> From<BezierCurve<Vector2<T>, 3>> for QuadraticSegment2D<T> {
+    fn from(value: BezierCurve<Vector2<T>, 3>) -> Self {
+        let [start, control, end] = value.points;
+        Self::new(start, control, end)
+    }
+}
+impl<T: Number> From<CubicSegment2D<T>> for BezierCurve<Vector2<T>, 4> {
+    fn from(value: CubicSegment2D<T>) -> Self {
+        Self::new([value.start, value.control1, value.control2, value.end])
+    }
+}
+impl<T: Number> From<BezierCurve<Vector2<T>, 4>> for CubicSegment2D<T> {
+    fn from(value: BezierCurve<Vector2<T>, 4>) -> Self {
+        let [start, control1, control2, end] = value.points;
+        Self::new(start, control1, control2, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::groups::vector_spaces::NormedVectorSpace;
+
+    use crate::{geometry::{BezierCurve, CubicSegment2D, QuadraticSegment2D, Segment}, vector::DVec2};
+
+    #[test]
+    fn degree_changes() {
+        let cubic = CubicSegment2D::new(DVec2::new(0.0, 0.0), DVec2::new(1.0, 3.0), DVec2::new(3.0, -1.0), DVec2::new(4.0, 2.0));
+        let curve = BezierCurve::from(cubic);
+        for i in 0..=10 {
+            let t = i as f64/10.0;
+            assert!((curve.evaluate(t) - Segment::get(&cubic, t)).length() < 1e-12);
+            assert!((curve.derivative::<3>().evaluate(t) - cubic.derivative(t)).length() < 1e-12);
+            let basis = BezierCurve::<DVec2, 4>::bernstein_basis(t);
+            let weighted = curve.points.iter().zip(basis).fold(DVec2::new(0.0, 0.0), |sum, (&point, weight)| sum + point*weight);
+            assert!((weighted - curve.evaluate(t)).length() < 1e-12);
+        }
+
+        // elevating is exact, so reducing it again gives back the same curve
+        let elevated = curve.elevate::<5>().elevate::<6>();
+        let reduced: CubicSegment2D<f64> = elevated.reduce::<4>().into();
+        for (a, b) in reduced.iter().zip(cubic.iter()) {
+            assert!((*a - *b).length() < 1e-9);
+        }
+
+        let power = curve.to_power_basis();
+        assert!((power[1] - cubic.derivative(0.0)).length() < 1e-12);
+        let back = BezierCurve::from_power_basis(power);
+        for (a, b) in back.iter().zip(curve.iter()) {
+            assert!((*a - *b).length() < 1e-12);
+        }
+
+        let quadratic = QuadraticSegment2D::new(DVec2::new(0.0, 0.0), DVec2::new(1.0, 2.0), DVec2::new(2.0, 0.0));
+        let (first, second) = BezierCurve::from(quadratic).split(0.5);
+        let (expected_first, expected_second) = quadratic.split(0.5);
+        assert_eq!(QuadraticSegment2D::from(first).control, expected_first.control);
+        assert_eq!(QuadraticSegment2D::from(second).control, expected_second.control);
+    }
+}
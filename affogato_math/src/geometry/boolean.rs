@@ -0,0 +1,719 @@
+use core::cmp::Ordering;
+
+use affogato_core::{groups::vector_spaces::VectorSpace, num::Number, sets::Real};
+
+use crate::vector::Vector2;
+
+use super::{orient2d, triangulate_monotone, Polygon2D, Rect, Triangle2D, Winding};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// The ways two shapes can be combined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BooleanOperation {
+    /// Everything inside either shape.
+    Union,
+    /// Everything inside both shapes.
+    Intersection,
+    /// Everything inside the first shape but not the second.
+    Difference,
+    /// Everything inside exactly one of the shapes.
+    Xor,
+}
+
+/// One closed boundary of a [`MultiPolygon2D`], along with where it sits in
+/// the hierarchy of contours.
+#[derive(Clone, Debug, Default)]
+pub struct Contour2D<T: Number> {
+    pub polygon: Polygon2D<T>,
+    /// The contour directly around this one.
+    pub parent: Option<usize>,
+    /// The contours directly inside this one, which are the holes of an
+    /// outline or the islands inside a hole.
+    pub children: Vec<usize>,
+    /// How many contours are around this one.
+    pub depth: usize,
+}
+impl<T: Number> Contour2D<T> {
+    pub fn is_hole(&self) -> bool {
+        self.depth%2 == 1
+    }
+}
+
+/// A region of the plane bounded by any amount of contours, which can have
+/// holes with islands inside them. Outlines wind counterclockwise and holes
+/// wind clockwise.
+#[derive(Clone, Debug, Default)]
+pub struct MultiPolygon2D<T: Number> {
+    pub contours: Vec<Contour2D<T>>,
+}
+impl<T: Number> MultiPolygon2D<T> {
+    pub fn is_empty(&self) -> bool {
+        self.contours.is_empty()
+    }
+    /// Every outline and hole in the shape.
+    pub fn polygons(&self) -> impl Iterator<Item = &Polygon2D<T>> + '_ {
+        self.contours.iter().map(|contour| &contour.polygon)
+    }
+    /// Whether `point` is inside an odd number of contours.
+    pub fn contains(&self, point: Vector2<T>) -> bool {
+        self.polygons().filter(|polygon| polygon.contains(point)).count()%2 == 1
+    }
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        self.polygons().filter_map(|polygon| polygon.bounds()).reduce(|bounds, other| bounds.merge(&other))
+    }
+    /// Links every contour to its parent and winds it the right way for its
+    /// depth.
+    fn link(&mut self) {
+        for i in 0..self.contours.len() {
+            if let Some(parent) = self.contours[i].parent {
+                self.contours[parent].children.push(i);
+            }
+            let contour = &mut self.contours[i];
+            let expected = if contour.is_hole() { Winding::Clockwise } else { Winding::CounterClockwise };
+            if contour.polygon.winding().is_some_and(|winding| winding != expected) {
+                contour.polygon.reverse();
+            }
+        }
+    }
+}
+impl<T: Real> MultiPolygon2D<T> {
+    /// Builds the hierarchy of the contours from which are inside of which.
+    /// The contours can touch but must not cross, the result of a
+    /// [`boolean`](Self::boolean) operation can be used to get such contours
+    /// from any others.
+    pub fn new(polygons: Vec<Polygon2D<T>>) -> Self {
+        let polygons: Vec<Polygon2D<T>> = polygons.into_iter().filter(|polygon| polygon.len() >= 3).collect();
+        let around: Vec<Vec<usize>> = polygons.iter().enumerate().map(|(i, polygon)| {
+            (0..polygons.len()).filter(|&j| j != i && polygons[j].contains(polygon[0])).collect()
+        }).collect();
+        let mut shape = Self {
+            contours: polygons.into_iter().enumerate().map(|(i, polygon)| {
+                let depth = around[i].len();
+                let parent = around[i].iter().copied().find(|&j| around[j].len() + 1 == depth);
+                Contour2D { polygon, parent, children: Vec::new(), depth }
+            }).collect(),
+        };
+        shape.link();
+        shape
+    }
+    /// The area inside the shape, not counting its holes.
+    pub fn area(&self) -> T {
+        self.polygons().fold(T::ZERO, |area, polygon| area + polygon.signed_area())
+    }
+    /// Combines two shapes with the sweep line algorithm from "A simple
+    /// algorithm for Boolean operations on polygons" by Martínez, Rueda and
+    /// Feito. The contours of each shape are filled with the even-odd rule,
+    /// and can cross themselves and each other, but a contour shouldn't run
+    /// back along its own edges.
+    pub fn boolean(&self, other: &Self, operation: BooleanOperation) -> Self {
+        let (Some(subject_bounds), Some(clipping_bounds)) = (self.bounds(), other.bounds()) else {
+            return match operation {
+                BooleanOperation::Intersection => Self { contours: Vec::new() },
+                BooleanOperation::Difference => self.clone(),
+                BooleanOperation::Union | BooleanOperation::Xor if self.is_empty() => other.clone(),
+                BooleanOperation::Union | BooleanOperation::Xor => self.clone(),
+            };
+        };
+        if !subject_bounds.intersect(&clipping_bounds) {
+            return match operation {
+                BooleanOperation::Intersection => Self { contours: Vec::new() },
+                BooleanOperation::Difference => self.clone(),
+                BooleanOperation::Union | BooleanOperation::Xor => {
+                    let offset = self.contours.len();
+                    let mut shape = self.clone();
+                    shape.contours.extend(other.contours.iter().map(|contour| Contour2D {
+                        polygon: contour.polygon.clone(),
+                        parent: contour.parent.map(|parent| parent + offset),
+                        children: contour.children.iter().map(|child| child + offset).collect(),
+                        depth: contour.depth,
+                    }));
+                    shape
+                },
+            };
+        }
+        let mut sweep = BooleanSweep {
+            events: Vec::new(),
+            queue: Vec::new(),
+            status: Vec::new(),
+            operation,
+            contour_count: 0,
+        };
+        for polygon in self.polygons() {
+            sweep.add_polygon(polygon, true);
+        }
+        for polygon in other.polygons() {
+            sweep.add_polygon(polygon, false);
+        }
+        // nothing right of either shape can be in an intersection
+        let limit = match operation {
+            BooleanOperation::Intersection => subject_bounds.maximum().x().min(clipping_bounds.maximum().x()),
+            BooleanOperation::Difference => subject_bounds.maximum().x(),
+            BooleanOperation::Union | BooleanOperation::Xor => T::INFINITY,
+        };
+        let sorted = sweep.subdivide(limit);
+        let mut shape = Self { contours: sweep.connect(&sorted) };
+        shape.link();
+        shape
+    }
+    pub fn union(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOperation::Union)
+    }
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOperation::Intersection)
+    }
+    pub fn difference(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOperation::Difference)
+    }
+    pub fn xor(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOperation::Xor)
+    }
+    /// Triangle indices covering the shape, indexing the vertices of every
+    /// contour in order.
+    pub fn triangulate(&self) -> Vec<u32> {
+        let mut starts = Vec::with_capacity(self.contours.len());
+        let mut start = 0;
+        for contour in self.contours.iter() {
+            starts.push(start);
+            start += contour.polygon.len() as u32;
+        }
+        let mut triangles = Vec::new();
+        for (i, contour) in self.contours.iter().enumerate().filter(|(_, contour)| !contour.is_hole()) {
+            let holes: Vec<&[Vector2<T>]> = contour.children.iter().map(|&hole| self.contours[hole].polygon.vertices.as_slice()).collect();
+            // map the indices into the outline followed by its holes back to
+            // the contours they came from
+            let rings: Vec<(u32, u32)> = core::iter::once(i).chain(contour.children.iter().copied())
+                .map(|ring| (starts[ring], self.contours[ring].polygon.len() as u32))
+                .collect();
+            triangles.extend(triangulate_monotone(&contour.polygon.vertices, &holes).into_iter().map(|mut index| {
+                for &(start, len) in rings.iter() {
+                    if index < len {
+                        return start + index;
+                    }
+                    index -= len;
+                }
+                index
+            }));
+        }
+        triangles
+    }
+}
+impl<T: Number> From<Polygon2D<T>> for MultiPolygon2D<T> {
+    fn from(polygon: Polygon2D<T>) -> Self {
+        let mut shape = Self { contours: alloc::vec![Contour2D { polygon, parent: None, children: Vec::new(), depth: 0 }] };
+        shape.link();
+        shape
+    }
+}
+impl<T: Number> From<Rect<T>> for MultiPolygon2D<T> {
+    fn from(rect: Rect<T>) -> Self {
+        let (min, max) = (*rect.minimum(), *rect.maximum());
+        Polygon2D::new(alloc::vec![min, Vector2::new(max.x(), min.y()), max, Vector2::new(min.x(), max.y())]).into()
+    }
+}
+impl<T: Number> From<Triangle2D<T>> for MultiPolygon2D<T> {
+    fn from(triangle: Triangle2D<T>) -> Self {
+        Polygon2D::new(alloc::vec![triangle[0], triangle[1], triangle[2]]).into()
+    }
+}
+
+enum EdgeIntersection<T: Real> {
+    None,
+    Point(Vector2<T>),
+    Overlap,
+}
+
+fn intersect_edges<T: Real>(a1: Vector2<T>, a2: Vector2<T>, b1: Vector2<T>, b2: Vector2<T>) -> EdgeIntersection<T> {
+    let (a, b, offset) = (a2-a1, b2-b1, b1-a1);
+    let cross = a.cross(&b);
+    if cross != T::ZERO {
+        // keep endpoints exact so they match the events at them
+        let between = |point: Vector2<T>, start: Vector2<T>, end: Vector2<T>| (start-point).dot(&(end-point)) <= T::ZERO;
+        for (point, start, end) in [(a1, b1, b2), (a2, b1, b2), (b1, a1, a2), (b2, a1, a2)] {
+            if orient2d(start, end, point) == T::ZERO {
+                return if between(point, start, end) { EdgeIntersection::Point(point) } else { EdgeIntersection::None };
+            }
+        }
+        let s = offset.cross(&b)/cross;
+        let t = offset.cross(&a)/cross;
+        if s < T::ZERO || s > T::ONE || t < T::ZERO || t > T::ONE {
+            return EdgeIntersection::None;
+        }
+        return EdgeIntersection::Point(a1 + a*s);
+    }
+    if offset.cross(&a) != T::ZERO {
+        return EdgeIntersection::None;
+    }
+    let length = a.dot(&a);
+    let start = a.dot(&offset)/length;
+    let end = start + a.dot(&b)/length;
+    let (min, max) = (start.min(end), start.max(end));
+    if min > T::ONE || max < T::ZERO {
+        EdgeIntersection::None
+    } else if min == T::ONE {
+        EdgeIntersection::Point(a2)
+    } else if max == T::ZERO {
+        EdgeIntersection::Point(a1)
+    } else {
+        EdgeIntersection::Overlap
+    }
+}
+
+/// How an edge overlapping another counts toward the result, only one of the
+/// two is kept.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EdgeKind {
+    Normal,
+    NonContributing,
+    /// Both shapes are on the same side of the edge.
+    SameTransition,
+    /// The shapes are on opposite sides of the edge.
+    DifferentTransition,
+}
+
+/// One end of an edge, where the sweep line reaches it or leaves it.
+#[derive(Clone, Copy)]
+struct SweepEvent<T: Real> {
+    point: Vector2<T>,
+    /// Whether this is the left end, where the edge enters the sweep line.
+    left: bool,
+    /// The event at the other end of the edge.
+    other: usize,
+    is_subject: bool,
+    contour: usize,
+    kind: EdgeKind,
+    /// Whether going up across the edge leaves its own shape.
+    in_out: bool,
+    /// Whether the closest edge of the other shape below this one is a way
+    /// out of it, meaning this edge is outside the other shape.
+    other_in_out: bool,
+    /// The closest edge below this one that is part of the result.
+    previous_in_result: Option<usize>,
+    /// Whether going up across the edge enters the result, or 0 when the edge
+    /// isn't part of it.
+    result_transition: i8,
+    output_contour: Option<usize>,
+}
+
+struct BooleanSweep<T: Real> {
+    events: Vec<SweepEvent<T>>,
+    /// The events still to come, sorted so the next one is last.
+    queue: Vec<usize>,
+    /// The left events of the edges crossing the sweep line, from the bottom
+    /// up.
+    status: Vec<usize>,
+    operation: BooleanOperation,
+    contour_count: usize,
+}
+impl<T: Real> BooleanSweep<T> {
+    fn other_point(&self, event: usize) -> Vector2<T> {
+        self.events[self.events[event].other].point
+    }
+    /// Whether `point` is below the edge of the event.
+    fn is_below(&self, event: usize, point: Vector2<T>) -> bool {
+        let (here, there) = (self.events[event].point, self.other_point(event));
+        if self.events[event].left {
+            orient2d(here, there, point) > T::ZERO
+        } else {
+            orient2d(there, here, point) > T::ZERO
+        }
+    }
+    fn is_vertical(&self, event: usize) -> bool {
+        self.events[event].point.x() == self.other_point(event).x()
+    }
+    /// The order the sweep line reaches events in, from left to right and
+    /// bottom to top, with right ends first and lower edges first where they
+    /// are at the same point.
+    fn compare_events(&self, a: usize, b: usize) -> Ordering {
+        let (first, second) = (&self.events[a], &self.events[b]);
+        if first.point.x() != second.point.x() {
+            return if first.point.x() > second.point.x() { Ordering::Greater } else { Ordering::Less };
+        }
+        if first.point.y() != second.point.y() {
+            return if first.point.y() > second.point.y() { Ordering::Greater } else { Ordering::Less };
+        }
+        if first.left != second.left {
+            return if first.left { Ordering::Greater } else { Ordering::Less };
+        }
+        let other = self.other_point(b);
+        if orient2d(first.point, self.other_point(a), other) != T::ZERO {
+            return if self.is_below(a, other) { Ordering::Less } else { Ordering::Greater };
+        }
+        if !first.is_subject && second.is_subject { Ordering::Greater } else { Ordering::Less }
+    }
+    /// The order of edges along the sweep line, from the bottom up.
+    fn compare_segments(&self, a: usize, b: usize) -> Ordering {
+        if a == b {
+            return Ordering::Equal;
+        }
+        let (first, second) = (&self.events[a], &self.events[b]);
+        let (first_other, second_other) = (self.other_point(a), self.other_point(b));
+        if orient2d(first.point, first_other, second.point) != T::ZERO || orient2d(first.point, first_other, second_other) != T::ZERO {
+            if first.point == second.point {
+                return if self.is_below(a, second_other) { Ordering::Less } else { Ordering::Greater };
+            }
+            if first.point.x() == second.point.x() {
+                return if first.point.y() < second.point.y() { Ordering::Less } else { Ordering::Greater };
+            }
+            // compare at whichever left end the sweep line reached last, or
+            // at its other end when it lies on the older edge
+            if self.compare_events(a, b) == Ordering::Greater {
+                let point = if orient2d(second.point, second_other, first.point) == T::ZERO { first_other } else { first.point };
+                return if self.is_below(b, point) { Ordering::Greater } else { Ordering::Less };
+            }
+            let point = if orient2d(first.point, first_other, second.point) == T::ZERO { second_other } else { second.point };
+            return if self.is_below(a, point) { Ordering::Less } else { Ordering::Greater };
+        }
+        if first.is_subject != second.is_subject {
+            return if first.is_subject { Ordering::Less } else { Ordering::Greater };
+        }
+        if first.point == second.point {
+            if first_other == second_other {
+                return Ordering::Equal;
+            }
+            return if first.contour > second.contour { Ordering::Greater } else { Ordering::Less };
+        }
+        if self.compare_events(a, b) == Ordering::Greater { Ordering::Greater } else { Ordering::Less }
+    }
+    fn push_event(&mut self, event: SweepEvent<T>) -> usize {
+        self.events.push(event);
+        self.events.len() - 1
+    }
+    fn enqueue(&mut self, event: usize) {
+        let position = self.queue.partition_point(|&other| self.compare_events(other, event) == Ordering::Greater);
+        self.queue.insert(position, event);
+    }
+    fn add_polygon(&mut self, polygon: &Polygon2D<T>, is_subject: bool) {
+        let contour = self.contour_count;
+        self.contour_count += 1;
+        for edge in polygon.edges().filter(|edge| edge.start != edge.end) {
+            let event = SweepEvent {
+                point: edge.start,
+                left: false,
+                other: self.events.len() + 1,
+                is_subject,
+                contour,
+                kind: EdgeKind::Normal,
+                in_out: false,
+                other_in_out: false,
+                previous_in_result: None,
+                result_transition: 0,
+                output_contour: None,
+            };
+            let start = self.push_event(event);
+            let end = self.push_event(SweepEvent { point: edge.end, other: start, ..event });
+            let left = if self.compare_events(start, end) == Ordering::Greater { end } else { start };
+            self.events[left].left = true;
+            self.enqueue(start);
+            self.enqueue(end);
+        }
+    }
+    /// Splits the edge of a left event at `point`.
+    fn divide(&mut self, event: usize, point: Vector2<T>) {
+        let other = self.events[event].other;
+        let right = self.push_event(SweepEvent { point, left: false, other: event, kind: EdgeKind::Normal, ..self.events[event] });
+        let left = self.push_event(SweepEvent { point, left: true, other, kind: EdgeKind::Normal, ..self.events[event] });
+        // rounding can put the split point right of the end of the edge
+        if self.compare_events(left, other) == Ordering::Greater {
+            self.events[other].left = true;
+            self.events[left].left = false;
+        }
+        self.events[other].other = left;
+        self.events[event].other = right;
+        self.enqueue(left);
+        self.enqueue(right);
+    }
+    fn in_result(&self, event: usize) -> bool {
+        let event = &self.events[event];
+        match (event.kind, self.operation) {
+            (EdgeKind::Normal, BooleanOperation::Intersection) => !event.other_in_out,
+            (EdgeKind::Normal, BooleanOperation::Union) => event.other_in_out,
+            (EdgeKind::Normal, BooleanOperation::Difference) => event.is_subject == event.other_in_out,
+            (EdgeKind::Normal, BooleanOperation::Xor) => true,
+            (EdgeKind::SameTransition, operation) => matches!(operation, BooleanOperation::Intersection | BooleanOperation::Union),
+            (EdgeKind::DifferentTransition, operation) => operation == BooleanOperation::Difference,
+            (EdgeKind::NonContributing, _) => false,
+        }
+    }
+    fn compute_fields(&mut self, event: usize, previous: Option<usize>) {
+        match previous {
+            None => {
+                self.events[event].in_out = false;
+                self.events[event].other_in_out = true;
+                self.events[event].previous_in_result = None;
+            },
+            Some(previous) => {
+                let below = self.events[previous];
+                if self.events[event].is_subject == below.is_subject {
+                    self.events[event].in_out = !below.in_out;
+                    self.events[event].other_in_out = below.other_in_out;
+                } else {
+                    self.events[event].in_out = !below.other_in_out;
+                    self.events[event].other_in_out = if self.is_vertical(previous) { !below.in_out } else { below.in_out };
+                }
+                self.events[event].previous_in_result = if !self.in_result(previous) || self.is_vertical(previous) {
+                    below.previous_in_result
+                } else {
+                    Some(previous)
+                };
+            },
+        }
+        self.events[event].result_transition = if self.in_result(event) {
+            let this_in = !self.events[event].in_out;
+            // where the edges of both shapes overlap, the other shape changes
+            // sides here as well
+            let that_in = match self.events[event].kind {
+                EdgeKind::SameTransition => this_in,
+                EdgeKind::DifferentTransition => !this_in,
+                _ => !self.events[event].other_in_out,
+            };
+            let is_in = match self.operation {
+                BooleanOperation::Intersection => this_in && that_in,
+                BooleanOperation::Union => this_in || that_in,
+                BooleanOperation::Xor => this_in != that_in,
+                BooleanOperation::Difference if self.events[event].is_subject => this_in && !that_in,
+                BooleanOperation::Difference => that_in && !this_in,
+            };
+            if is_in { 1 } else { -1 }
+        } else {
+            0
+        };
+    }
+    /// Splits two neighbouring edges where they cross, giving 2 when they
+    /// overlap from the same left end.
+    fn possible_intersection(&mut self, a: usize, b: usize) -> u8 {
+        let (a1, a2, b1, b2) = (self.events[a].point, self.other_point(a), self.events[b].point, self.other_point(b));
+        match intersect_edges(a1, a2, b1, b2) {
+            EdgeIntersection::None => 0,
+            EdgeIntersection::Point(_) if a1 == b1 || a2 == b2 => 0,
+            EdgeIntersection::Point(point) => {
+                if point != a1 && point != a2 {
+                    self.divide(a, point);
+                }
+                if point != b1 && point != b2 {
+                    self.divide(b, point);
+                }
+                1
+            },
+            // the even-odd rule makes overlaps within a shape cancel out
+            EdgeIntersection::Overlap if self.events[a].is_subject == self.events[b].is_subject => 0,
+            EdgeIntersection::Overlap => {
+                let mut order = Vec::with_capacity(4);
+                let left_coincide = a1 == b1;
+                let right_coincide = a2 == b2;
+                if !left_coincide {
+                    order.extend(if self.compare_events(a, b) == Ordering::Greater { [b, a] } else { [a, b] });
+                }
+                let (a_other, b_other) = (self.events[a].other, self.events[b].other);
+                if !right_coincide {
+                    order.extend(if self.compare_events(a_other, b_other) == Ordering::Greater { [b_other, a_other] } else { [a_other, b_other] });
+                }
+                if left_coincide {
+                    self.events[b].kind = EdgeKind::NonContributing;
+                    self.events[a].kind = if self.events[b].in_out == self.events[a].in_out {
+                        EdgeKind::SameTransition
+                    } else {
+                        EdgeKind::DifferentTransition
+                    };
+                    if !right_coincide {
+                        self.divide(self.events[order[1]].other, self.events[order[0]].point);
+                    }
+                    return 2;
+                }
+                if right_coincide {
+                    self.divide(order[0], self.events[order[1]].point);
+                } else if order[0] != self.events[order[3]].other {
+                    // the edges overlap partially
+                    self.divide(order[0], self.events[order[1]].point);
+                    self.divide(order[1], self.events[order[2]].point);
+                } else {
+                    // one edge covers the other
+                    self.divide(order[0], self.events[order[1]].point);
+                    self.divide(self.events[order[3]].other, self.events[order[2]].point);
+                }
+                3
+            },
+        }
+    }
+    /// Sweeps across both shapes up to `limit`, splitting edges wherever
+    /// they cross and working out which are part of the result, giving the
+    /// events in the order they were reached.
+    fn subdivide(&mut self, limit: T) -> Vec<usize> {
+        let mut sorted = Vec::new();
+        while let Some(event) = self.queue.pop() {
+            if self.events[event].point.x() > limit {
+                break;
+            }
+            sorted.push(event);
+            if self.events[event].left {
+                let position = self.status.partition_point(|&other| self.compare_segments(other, event) == Ordering::Less);
+                self.status.insert(position, event);
+                let previous = position.checked_sub(1).map(|below| self.status[below]);
+                let next = self.status.get(position+1).copied();
+                self.compute_fields(event, previous);
+                if let Some(next) = next && self.possible_intersection(event, next) == 2 {
+                    self.compute_fields(event, previous);
+                    self.compute_fields(next, Some(event));
+                }
+                if let Some(previous) = previous && self.possible_intersection(previous, event) == 2 {
+                    let before = position.checked_sub(2).map(|below| self.status[below]);
+                    self.compute_fields(previous, before);
+                    self.compute_fields(event, Some(previous));
+                }
+            } else {
+                let left = self.events[event].other;
+                if let Some(position) = self.status.iter().position(|&other| other == left) {
+                    let previous = position.checked_sub(1).map(|below| self.status[below]);
+                    let next = self.status.get(position+1).copied();
+                    self.status.remove(position);
+                    if let (Some(previous), Some(next)) = (previous, next) {
+                        self.possible_intersection(previous, next);
+                    }
+                }
+            }
+        }
+        sorted
+    }
+    /// Finds the parent and depth of a contour starting at `event` from the
+    /// closest edge of the result below it.
+    fn contour_context(&self, event: usize, contours: &[Contour2D<T>]) -> (Option<usize>, usize) {
+        let Some(below) = self.events[event].previous_in_result else {
+            return (None, 0);
+        };
+        let Some(lower) = self.events[below].output_contour else {
+            return (None, 0);
+        };
+        let lower_contour = &contours[lower];
+        // directly above the lower contour is either inside or outside of it
+        let inside_lower = (self.events[below].result_transition > 0) != lower_contour.is_hole();
+        let parent = if inside_lower { Some(lower) } else { lower_contour.parent };
+        (parent, parent.map_or(0, |parent| contours[parent].depth + 1))
+    }
+    /// Joins the edges in the result into contours, walking each one with
+    /// the result on its left and taking the sharpest turn where several
+    /// meet, so contours touching at a point stay separate.
+    fn connect(&mut self, sorted: &[usize]) -> Vec<Contour2D<T>> {
+        let mut result: Vec<usize> = sorted.iter().copied().filter(|&event| {
+            let left = if self.events[event].left { event } else { self.events[event].other };
+            self.events[left].result_transition != 0
+        }).collect();
+        // overlapping edges can leave some events out of order
+        result.sort_by(|&a, &b| self.compare_events(a, b));
+        // events at the same point are next to each other after sorting
+        let mut points: Vec<Vector2<T>> = Vec::new();
+        let mut vertex_of = alloc::vec![0; self.events.len()];
+        for &event in result.iter() {
+            let point = self.events[event].point;
+            if points.last() != Some(&point) {
+                points.push(point);
+            }
+            vertex_of[event] = points.len() - 1;
+        }
+        // each edge goes from the vertex it leaves to the one it reaches,
+        // stored along with the left event it came from
+        let edges: Vec<(usize, usize, usize)> = result.iter().copied().filter(|&event| self.events[event].left).map(|event| {
+            let (left, right) = (vertex_of[event], vertex_of[self.events[event].other]);
+            if self.events[event].result_transition > 0 { (left, right, event) } else { (right, left, event) }
+        }).collect();
+
+        let mut outgoing: Vec<Vec<usize>> = alloc::vec![Vec::new(); points.len()];
+        for (i, &(from, _, _)) in edges.iter().enumerate() {
+            outgoing[from].push(i);
+        }
+        let mut used = alloc::vec![false; edges.len()];
+        let mut contours: Vec<Contour2D<T>> = Vec::new();
+        for first in 0..edges.len() {
+            if used[first] {
+                continue;
+            }
+            let id = contours.len();
+            let (parent, depth) = self.contour_context(edges[first].2, &contours);
+            let mut vertices = Vec::new();
+            let mut walked = Vec::new();
+            let mut edge = first;
+            while !used[edge] {
+                used[edge] = true;
+                let (from, to, event) = edges[edge];
+                walked.push(event);
+                vertices.push(points[from]);
+                // the next edge is the first one clockwise from the way back
+                let back = points[from] - points[to];
+                let next = outgoing[to].iter().copied().filter(|&next| !used[next] || next == first).map(|next| {
+                    let direction = points[edges[next].1] - points[to];
+                    let angle = direction.cross(&back).atan2(direction.dot(&back));
+                    (if angle <= T::ZERO { angle + T::TAU } else { angle }, next)
+                }).reduce(|best, candidate| if candidate.0 < best.0 { candidate } else { best });
+                let Some((_, next)) = next else {
+                    break;
+                };
+                edge = next;
+            }
+            // rounding can leave edges that don't close up into a contour
+            if vertices.len() < 3 {
+                continue;
+            }
+            for event in walked {
+                self.events[event].output_contour = Some(id);
+            }
+            contours.push(Contour2D { polygon: Polygon2D::new(vertices), parent, children: Vec::new(), depth });
+        }
+        contours
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::{MultiPolygon2D, Polygon2D, Rect, Triangle2D}, vector::DVec2};
+
+    fn square(min: DVec2, size: f64) -> MultiPolygon2D<f64> {
+        Rect::new(min, min + DVec2::new(size, size)).into()
+    }
+
+    #[test]
+    fn polygon_booleans() {
+        let a = square(DVec2::new(0.0, 0.0), 2.0);
+        let b = square(DVec2::new(1.0, 1.0), 2.0);
+        assert_eq!(a.union(&b).area(), 7.0);
+        assert_eq!(a.intersection(&b).area(), 1.0);
+        assert_eq!(a.difference(&b).area(), 3.0);
+        assert_eq!(a.xor(&b).area(), 6.0);
+        assert_eq!(a.union(&b).contours.len(), 1);
+        assert_eq!(a.intersection(&b).polygons().next().map(|polygon| polygon.len()), Some(4));
+
+        // cutting a hole leaves an outline with one child
+        let frame = square(DVec2::new(0.0, 0.0), 4.0).difference(&square(DVec2::new(1.0, 1.0), 2.0));
+        assert_eq!(frame.area(), 12.0);
+        assert_eq!(frame.contours.len(), 2);
+        let hole = frame.contours.iter().position(|contour| contour.is_hole()).unwrap();
+        let outline = frame.contours[hole].parent.unwrap();
+        assert_eq!(frame.contours[outline].children, [hole]);
+        assert!(!frame.contains(DVec2::new(2.0, 2.0)) && frame.contains(DVec2::new(0.5, 2.0)));
+        assert_eq!(frame.triangulate().len(), 8*3);
+
+        // an island inside the hole, and shapes sharing edges
+        let island = frame.union(&square(DVec2::new(1.5, 1.5), 1.0));
+        assert_eq!(island.area(), 13.0);
+        assert_eq!(island.contours.iter().map(|contour| contour.depth).max(), Some(2));
+        let halves = square(DVec2::new(0.0, 0.0), 2.0).union(&Rect::new(DVec2::new(2.0, 0.0), DVec2::new(4.0, 2.0)).into());
+        assert_eq!(halves.area(), 8.0);
+        assert_eq!(halves.contours.len(), 1);
+        let triangle: MultiPolygon2D<f64> = Triangle2D::new(DVec2::new(0.0, 0.0), DVec2::new(4.0, 0.0), DVec2::new(0.0, 4.0)).into();
+        assert_eq!(square(DVec2::new(0.0, 0.0), 2.0).intersection(&triangle).area(), 4.0);
+
+        // vertices of one shape lying on edges of the other
+        let points = |points: &[(f64, f64)]| MultiPolygon2D::from(Polygon2D::new(points.iter().map(|&(x, y)| DVec2::new(x, y)).collect()));
+        let kite = points(&[(-3.0, 2.0), (-1.0, 0.0), (3.0, 0.0), (-2.0, 3.0)]);
+        let cap = points(&[(2.0, 2.0), (1.0, 3.0), (0.0, 5.0), (-2.0, 3.0), (-2.0, 1.0), (0.0, 1.0), (1.0, 1.0)]);
+        let (union, intersection) = (kite.union(&cap), kite.intersection(&cap));
+        assert_eq!(union.contours.len(), 1);
+        assert!((union.area() + intersection.area() - kite.area() - cap.area()).abs() < 1e-9);
+        assert!((kite.xor(&cap).area() - union.area() + intersection.area()).abs() < 1e-9);
+
+        let nested = MultiPolygon2D::new(super::alloc::vec![
+            Polygon2D::new(super::alloc::vec![DVec2::new(1.0, 1.0), DVec2::new(2.0, 1.0), DVec2::new(2.0, 2.0), DVec2::new(1.0, 2.0)]),
+            Polygon2D::new(super::alloc::vec![DVec2::new(0.0, 0.0), DVec2::new(3.0, 0.0), DVec2::new(3.0, 3.0), DVec2::new(0.0, 3.0)]),
+        ]);
+        assert_eq!(nested.contours[0].parent, Some(1));
+        assert_eq!(nested.area(), 8.0);
+    }
+}
@@ -0,0 +1,765 @@
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{Bounds, Zero}, sets::Real};
+
+use crate::{algebra::Quaternion, vector::{Vector2, Vector3}};
+
+use super::{ArcSegment2D, BezierCurve, Capsule, Capsule2D, CalculateCentroid, Circle, Cone, CubicSegment2D, CubicSegment3D, Cylinder, Ellipse, Frustum, LinearSegment2D, LinearSegment3D, OrientedBox3D, OrientedRect, QuadraticSegment2D, QuadraticSegment3D, Rect, Rect3D, RoundedRect, Segment2D, Segment3D, Sphere, Tetrahedron, Torus, Triangle2D, Triangle3D};
+#[cfg(feature="alloc")]
+use super::{MultiPolygon2D, Path2D, Polygon2D, Polygon3D, SubPath2D};
+#[cfg(feature="alloc")]
+extern crate alloc;
+
+/// Shapes that can be wrapped in an axis aligned box and a circle or sphere,
+/// so broadphases and culling can take any of them.
+pub trait BoundingVolume {
+    /// A [`Rect`] or [`Rect3D`].
+    type Bounds;
+    /// A [`Circle`] or [`Sphere`].
+    type Sphere;
+    /// The smallest axis aligned box holding the shape, except for a
+    /// [`BezierCurve`] where it is the box around its control points.
+    fn bounding_box(&self) -> Self::Bounds;
+    /// A circle or sphere holding the shape. It is the smallest one for
+    /// shapes made of points and for the solids where it has a closed form,
+    /// curves give the smallest one around their control points.
+    fn bounding_sphere(&self) -> Self::Sphere;
+}
+
+/// Whether `point` is in `sphere`, letting it be a little outside so points
+/// that were used to make the sphere always are.
+fn encloses<T: Real, V: NormedVectorSpace<Scalar = T>>(center: V, radius: T, point: V) -> bool {
+    (point-center).length() <= radius*(T::ONE + T::EPSILON*T::from_f64(64.0)) + T::EPSILON
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a%b) }
+}
+
+/// Welzl's algorithm over `points` visited in `order`, with `through`
+/// making the sphere through up to `limit` points on its boundary.
+struct Welzl<'a, V, O, F, E> {
+    points: &'a [V],
+    order: O,
+    limit: usize,
+    through: F,
+    encloses: E,
+}
+
+impl<V: Copy, S: Copy, O: Fn(usize) -> usize, F: Fn(&[V]) -> Option<S>, E: Fn(S, V) -> bool> Welzl<'_, V, O, F, E> {
+    /// The sphere of the first `count` points with `support` on its
+    /// boundary, where each point that doesn't fit in the sphere of the ones
+    /// before it is put on the boundary too.
+    fn enclose(&self, support: &mut [V; 4], supported: usize, count: usize) -> Option<S> {
+        let mut sphere = (self.through)(&support[..supported]);
+        if supported == self.limit {
+            return sphere;
+        }
+        for i in 0..count {
+            let point = self.points[(self.order)(i)];
+            if !sphere.is_some_and(|sphere| (self.encloses)(sphere, point)) {
+                support[supported] = point;
+                sphere = self.enclose(support, supported+1, i);
+            }
+        }
+        sphere
+    }
+}
+
+/// Visits the points with a stride coprime to their count, so points in
+/// sorted order don't hit the slow worst case of Welzl's algorithm as easily.
+/// The products are worked out in u64, they'd overflow a 32 bit usize with
+/// under a hundred thousand points.
+fn scrambled(count: usize) -> impl Fn(usize) -> usize {
+    let mut stride = (count as u64*5/8) | 1;
+    while gcd(stride, count as u64) != 1 {
+        stride += 1;
+    }
+    move |i| (i as u64*stride%count as u64) as usize
+}
+
+fn circle_through<T: Real>(points: &[Vector2<T>]) -> Option<Circle<T>> {
+    match *points {
+        [a] => Some(Circle::new(a, T::ZERO)),
+        [a, b] => Some(Circle::new((a+b)*T::from_f64(0.5), (b-a).length()*T::from_f64(0.5))),
+        [a, b, c] => {
+            let (ab, ac) = (b-a, c-a);
+            let determinant = T::from_f64(2.0)*ab.cross(&ac);
+            if determinant == T::ZERO {
+                // collinear, so the two farthest apart are on the circle
+                return [(a, b), (a, c), (b, c)].into_iter()
+                    .filter_map(|(p, q)| circle_through(&[p, q]))
+                    .reduce(|best, circle| if circle.radius > best.radius { circle } else { best });
+            }
+            let (ab2, ac2) = (ab.length_squared(), ac.length_squared());
+            let offset = Vector2::new(ac.y()*ab2 - ab.y()*ac2, ab.x()*ac2 - ac.x()*ab2)/determinant;
+            Some(Circle::new(a + offset, offset.length()))
+        }
+        _ => None,
+    }
+}
+
+fn sphere_through<T: Real>(points: &[Vector3<T>]) -> Option<Sphere<T>> {
+    match *points {
+        [a] => Some(Sphere::new(a, T::ZERO)),
+        [a, b] => Some(Sphere::new((a+b)*T::from_f64(0.5), (b-a).length()*T::from_f64(0.5))),
+        [a, b, c] => {
+            let (ab, ac) = (b-a, c-a);
+            let normal = ab.cross(&ac);
+            let determinant = T::from_f64(2.0)*normal.length_squared();
+            if determinant == T::ZERO {
+                return [(a, b), (a, c), (b, c)].into_iter()
+                    .filter_map(|(p, q)| sphere_through(&[p, q]))
+                    .reduce(|best, sphere| if sphere.radius > best.radius { sphere } else { best });
+            }
+            let offset = (normal.cross(&ab)*ac.length_squared() + ac.cross(&normal)*ab.length_squared())/determinant;
+            Some(Sphere::new(a + offset, offset.length()))
+        }
+        [a, b, c, d] => {
+            let (ab, ac, ad) = (b-a, c-a, d-a);
+            let determinant = T::from_f64(2.0)*ab.dot(&ac.cross(&ad));
+            if determinant == T::ZERO {
+                // coplanar, so the smallest circle of three of them holding
+                // the fourth
+                return [[a, b, c, d], [a, b, d, c], [a, c, d, b], [b, c, d, a]].into_iter()
+                    .filter_map(|[p, q, r, s]| sphere_through(&[p, q, r]).filter(|sphere| encloses(sphere.center, sphere.radius, s)))
+                    .reduce(|best, sphere| if sphere.radius < best.radius { sphere } else { best });
+            }
+            let offset = (ac.cross(&ad)*ab.length_squared() + ad.cross(&ab)*ac.length_squared() + ab.cross(&ac)*ad.length_squared())/determinant;
+            Some(Sphere::new(a + offset, offset.length()))
+        }
+        _ => None,
+    }
+}
+
+/// The smallest circle holding every point, using Welzl's algorithm, or
+/// [`None`] when there are no points.
+pub fn minimal_enclosing_circle<T: Real>(points: &[Vector2<T>]) -> Option<Circle<T>> {
+    let welzl = Welzl {
+        points,
+        order: scrambled(points.len()),
+        limit: 3,
+        through: circle_through,
+        encloses: |circle: Circle<T>, point| encloses(circle.center, circle.radius, point),
+    };
+    welzl.enclose(&mut [Vector2::ZERO; 4], 0, points.len())
+}
+
+/// The smallest sphere holding every point, using Welzl's algorithm, or
+/// [`None`] when there are no points.
+pub fn minimal_enclosing_sphere<T: Real>(points: &[Vector3<T>]) -> Option<Sphere<T>> {
+    let welzl = Welzl {
+        points,
+        order: scrambled(points.len()),
+        limit: 4,
+        through: sphere_through,
+        encloses: |sphere: Sphere<T>, point| encloses(sphere.center, sphere.radius, point),
+    };
+    welzl.enclose(&mut [Vector3::ZERO; 4], 0, points.len())
+}
+
+/// The bounds of `points`, or an empty rect at the origin when there are none.
+fn points_rect<T: Real>(points: impl IntoIterator<Item = Vector2<T>>) -> Rect<T> {
+    points.into_iter()
+        .map(|point| Rect::new(point, point))
+        .reduce(|bounds, other| bounds.merge(&other))
+        .unwrap_or(Rect::new(Vector2::ZERO, Vector2::ZERO))
+}
+
+fn points_rect3d<T: Real>(points: impl IntoIterator<Item = Vector3<T>>) -> Rect3D<T> {
+    points.into_iter()
+        .map(|point| (point, point))
+        .reduce(|(min, max), (other, _)| (min.min(other), max.max(other)))
+        .map_or(Rect3D::new(Vector3::ZERO, Vector3::ZERO), |(min, max)| Rect3D::new(min, max))
+}
+
+fn rect_circle<T: Real>(rect: &Rect<T>) -> Circle<T> {
+    Circle::new(rect.centroid(), rect.size().length()*T::from_f64(0.5))
+}
+
+fn rect3d_sphere<T: Real>(rect: &Rect3D<T>) -> Sphere<T> {
+    Sphere::new((rect.min+rect.max)*T::from_f64(0.5), (rect.max-rect.min).length()*T::from_f64(0.5))
+}
+
+/// How far a disc of `radius` facing the unit `axis` reaches along each
+/// world axis.
+fn disc_reach<T: Real>(axis: Vector3<T>, radius: T) -> Vector3<T> {
+    let reach = |along: T| radius*(T::ONE - along*along).max(T::ZERO).sqrt();
+    Vector3::new(reach(axis.x()), reach(axis.y()), reach(axis.z()))
+}
+
+/// Implements [`BoundingVolume`] for shapes that are the hull of a few
+/// points, where both volumes come from those points.
+macro_rules! impl_point_bounding_volume {
+    ($shape:ident, $vector:ident, $rect:ident, $points_rect:ident, $sphere:ident, $enclosing:ident, |$value:ident| $points:expr) => {
+        impl<T: Real> BoundingVolume for $shape<T> {
+            type Bounds = $rect<T>;
+            type Sphere = $sphere<T>;
+            fn bounding_box(&self) -> $rect<T> {
+                let $value = self;
+                $points_rect($points)
+            }
+            fn bounding_sphere(&self) -> $sphere<T> {
+                let $value = self;
+                let points: [$vector<T>; _] = $points;
+                $enclosing(&points).expect("a shape always has points")
+            }
+        }
+    };
+}
+impl_point_bounding_volume!(Triangle2D, Vector2, Rect, points_rect, Circle, minimal_enclosing_circle, |triangle| [triangle[0], triangle[1], triangle[2]]);
+impl_point_bounding_volume!(LinearSegment2D, Vector2, Rect, points_rect, Circle, minimal_enclosing_circle, |segment| [segment.start, segment.end]);
+impl_point_bounding_volume!(Triangle3D, Vector3, Rect3D, points_rect3d, Sphere, minimal_enclosing_sphere, |triangle| [triangle[0], triangle[1], triangle[2]]);
+impl_point_bounding_volume!(Tetrahedron, Vector3, Rect3D, points_rect3d, Sphere, minimal_enclosing_sphere, |tetrahedron| [tetrahedron[0], tetrahedron[1], tetrahedron[2], tetrahedron[3]]);
+impl_point_bounding_volume!(LinearSegment3D, Vector3, Rect3D, points_rect3d, Sphere, minimal_enclosing_sphere, |segment| [segment.start, segment.end]);
+impl_point_bounding_volume!(OrientedRect, Vector2, Rect, points_rect, Circle, minimal_enclosing_circle, |rect| rect.corners());
+impl_point_bounding_volume!(OrientedBox3D, Vector3, Rect3D, points_rect3d, Sphere, minimal_enclosing_sphere, |oriented_box| oriented_box.corners());
+
+/// Implements [`BoundingVolume`] for curves with a tight `bounds`, where
+/// the sphere holds their control points.
+macro_rules! impl_curve_bounding_volume {
+    ($shape:ident, $rect:ident, $sphere:ident, $enclosing:ident, [$($point:ident),*]) => {
+        impl<T: Real> BoundingVolume for $shape<T> {
+            type Bounds = $rect<T>;
+            type Sphere = $sphere<T>;
+            fn bounding_box(&self) -> $rect<T> {
+                self.bounds()
+            }
+            fn bounding_sphere(&self) -> $sphere<T> {
+                $enclosing(&[$(self.$point),*]).expect("a curve always has control points")
+            }
+        }
+    };
+}
+impl_curve_bounding_volume!(QuadraticSegment2D, Rect, Circle, minimal_enclosing_circle, [start, control, end]);
+impl_curve_bounding_volume!(CubicSegment2D, Rect, Circle, minimal_enclosing_circle, [start, control1, control2, end]);
+impl_curve_bounding_volume!(QuadraticSegment3D, Rect3D, Sphere, minimal_enclosing_sphere, [start, control, end]);
+impl_curve_bounding_volume!(CubicSegment3D, Rect3D, Sphere, minimal_enclosing_sphere, [start, control1, control2, end]);
+
+/// The curve is inside of the hull of its control points, so both volumes
+/// hold those.
+impl<T: Real, const N: usize> BoundingVolume for BezierCurve<Vector2<T>, N> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        points_rect(self.points)
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        minimal_enclosing_circle(&self.points).expect("a curve always has control points")
+    }
+}
+/// The curve is inside of the hull of its control points, so both volumes
+/// hold those.
+impl<T: Real, const N: usize> BoundingVolume for BezierCurve<Vector3<T>, N> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        points_rect3d(self.points)
+    }
+    fn bounding_sphere(&self) -> Sphere<T> {
+        minimal_enclosing_sphere(&self.points).expect("a curve always has control points")
+    }
+}
+
+impl<T: Real> BoundingVolume for ArcSegment2D<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        self.bounds()
+    }
+    /// The circle around the bounds of the arc.
+    fn bounding_sphere(&self) -> Circle<T> {
+        rect_circle(&self.bounds())
+    }
+}
+
+impl<T: Real> BoundingVolume for Segment2D<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        self.bounds()
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        match self {
+            Segment2D::Linear(linear) => linear.bounding_sphere(),
+            Segment2D::Quadratic(quadratic) => quadratic.bounding_sphere(),
+            Segment2D::Cubic(cubic) => cubic.bounding_sphere(),
+            Segment2D::Arc(arc) => arc.bounding_sphere(),
+        }
+    }
+}
+
+impl<T: Real> BoundingVolume for Segment3D<T> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        self.bounds()
+    }
+    fn bounding_sphere(&self) -> Sphere<T> {
+        match self {
+            Segment3D::Linear(linear) => linear.bounding_sphere(),
+            Segment3D::Quadratic(quadratic) => quadratic.bounding_sphere(),
+            Segment3D::Cubic(cubic) => cubic.bounding_sphere(),
+        }
+    }
+}
+
+impl<T: Real> BoundingVolume for Circle<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        let radius = Vector2::from(self.radius);
+        Rect::new(self.center - radius, self.center + radius)
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        *self
+    }
+}
+
+impl<T: Real> BoundingVolume for Rect<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        *self
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        rect_circle(self)
+    }
+}
+
+impl<T: Real> BoundingVolume for Ellipse<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        self.bounds()
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        Circle::new(self.center, self.radii.x().abs().max(self.radii.y().abs()))
+    }
+}
+
+impl<T: Real> BoundingVolume for Capsule2D<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        self.bounds()
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        Circle::new(self.centroid(), self.length()*T::from_f64(0.5) + self.radius)
+    }
+}
+
+impl<T: Real> BoundingVolume for RoundedRect<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        self.bounds()
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        let [min, _, max, _] = self.corner_centers();
+        Circle::new(self.centroid(), (max-min).length()*T::from_f64(0.5) + self.radius)
+    }
+}
+
+impl<T: Real> BoundingVolume for Sphere<T> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        let radius = Vector3::from(self.radius);
+        Rect3D::new(self.center - radius, self.center + radius)
+    }
+    fn bounding_sphere(&self) -> Sphere<T> {
+        *self
+    }
+}
+
+impl<T: Real> BoundingVolume for Rect3D<T> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        *self
+    }
+    fn bounding_sphere(&self) -> Sphere<T> {
+        rect3d_sphere(self)
+    }
+}
+
+impl<T: Real> BoundingVolume for Capsule<T> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        let radius = Vector3::from(self.radius);
+        Rect3D::new(self.start.min(self.end) - radius, self.start.max(self.end) + radius)
+    }
+    fn bounding_sphere(&self) -> Sphere<T> {
+        Sphere::new(self.centroid(), self.height()*T::from_f64(0.5) + self.radius)
+    }
+}
+
+impl<T: Real> BoundingVolume for Cylinder<T> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        let reach = disc_reach(self.axis(), self.radius);
+        Rect3D::new(self.start.min(self.end) - reach, self.start.max(self.end) + reach)
+    }
+    fn bounding_sphere(&self) -> Sphere<T> {
+        let half_height = self.height()*T::from_f64(0.5);
+        Sphere::new(self.centroid(), (half_height*half_height + self.radius*self.radius).sqrt())
+    }
+}
+
+impl<T: Real> BoundingVolume for Cone<T> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        let reach = disc_reach(self.axis(), self.radius);
+        Rect3D::new((self.base - reach).min(self.apex), (self.base + reach).max(self.apex))
+    }
+    /// The sphere through the apex and the rim of the base, unless the cone
+    /// is flat enough for the base alone to give it.
+    fn bounding_sphere(&self) -> Sphere<T> {
+        let height = self.height();
+        // how far up the axis the center is so it is as far from the rim as the apex
+        let along = (height*height - self.radius*self.radius)/(T::from_f64(2.0)*height);
+        if height == T::ZERO || along <= T::ZERO {
+            return Sphere::new(self.base, self.radius);
+        }
+        Sphere::new(self.base + self.axis()*along, height - along)
+    }
+}
+
+impl<T: Real> BoundingVolume for Torus<T> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        let reach = disc_reach(self.axis, self.major_radius) + Vector3::from(self.minor_radius);
+        Rect3D::new(self.center - reach, self.center + reach)
+    }
+    fn bounding_sphere(&self) -> Sphere<T> {
+        Sphere::new(self.center, self.major_radius + self.minor_radius)
+    }
+}
+
+/// Polygons without vertices give an empty box and circle at the origin.
+#[cfg(feature="alloc")]
+impl<T: Real> BoundingVolume for Polygon2D<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        points_rect(self.vertices.iter().copied())
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        minimal_enclosing_circle(&self.vertices).unwrap_or(Circle::new(Vector2::ZERO, T::ZERO))
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T: Real> BoundingVolume for Polygon3D<T> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        points_rect3d(self.vertices.iter().copied())
+    }
+    fn bounding_sphere(&self) -> Sphere<T> {
+        minimal_enclosing_sphere(&self.vertices).unwrap_or(Sphere::new(Vector3::ZERO, T::ZERO))
+    }
+}
+
+/// Holes are inside of the outlines, so only the outlines are used.
+#[cfg(feature="alloc")]
+impl<T: Real> BoundingVolume for MultiPolygon2D<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        points_rect(self.contours.iter().filter(|contour| !contour.is_hole()).flat_map(|contour| contour.polygon.vertices.iter().copied()))
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        let points: alloc::vec::Vec<Vector2<T>> = self.contours.iter()
+            .filter(|contour| !contour.is_hole())
+            .flat_map(|contour| contour.polygon.vertices.iter().copied())
+            .collect();
+        minimal_enclosing_circle(&points).unwrap_or(Circle::new(Vector2::ZERO, T::ZERO))
+    }
+}
+
+impl<T: Real> BoundingVolume for Frustum<T> {
+    type Bounds = Rect3D<T>;
+    type Sphere = Sphere<T>;
+    fn bounding_box(&self) -> Rect3D<T> {
+        points_rect3d(self.corners())
+    }
+    fn bounding_sphere(&self) -> Sphere<T> {
+        minimal_enclosing_sphere(&self.corners()).expect("a frustum always has corners")
+    }
+}
+
+/// Points whose hull holds the subpath, the control points of its curves and
+/// the corners of the bounds of its arcs.
+#[cfg(feature="alloc")]
+fn subpath_hull<T: Real>(subpath: &SubPath2D<T>, points: &mut alloc::vec::Vec<Vector2<T>>) {
+    points.push(subpath.start);
+    for segment in &subpath.segments {
+        match segment {
+            Segment2D::Linear(linear) => points.push(linear.end),
+            Segment2D::Quadratic(quadratic) => points.extend([quadratic.control, quadratic.end]),
+            Segment2D::Cubic(cubic) => points.extend([cubic.control1, cubic.control2, cubic.end]),
+            Segment2D::Arc(arc) => {
+                let bounds = arc.bounds();
+                points.extend([bounds.min, Vector2::new(bounds.max.x(), bounds.min.y()), bounds.max, Vector2::new(bounds.min.x(), bounds.max.y())]);
+            }
+        }
+    }
+}
+
+/// The circle holds the control points of the curves and the bounds of the
+/// arcs, so it isn't always the smallest one.
+#[cfg(feature="alloc")]
+impl<T: Real> BoundingVolume for SubPath2D<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        self.bounds()
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        let mut points = alloc::vec::Vec::new();
+        subpath_hull(self, &mut points);
+        minimal_enclosing_circle(&points).expect("a subpath always has a start")
+    }
+}
+
+/// Like [`SubPath2D`] over every subpath, a path without subpaths gives an
+/// empty box and circle at the origin.
+#[cfg(feature="alloc")]
+impl<T: Real> BoundingVolume for Path2D<T> {
+    type Bounds = Rect<T>;
+    type Sphere = Circle<T>;
+    fn bounding_box(&self) -> Rect<T> {
+        self.bounds().unwrap_or(Rect::new(Vector2::ZERO, Vector2::ZERO))
+    }
+    fn bounding_sphere(&self) -> Circle<T> {
+        let mut points = alloc::vec::Vec::new();
+        for subpath in self.subpaths() {
+            subpath_hull(subpath, &mut points);
+        }
+        minimal_enclosing_circle(&points).unwrap_or(Circle::new(Vector2::ZERO, T::ZERO))
+    }
+}
+
+/// The unit eigenvectors of the symmetric `matrix` from the largest
+/// eigenvalue to the smallest, using Jacobi rotations.
+pub(crate) fn symmetric_eigenvectors<T: Real>(mut matrix: [[T; 3]; 3]) -> [Vector3<T>; 3] {
+    let mut vectors = [[T::ONE, T::ZERO, T::ZERO], [T::ZERO, T::ONE, T::ZERO], [T::ZERO, T::ZERO, T::ONE]];
+    for _ in 0..32 {
+        let off_diagonal = matrix[0][1].abs() + matrix[0][2].abs() + matrix[1][2].abs();
+        if off_diagonal <= T::EPSILON*(matrix[0][0].abs() + matrix[1][1].abs() + matrix[2][2].abs()) {
+            break;
+        }
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if matrix[p][q] == T::ZERO {
+                continue;
+            }
+            let theta = (matrix[q][q] - matrix[p][p])/(T::from_f64(2.0)*matrix[p][q]);
+            let t = theta.signum()/(theta.abs() + (theta*theta + T::ONE).sqrt());
+            let cos = T::ONE/(t*t + T::ONE).sqrt();
+            let sin = t*cos;
+            for row in matrix.iter_mut().chain(vectors.iter_mut()) {
+                let (a, b) = (row[p], row[q]);
+                row[p] = cos*a - sin*b;
+                row[q] = sin*a + cos*b;
+            }
+            let (a, b) = (matrix[p], matrix[q]);
+            for k in 0..3 {
+                matrix[p][k] = cos*a[k] - sin*b[k];
+                matrix[q][k] = sin*a[k] + cos*b[k];
+            }
+        }
+    }
+    let mut order = [0, 1, 2];
+    order.sort_unstable_by(|&a, &b| matrix[b][b].partial_cmp(&matrix[a][a]).unwrap_or(core::cmp::Ordering::Equal));
+    order.map(|column| Vector3::new(vectors[0][column], vectors[1][column], vectors[2][column]))
+}
+
+impl<T: Real> OrientedRect<T> {
+    /// The rect around `points` lined up with their principal axes, the
+    /// directions they spread out along the most and the least, or [`None`]
+    /// when there are no points.
+    pub fn from_points(points: &[Vector2<T>]) -> Option<Self> {
+        let count = T::from_f64(points.len() as f64);
+        let mean = points.iter().copied().reduce(|sum, point| sum + point)?/count;
+        let (mut xx, mut xy, mut yy) = (T::ZERO, T::ZERO, T::ZERO);
+        for &point in points {
+            let offset = point - mean;
+            xx += offset.x()*offset.x();
+            xy += offset.x()*offset.y();
+            yy += offset.y()*offset.y();
+        }
+        let rotation = (T::from_f64(2.0)*xy).atan2(xx - yy)*T::from_f64(0.5);
+        let (sin, cos) = rotation.sin_cos();
+        let axes = [Vector2::new(cos, sin), Vector2::new(-sin, cos)];
+        let local = points_rect(points.iter().map(|&point| Vector2::new(point.dot(&axes[0]), point.dot(&axes[1]))));
+        let center = local.centroid();
+        Some(Self::new(axes[0]*center.x() + axes[1]*center.y(), local.size()*T::from_f64(0.5), rotation))
+    }
+}
+
+impl<T: Real> OrientedBox3D<T> {
+    /// The box around `points` lined up with their principal axes, the
+    /// eigenvectors of their covariance, or [`None`] when there are no
+    /// points.
+    pub fn from_points(points: &[Vector3<T>]) -> Option<Self> {
+        let count = T::from_f64(points.len() as f64);
+        let mean = points.iter().copied().reduce(|sum, point| sum + point)?/count;
+        let mut covariance = [[T::ZERO; 3]; 3];
+        for &point in points {
+            let offset = point - mean;
+            for (row, covariance) in covariance.iter_mut().enumerate() {
+                for (column, value) in covariance.iter_mut().enumerate() {
+                    *value += offset[row]*offset[column];
+                }
+            }
+        }
+        let [right, up, _] = symmetric_eigenvectors(covariance).map(|vector| vector.normalize());
+        let forward = right.cross(&up);
+        let local = points_rect3d(points.iter().map(|&point| Vector3::new(point.dot(&right), point.dot(&up), point.dot(&forward))));
+        let center = (local.min + local.max)*T::from_f64(0.5);
+        Some(Self::new(
+            right*center.x() + up*center.y() + forward*center.z(),
+            (local.max - local.min)*T::from_f64(0.5),
+            Quaternion::from_axes(right, up, forward),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use affogato_core::groups::vector_spaces::NormedVectorSpace;
+
+    use crate::{algebra::Quaternion, geometry::{minimal_enclosing_circle, minimal_enclosing_sphere, BezierCurve, BoundingVolume, Cone, Cylinder, Frustum, OrientedBox3D, OrientedRect, Path2D, Torus, Triangle2D}, matrix::Matrix4, vector::{DVec2, DVec3}};
+
+    /// A deterministic scatter of points in the unit square or cube.
+    fn scatter(index: usize, axis: usize) -> f64 {
+        ((index*7919 + axis*104729) as f64*0.618_033_988_749_895).fract()
+    }
+
+    #[test]
+    fn enclosing_spheres() {
+        let mut points = [DVec2::new(0.0, 0.0); 200];
+        for (i, point) in points.iter_mut().enumerate() {
+            *point = DVec2::new(scatter(i, 0), scatter(i, 1))*2.0 - DVec2::new(1.0, 1.0);
+        }
+        points[17] = DVec2::new(3.0, 0.0);
+        points[90] = DVec2::new(-3.0, 0.0);
+        let circle = minimal_enclosing_circle(&points).unwrap();
+        assert!(circle.center.length() < 1e-12 && (circle.radius - 3.0).abs() < 1e-12);
+        // an acute triangle is held by its circumcircle and an obtuse one by
+        // its longest side
+        let acute = Triangle2D::new(DVec2::new(-1.0, 0.0), DVec2::new(1.0, 0.0), DVec2::new(0.0, 1.5)).bounding_sphere();
+        assert!((acute.radius - (1.0 + 1.5*1.5)/3.0).abs() < 1e-12);
+        let obtuse = Triangle2D::new(DVec2::new(-1.0, 0.0), DVec2::new(1.0, 0.0), DVec2::new(0.0, 0.5)).bounding_sphere();
+        assert!(obtuse.center.length() < 1e-12 && (obtuse.radius - 1.0).abs() < 1e-12);
+        assert!(minimal_enclosing_circle::<f64>(&[]).is_none());
+
+        let mut points = [DVec3::new(0.0, 0.0, 0.0); 300];
+        for (i, point) in points.iter_mut().enumerate() {
+            *point = DVec3::new(scatter(i, 0), scatter(i, 1), scatter(i, 2))*2.0 - DVec3::new(1.0, 1.0, 1.0);
+        }
+        // a regular tetrahedron around the cloud, which sets the sphere
+        let corners = [DVec3::new(1.0, 1.0, 1.0), DVec3::new(1.0, -1.0, -1.0), DVec3::new(-1.0, 1.0, -1.0), DVec3::new(-1.0, -1.0, 1.0)];
+        for (i, corner) in corners.into_iter().enumerate() {
+            points[i*71] = corner*2.0;
+        }
+        let sphere = minimal_enclosing_sphere(&points).unwrap();
+        assert!(sphere.center.length() < 1e-9 && (sphere.radius - 2.0*3f64.sqrt()).abs() < 1e-9);
+        assert!(points.iter().all(|point| (*point - sphere.center).length() <= sphere.radius + 1e-9));
+        let coplanar = [DVec3::new(1.0, 0.0, 0.0), DVec3::new(0.0, 1.0, 0.0), DVec3::new(-1.0, 0.0, 0.0), DVec3::new(0.0, -1.0, 0.0)];
+        assert!((minimal_enclosing_sphere(&coplanar).unwrap().radius - 1.0).abs() < 1e-12);
+
+        // past the size where the stride times the index overflows 32 bits
+        // every point is still visited once
+        const COUNT: usize = 100_003;
+        let order = super::scrambled(COUNT);
+        let mut seen = [false; COUNT];
+        for i in 0..COUNT {
+            assert!(!core::mem::replace(&mut seen[order(i)], true));
+        }
+    }
+
+    #[test]
+    fn solid_volumes() {
+        let cylinder = Cylinder::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(0.0, 0.0, 2.0), 1.0);
+        let bounds = cylinder.bounding_box();
+        assert!((bounds.min - DVec3::new(-1.0, -1.0, 0.0)).length() < 1e-12 && (bounds.max - DVec3::new(1.0, 1.0, 2.0)).length() < 1e-12);
+        assert!((cylinder.bounding_sphere().radius - 2f64.sqrt()).abs() < 1e-12);
+
+        let tall = Cone::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(0.0, 4.0, 0.0), 2.0).bounding_sphere();
+        assert!((tall.center - DVec3::new(0.0, 1.5, 0.0)).length() < 1e-12 && (tall.radius - 2.5).abs() < 1e-12);
+        let flat = Cone::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(0.0, 1.0, 0.0), 2.0).bounding_sphere();
+        assert!(flat.center.length() < 1e-12 && (flat.radius - 2.0).abs() < 1e-12);
+
+        let torus = Torus::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(0.0, 0.0, 1.0), 2.0, 0.5).bounding_box();
+        assert!((torus.max - DVec3::new(2.5, 2.5, 0.5)).length() < 1e-12);
+    }
+
+    #[test]
+    fn curve_and_frustum_volumes() {
+        let curve = BezierCurve::new([DVec2::new(0.0, 0.0), DVec2::new(1.0, 3.0), DVec2::new(4.0, 0.0)]);
+        let bounds = curve.bounding_box();
+        assert!((bounds.min - DVec2::new(0.0, 0.0)).length() < 1e-12 && (bounds.max - DVec2::new(4.0, 3.0)).length() < 1e-12);
+        assert!((curve.bounding_sphere().radius - 5f64.sqrt()).abs() < 1e-12);
+
+        let path: Path2D<f64> = "M0 0 H4 A2 2 0 0 1 4 4 H0 Z M10 0 V1".parse().unwrap();
+        let bounds = path.bounding_box();
+        assert!((bounds.min - DVec2::new(0.0, 0.0)).length() < 1e-12 && (bounds.max - DVec2::new(10.0, 4.0)).length() < 1e-12);
+        let circle = path.bounding_sphere();
+        for subpath in path.subpaths() {
+            for i in 0..=16 {
+                let t = i as f64/16.0;
+                for segment in &subpath.segments {
+                    assert!((crate::geometry::Segment::get(segment, t) - circle.center).length() <= circle.radius + 1e-9);
+                }
+            }
+        }
+        assert_eq!(Path2D::<f64>::new().bounding_sphere().radius, 0.0);
+
+        let frustum = Frustum::from_matrix(&Matrix4::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 10.0/9.0, 1.0,
+            0.0, 0.0, -10.0/9.0, 0.0,
+        ));
+        let bounds = frustum.bounding_box();
+        assert!((bounds.min - DVec3::new(-10.0, -10.0, 1.0)).length() < 1e-9 && (bounds.max - DVec3::new(10.0, 10.0, 10.0)).length() < 1e-9);
+        let sphere = frustum.bounding_sphere();
+        assert!(frustum.corners().iter().all(|corner| (*corner - sphere.center).length() <= sphere.radius + 1e-9));
+    }
+
+    #[test]
+    fn principal_axes() {
+        let rect = OrientedRect::new(DVec2::new(1.0, 2.0), DVec2::new(3.0, 1.0), PI/6.0);
+        let mut points = [DVec2::new(0.0, 0.0); 400];
+        for (i, point) in points.iter_mut().enumerate() {
+            // a grid over the rect, so it is spread out the most along its long side
+            let local = DVec2::new((i%20) as f64/19.0*6.0 - 3.0, (i/20) as f64/19.0*2.0 - 1.0);
+            *point = rect.to_world(local);
+        }
+        let fitted = OrientedRect::from_points(&points).unwrap();
+        assert!((fitted.center - rect.center).length() < 1e-9);
+        assert!((fitted.half_extents - rect.half_extents).length() < 1e-9);
+        assert!((fitted.area() - rect.area()).abs() < 1e-9);
+
+        let rotation = Quaternion::angle_axis(0.7, DVec3::new(1.0, 2.0, 3.0).normalize());
+        let turned = Quaternion::from_axes(rotation.right(), rotation.up(), rotation.forward());
+        assert!((turned.dot(&rotation).abs() - 1.0).abs() < 1e-12);
+        let obb = OrientedBox3D::new(DVec3::new(-1.0, 0.5, 2.0), DVec3::new(4.0, 2.0, 1.0), rotation);
+        let mut points = [DVec3::new(0.0, 0.0, 0.0); 8*8*8];
+        for (i, point) in points.iter_mut().enumerate() {
+            let [x, y, z] = [i%8, i/8%8, i/64].map(|step| step as f64/7.0*2.0 - 1.0);
+            *point = obb.to_world(DVec3::new(x*4.0, y*2.0, z));
+        }
+        let fitted = OrientedBox3D::from_points(&points).unwrap();
+        assert!((fitted.center - obb.center).length() < 1e-9);
+        assert!((fitted.volume() - obb.volume()).abs() < 1e-9);
+        assert!((fitted.half_extents - obb.half_extents).length() < 1e-9);
+    }
+}
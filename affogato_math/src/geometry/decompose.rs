@@ -0,0 +1,115 @@
+use affogato_core::{groups::vector_spaces::VectorSpace, sets::Real};
+
+use crate::vector::Vector2;
+
+use super::{orient2d, triangulate_ear_clipping, Polygon2D};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// The piece a merged piece ended up in.
+fn root(owner: &[usize], mut piece: usize) -> usize {
+    while owner[piece] != piece {
+        piece = owner[piece];
+    }
+    piece
+}
+
+/// Joins two pieces along the diagonal going from `from` to `to` in `a` and
+/// back in `b`, unless that would make a reflex corner at either end of it.
+fn merge<T: Real>(points: &[Vector2<T>], a: &[u32], b: &[u32], from: u32, to: u32) -> Option<Vec<u32>> {
+    let start = (0..a.len()).find(|&i| a[i] == from && a[(i+1)%a.len()] == to)?;
+    // around `a` from `to` back to `from`, then around `b` between them
+    let mut merged: Vec<u32> = (1..=a.len()).map(|i| a[(start+i)%a.len()]).collect();
+    let start = (0..b.len()).find(|&i| b[i] == to && b[(i+1)%b.len()] == from)?;
+    merged.extend((2..b.len()).map(|i| b[(start+i)%b.len()]));
+    let count = merged.len();
+    let is_convex = |i: usize| {
+        let (previous, next) = (merged[(i+count-1)%count], merged[(i+1)%count]);
+        orient2d(points[previous as usize], points[merged[i] as usize], points[next as usize]) >= T::ZERO
+    };
+    (is_convex(0) && is_convex(a.len()-1)).then_some(merged)
+}
+
+/// Splits a polygon into convex pieces with the algorithm by Hertel and
+/// Mehlhorn, which removes the diagonals of a triangulation wherever the
+/// pieces on both sides stay convex. This gives at most four times as many
+/// pieces as the fewest possible.
+///
+/// Each piece is a ring of indices winding counterclockwise, addressing the
+/// vertices of the outline followed by the vertices of each hole like
+/// [`triangulate_ear_clipping`].
+pub fn convex_decomposition<T: Real>(outline: &[Vector2<T>], holes: &[&[Vector2<T>]]) -> Vec<Vec<u32>> {
+    let triangles = triangulate_ear_clipping(outline, holes);
+    let points: Vec<Vector2<T>> = outline.iter().chain(holes.iter().flat_map(|hole| hole.iter())).copied().collect();
+    let mut pieces: Vec<Vec<u32>> = triangles.chunks_exact(3).map(|triangle| triangle.to_vec()).collect();
+    let mut owner: Vec<usize> = (0..pieces.len()).collect();
+    let mut edges: Vec<((u32, u32), usize)> = triangles.chunks_exact(3).enumerate().flat_map(|(i, triangle)| {
+        [((triangle[0], triangle[1]), i), ((triangle[1], triangle[2]), i), ((triangle[2], triangle[0]), i)]
+    }).collect();
+    edges.sort_unstable_by_key(|&(edge, _)| edge);
+    for &((from, to), triangle) in edges.iter() {
+        // diagonals show up once in each direction, so only go one way
+        if from > to {
+            continue;
+        }
+        let Ok(opposite) = edges.binary_search_by_key(&(to, from), |&(edge, _)| edge) else {
+            continue;
+        };
+        let (a, b) = (root(&owner, triangle), root(&owner, edges[opposite].1));
+        if a == b {
+            continue;
+        }
+        if let Some(merged) = merge(&points, &pieces[a], &pieces[b], from, to) {
+            pieces[a] = merged;
+            pieces[b] = Vec::new();
+            owner[b] = a;
+        }
+    }
+    pieces.retain(|piece| !piece.is_empty());
+    pieces
+}
+
+impl<T: Real> Polygon2D<T> {
+    /// The polygon split into convex pieces, see [`convex_decomposition`].
+    pub fn convex_decomposition(&self) -> Vec<Polygon2D<T>> {
+        self.convex_decomposition_with_holes(&[])
+    }
+    /// The polygon without its holes split into convex pieces, see
+    /// [`convex_decomposition`].
+    pub fn convex_decomposition_with_holes(&self, holes: &[Polygon2D<T>]) -> Vec<Polygon2D<T>> {
+        let rings: Vec<&[Vector2<T>]> = holes.iter().map(|hole| hole.vertices.as_slice()).collect();
+        let points: Vec<Vector2<T>> = core::iter::once(self).chain(holes.iter()).flat_map(|ring| ring.vertices.iter().copied()).collect();
+        convex_decomposition(&self.vertices, &rings).into_iter().map(|piece| {
+            Polygon2D::new(piece.into_iter().map(|index| points[index as usize]).collect())
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::Polygon2D, vector::DVec2};
+
+    use super::alloc::vec;
+
+    #[test]
+    fn decompose_polygons() {
+        // three teeth on a base need at least four pieces
+        let comb = Polygon2D::new(vec![
+            DVec2::new(0.0, 0.0), DVec2::new(5.0, 0.0), DVec2::new(5.0, 3.0), DVec2::new(4.0, 3.0),
+            DVec2::new(4.0, 1.0), DVec2::new(3.0, 1.0), DVec2::new(3.0, 3.0), DVec2::new(2.0, 3.0),
+            DVec2::new(2.0, 1.0), DVec2::new(1.0, 1.0), DVec2::new(1.0, 3.0), DVec2::new(0.0, 3.0),
+        ]);
+        let pieces = comb.convex_decomposition();
+        assert!(pieces.len() >= 4 && pieces.len() < 10);
+        assert!(pieces.iter().all(|piece| piece.is_convex() && piece.signed_area() > 0.0));
+        assert!((pieces.iter().map(|piece| piece.signed_area()).sum::<f64>() - 11.0).abs() < 1e-9);
+
+        let square = Polygon2D::new(vec![DVec2::new(0.0, 0.0), DVec2::new(4.0, 0.0), DVec2::new(4.0, 4.0), DVec2::new(0.0, 4.0)]);
+        assert_eq!(square.convex_decomposition().len(), 1);
+        let hole = Polygon2D::new(vec![DVec2::new(1.0, 1.0), DVec2::new(3.0, 1.0), DVec2::new(3.0, 3.0), DVec2::new(1.0, 3.0)]);
+        let pieces = square.convex_decomposition_with_holes(&[hole]);
+        assert!(pieces.len() >= 3);
+        assert!(pieces.iter().all(|piece| piece.is_convex()));
+        assert!((pieces.iter().map(|piece| piece.signed_area()).sum::<f64>() - 12.0).abs() < 1e-9);
+    }
+}
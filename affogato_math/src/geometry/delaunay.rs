@@ -0,0 +1,469 @@
+use core::cmp::Ordering;
+
+use affogato_core::{groups::vector_spaces::VectorSpace, num::Zero, sets::Real};
+
+use crate::vector::{Vector2, Vector3};
+
+use super::{incircle, insphere, orient2d, orient3d, Polygon2D, Rect, Tetrahedron};
+extern crate alloc;
+use alloc::{collections::VecDeque, vec::Vec};
+
+/// Stands in for a point infinitely far outside the hull, so every face of
+/// the hull has a simplex on its other side. These simplices keep the ghost
+/// as their last corner.
+const GHOST: u32 = u32::MAX;
+
+/// Whether the triangle has to be removed for `point` to be added. A point
+/// conflicts with a ghost triangle when it is outside of its edge of the
+/// hull, or on the edge itself.
+fn conflicts_2d<T: Real>(vertices: &[Vector2<T>], [a, b, c]: [u32; 3], point: Vector2<T>) -> bool {
+    let (a, b) = (vertices[a as usize], vertices[b as usize]);
+    if c == GHOST {
+        let side = orient2d(a, b, point);
+        return side > T::ZERO || (side == T::ZERO && (a-point).dot(&(b-point)) < T::ZERO);
+    }
+    incircle(a, b, vertices[c as usize], point) > T::ZERO
+}
+
+/// Bowyer–Watson, giving counterclockwise triangles between the points and
+/// leaving out repeated points.
+fn bowyer_watson_2d<T: Real>(points: &[Vector2<T>]) -> Vec<[u32; 3]> {
+    let count = points.len() as u32;
+    let Some(second) = (1..count).find(|&i| points[i as usize] != points[0]) else {
+        return Vec::new();
+    };
+    let Some(third) = (second+1..count).find(|&i| orient2d(points[0], points[second as usize], points[i as usize]) != T::ZERO) else {
+        return Vec::new();
+    };
+    let first = if orient2d(points[0], points[second as usize], points[third as usize]) > T::ZERO { [0, second, third] } else { [0, third, second] };
+    let mut triangles = alloc::vec![first];
+    triangles.extend([(first[0], first[1]), (first[1], first[2]), (first[2], first[0])].map(|(from, to)| [to, from, GHOST]));
+    let mut bad: Vec<[u32; 3]> = Vec::new();
+    let mut boundary: Vec<(u32, u32)> = Vec::new();
+    for index in (1..count).filter(|&index| index != second && index != third) {
+        let point = points[index as usize];
+        bad.clear();
+        triangles.retain(|&triangle| {
+            let conflicts = conflicts_2d(points, triangle, point);
+            if conflicts {
+                bad.push(triangle);
+            }
+            !conflicts
+        });
+        if bad.is_empty() {
+            continue;
+        }
+        // grow the hole until the point sees all of its edges, which ties in
+        // the circle test can otherwise break
+        loop {
+            boundary.clear();
+            boundary.extend(bad.iter().flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)]));
+            boundary.sort_unstable();
+            let edges = boundary.clone();
+            boundary.retain(|&(from, to)| edges.binary_search(&(to, from)).is_err());
+            let hidden = boundary.iter().copied().find(|&(from, to)| {
+                from != GHOST && to != GHOST && orient2d(points[from as usize], points[to as usize], point) <= T::ZERO
+            });
+            let Some((from, to)) = hidden else {
+                break;
+            };
+            let Some(across) = triangles.iter().position(|&[a, b, c]| [(a, b), (b, c), (c, a)].contains(&(to, from))) else {
+                break;
+            };
+            bad.push(triangles.swap_remove(across));
+        }
+        triangles.extend(boundary.iter().map(|&(from, to)| match (from, to) {
+            (GHOST, to) => [to, index, GHOST],
+            (from, GHOST) => [index, from, GHOST],
+            (from, to) => [from, to, index],
+        }));
+    }
+    triangles.retain(|triangle| triangle[2] != GHOST);
+    triangles
+}
+
+/// The Delaunay triangulation of the points as counterclockwise triangle
+/// indices, where no point is inside the circle through any triangle.
+/// Repeated points are only used once, and collinear points give no
+/// triangles.
+pub fn delaunay_2d<T: Real>(points: &[Vector2<T>]) -> Vec<u32> {
+    bowyer_watson_2d(points).into_iter().flatten().collect()
+}
+
+/// The third corner of the triangle with the edge going from `from` to `to`.
+fn opposite(triangles: &[[u32; 3]], from: u32, to: u32) -> Option<(usize, u32)> {
+    triangles.iter().enumerate().find_map(|(i, &[a, b, c])| match (from, to) {
+        _ if (a, b) == (from, to) => Some((i, c)),
+        _ if (b, c) == (from, to) => Some((i, a)),
+        _ if (c, a) == (from, to) => Some((i, b)),
+        _ => None,
+    })
+}
+
+/// Turns the edge between two triangles into the other diagonal of the
+/// quadrilateral they make, giving the new edge.
+fn flip(triangles: &mut [[u32; 3]], from: u32, to: u32) -> Option<(u32, u32)> {
+    let (first, left) = opposite(triangles, from, to)?;
+    let (second, right) = opposite(triangles, to, from)?;
+    triangles[first] = [from, right, left];
+    triangles[second] = [right, to, left];
+    Some((left, right))
+}
+
+/// Whether two segments cross at a point inside both of them.
+fn crosses<T: Real>(a: Vector2<T>, b: Vector2<T>, c: Vector2<T>, d: Vector2<T>) -> bool {
+    let opposite = |first: T, second: T| (first > T::ZERO && second < T::ZERO) || (first < T::ZERO && second > T::ZERO);
+    opposite(orient2d(a, b, c), orient2d(a, b, d)) && opposite(orient2d(c, d, a), orient2d(c, d, b))
+}
+
+/// Forces the edge from `a` to `b` into the triangulation with the edge
+/// flipping of Sloan, then flips the new edges back towards Delaunay.
+fn insert_constraint<T: Real>(points: &[Vector2<T>], triangles: &mut [[u32; 3]], a: u32, b: u32, constraints: &[(u32, u32)]) {
+    let has_edge = |triangles: &[[u32; 3]], from: u32, to: u32| opposite(triangles, from, to).is_some() || opposite(triangles, to, from).is_some();
+    if has_edge(triangles, a, b) {
+        return;
+    }
+    let (start, end) = (points[a as usize], points[b as usize]);
+    let mut crossing: VecDeque<(u32, u32)> = triangles.iter()
+        .flat_map(|&[p, q, r]| [(p, q), (q, r), (r, p)])
+        .filter(|&(from, to)| from < to && crosses(start, end, points[from as usize], points[to as usize]))
+        .collect();
+    let mut created = Vec::new();
+    let mut stalled = 0;
+    while let Some((from, to)) = crossing.pop_front() {
+        let (Some((_, left)), Some((_, right))) = (opposite(triangles, from, to), opposite(triangles, to, from)) else {
+            continue;
+        };
+        // only a convex quadrilateral can be flipped
+        if !crosses(points[left as usize], points[right as usize], points[from as usize], points[to as usize]) {
+            crossing.push_back((from, to));
+            stalled += 1;
+            if stalled > crossing.len() {
+                break;
+            }
+            continue;
+        }
+        stalled = 0;
+        let Some(edge) = flip(triangles, from, to) else {
+            continue;
+        };
+        if crosses(start, end, points[edge.0 as usize], points[edge.1 as usize]) {
+            crossing.push_back(edge);
+        } else {
+            created.push(edge);
+        }
+    }
+    let is_constraint = |(from, to): (u32, u32)| (from, to) == (a, b) || (to, from) == (a, b)
+        || constraints.contains(&(from, to)) || constraints.contains(&(to, from));
+    let mut flipped = true;
+    while flipped {
+        flipped = false;
+        for edge in created.iter_mut() {
+            if is_constraint(*edge) {
+                continue;
+            }
+            let (Some((_, left)), Some((_, right))) = (opposite(triangles, edge.0, edge.1), opposite(triangles, edge.1, edge.0)) else {
+                continue;
+            };
+            let [from, to] = [edge.0, edge.1].map(|index| points[index as usize]);
+            if incircle(from, to, points[left as usize], points[right as usize]) > T::ZERO
+                && let Some(new_edge) = flip(triangles, edge.0, edge.1) {
+                *edge = new_edge;
+                flipped = true;
+            }
+        }
+    }
+}
+
+/// The Delaunay triangulation of the points with the edges between the
+/// pairs of points in `constraints` forced into it, so triangles only break
+/// the circle rule across those edges. Points lying on a constraint split it
+/// up, but constraints must not cross each other.
+pub fn constrained_delaunay_2d<T: Real>(points: &[Vector2<T>], constraints: &[(u32, u32)]) -> Vec<u32> {
+    let mut triangles = bowyer_watson_2d(points);
+    let mut pieces: Vec<(u32, u32)> = Vec::with_capacity(constraints.len());
+    for &(a, b) in constraints {
+        let (start, end) = (points[a as usize], points[b as usize]);
+        if start == end {
+            continue;
+        }
+        let direction = end-start;
+        let mut along: Vec<(T, u32)> = (0..points.len() as u32).filter_map(|index| {
+            let point = points[index as usize];
+            let t = (point-start).dot(&direction)/direction.length_squared();
+            (orient2d(start, end, point) == T::ZERO && t > T::ZERO && t < T::ONE).then_some((t, index))
+        }).collect();
+        along.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        let mut previous = a;
+        for (_, index) in along.into_iter().chain(core::iter::once((T::ONE, b))) {
+            if points[index as usize] != points[previous as usize] {
+                pieces.push((previous, index));
+                previous = index;
+            }
+        }
+    }
+    // repeated points only take part through their first copy
+    let first: Vec<u32> = (0..points.len()).map(|i| (0..i).find(|&j| points[j] == points[i]).unwrap_or(i) as u32).collect();
+    let pieces: Vec<(u32, u32)> = pieces.into_iter().map(|(a, b)| (first[a as usize], first[b as usize])).collect();
+    for &(a, b) in pieces.iter() {
+        insert_constraint(points, &mut triangles, a, b, &pieces);
+    }
+    triangles.into_iter().flatten().collect()
+}
+
+/// Keeps the part of a convex polygon on the side of the line through
+/// `point` that `normal` points away from.
+fn clip<T: Real>(polygon: Vec<Vector2<T>>, point: Vector2<T>, normal: Vector2<T>) -> Vec<Vector2<T>> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for i in 0..polygon.len() {
+        let (current, next) = (polygon[i], polygon[(i+1)%polygon.len()]);
+        let (current_side, next_side) = ((current-point).dot(&normal), (next-point).dot(&normal));
+        if current_side <= T::ZERO {
+            clipped.push(current);
+        }
+        if (current_side < T::ZERO && next_side > T::ZERO) || (current_side > T::ZERO && next_side < T::ZERO) {
+            clipped.push(current + (next-current)*(current_side/(current_side-next_side)));
+        }
+    }
+    clipped
+}
+
+/// The Voronoi cell of every point within `bounds`, in the same order as the
+/// points. Each cell is the part of the bounds closer to its point than to
+/// any other, winding counterclockwise, and repeated points share a cell.
+pub fn voronoi_2d<T: Real>(points: &[Vector2<T>], bounds: Rect<T>) -> Vec<Polygon2D<T>> {
+    let triangles = bowyer_watson_2d(points);
+    let first: Vec<usize> = (0..points.len()).map(|i| (0..i).find(|&j| points[j] == points[i]).unwrap_or(i)).collect();
+    // the cell of a point only borders those it shares a Delaunay edge with,
+    // or any of them when there are no triangles
+    let mut neighbours: Vec<Vec<u32>> = alloc::vec![Vec::new(); points.len()];
+    for &[a, b, c] in triangles.iter() {
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            neighbours[from as usize].push(to);
+            neighbours[to as usize].push(from);
+        }
+    }
+    if triangles.is_empty() {
+        for (i, neighbours) in neighbours.iter_mut().enumerate() {
+            neighbours.extend((0..points.len() as u32).filter(|&j| first[j as usize] == j as usize && j as usize != i));
+        }
+    }
+    let (min, max) = (*bounds.minimum(), *bounds.maximum());
+    let corners = alloc::vec![min, Vector2::new(max.x(), min.y()), max, Vector2::new(min.x(), max.y())];
+    (0..points.len()).map(|i| {
+        let site = points[i];
+        let cell = neighbours[first[i]].iter().fold(corners.clone(), |cell, &neighbour| {
+            let other = points[neighbour as usize];
+            if cell.is_empty() || other == site {
+                return cell;
+            }
+            clip(cell, (site + other)/T::from_f64(2.0), other-site)
+        });
+        Polygon2D::new(cell)
+    }).collect()
+}
+
+/// Whether `point` is inside the circle through the triangle, when it is
+/// in the plane of the triangle.
+fn in_circumcircle<T: Real>(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>, point: Vector3<T>) -> bool {
+    let (ab, ac) = (b-a, c-a);
+    let normal = ab.cross(&ac);
+    let center = a + (normal.cross(&ab)*ac.length_squared() + ac.cross(&normal)*ab.length_squared())/(normal.length_squared()*T::from_f64(2.0));
+    (point-center).length_squared() < (a-center).length_squared()
+}
+
+/// Whether the tetrahedron has to be removed for `point` to be added, like
+/// [`conflicts_2d`].
+fn conflicts_3d<T: Real>(vertices: &[Vector3<T>], [a, b, c, d]: [u32; 4], point: Vector3<T>) -> bool {
+    let (a, b, c) = (vertices[a as usize], vertices[b as usize], vertices[c as usize]);
+    if d == GHOST {
+        let side = orient3d(a, b, c, point);
+        return side > T::ZERO || (side == T::ZERO && in_circumcircle(a, b, c, point));
+    }
+    insphere(a, b, c, vertices[d as usize], point) > T::ZERO
+}
+
+/// The faces of a tetrahedron with a positive [`orient3d`], winding
+/// counterclockwise seen from outside.
+fn faces([a, b, c, d]: [u32; 4]) -> [[u32; 3]; 4] {
+    [[a, c, b], [a, b, d], [a, d, c], [b, c, d]]
+}
+
+/// Bowyer–Watson in 3D, giving tetrahedra with a positive [`orient3d`]
+/// between the points and leaving out repeated points.
+fn bowyer_watson_3d<T: Real>(points: &[Vector3<T>]) -> Vec<[u32; 4]> {
+    let count = points.len() as u32;
+    let corner = |vertex: u32| points[vertex as usize];
+    let Some(second) = (1..count).find(|&i| corner(i) != points[0]) else {
+        return Vec::new();
+    };
+    let Some(third) = (second+1..count).find(|&i| !(corner(second)-points[0]).cross(&(corner(i)-points[0])).is_zero()) else {
+        return Vec::new();
+    };
+    let Some(fourth) = (third+1..count).find(|&i| orient3d(points[0], corner(second), corner(third), corner(i)) != T::ZERO) else {
+        return Vec::new();
+    };
+    let first = if orient3d(points[0], corner(second), corner(third), corner(fourth)) > T::ZERO {
+        [0, second, third, fourth]
+    } else {
+        [0, third, second, fourth]
+    };
+    let mut tetrahedra = alloc::vec![first];
+    tetrahedra.extend(faces(first).map(|[a, b, c]| [a, b, c, GHOST]));
+    let key = |mut face: [u32; 3]| {
+        face.sort_unstable();
+        face
+    };
+    let mut bad: Vec<[u32; 4]> = Vec::new();
+    let mut boundary: Vec<[u32; 3]> = Vec::new();
+    for index in (1..count).filter(|&index| ![second, third, fourth].contains(&index)) {
+        let point = corner(index);
+        bad.clear();
+        tetrahedra.retain(|&tetrahedron| {
+            let conflicts = conflicts_3d(points, tetrahedron, point);
+            if conflicts {
+                bad.push(tetrahedron);
+            }
+            !conflicts
+        });
+        if bad.is_empty() {
+            continue;
+        }
+        loop {
+            let mut keys: Vec<[u32; 3]> = bad.iter().copied().flat_map(faces).map(key).collect();
+            keys.sort_unstable();
+            boundary.clear();
+            boundary.extend(bad.iter().copied().flat_map(faces).filter(|&face| {
+                let face = key(face);
+                keys.partition_point(|&other| other < face) + 1 == keys.partition_point(|&other| other <= face)
+            }));
+            let hidden = boundary.iter().copied().find(|&[a, b, c]| {
+                ![a, b, c].contains(&GHOST) && orient3d(corner(a), corner(c), corner(b), point) <= T::ZERO
+            });
+            let Some(face) = hidden else {
+                break;
+            };
+            let face = key(face);
+            let Some(across) = tetrahedra.iter().position(|&tetrahedron| faces(tetrahedron).into_iter().any(|other| key(other) == face)) else {
+                break;
+            };
+            bad.push(tetrahedra.swap_remove(across));
+        }
+        tetrahedra.extend(boundary.iter().map(|&[a, b, c]| {
+            let mut tetrahedron = [a, c, b, index];
+            // move the ghost last without changing the orientation
+            if let Some(ghost) = tetrahedron.iter().position(|&vertex| vertex == GHOST) {
+                tetrahedron.swap(ghost, 3);
+                let others: Vec<usize> = (0..3).filter(|&i| i != ghost).collect();
+                tetrahedron.swap(others[0], others[1]);
+            }
+            tetrahedron
+        }));
+    }
+    tetrahedra.retain(|tetrahedron| tetrahedron[3] != GHOST);
+    tetrahedra
+}
+
+/// The Delaunay tetrahedralization of the points, four indices for each
+/// tetrahedron, where no point is inside the sphere through any of them.
+/// Every tetrahedron has its fourth point on the side of the first three
+/// they wind counterclockwise on. Repeated points are only used once, and
+/// coplanar points give no tetrahedra.
+pub fn delaunay_3d<T: Real>(points: &[Vector3<T>]) -> Vec<u32> {
+    bowyer_watson_3d(points).into_iter().flatten().collect()
+}
+
+/// The cells of [`delaunay_3d`] as tetrahedra.
+pub fn delaunay_tetrahedra<T: Real>(points: &[Vector3<T>]) -> Vec<Tetrahedron<T>> {
+    bowyer_watson_3d(points).into_iter().map(|[a, b, c, d]| {
+        Tetrahedron::new(points[a as usize], points[b as usize], points[c as usize], points[d as usize])
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::{constrained_delaunay_2d, convex_hull_3d, delaunay_2d, delaunay_3d, voronoi_2d, Rect}, vector::{DVec2, DVec3}};
+
+    use super::{alloc::vec::Vec, incircle, insphere, orient2d, orient3d};
+
+    #[test]
+    fn delaunay_triangulations() {
+        // a grid has four points on every circle, and points along its hull
+        let mut grid = Vec::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                grid.push(DVec2::new(x as f64, y as f64));
+            }
+        }
+        grid.push(DVec2::new(1.0, 1.0));
+        let triangles = delaunay_2d(&grid);
+        assert_eq!(triangles.len(), 18*3);
+        let mut area = 0.0;
+        for triangle in triangles.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|index| grid[index as usize]);
+            assert!(orient2d(a, b, c) > 0.0);
+            assert!(grid.iter().all(|&point| incircle(a, b, c, point) <= 0.0));
+            area += orient2d(a, b, c)/2.0;
+        }
+        assert_eq!(area, 9.0);
+
+        // a grid in steps of a third rounds every circle slightly, which only
+        // exact predicates see through
+        let thirds: Vec<DVec2> = (0..36).map(|i| DVec2::new((i % 6) as f64/3.0, (i / 6) as f64/3.0)).collect();
+        let triangles = delaunay_2d(&thirds);
+        assert_eq!(triangles.len(), 50*3);
+        for triangle in triangles.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|index| thirds[index as usize]);
+            assert!(orient2d(a, b, c) > 0.0);
+            assert!(thirds.iter().all(|&point| incircle(a, b, c, point) <= 0.0));
+        }
+
+        // the short diagonal is Delaunay, but the long one can be forced in
+        let kite = [DVec2::new(0.0, 0.0), DVec2::new(10.0, 0.0), DVec2::new(5.0, 1.0), DVec2::new(5.0, -1.0)];
+        let has_edge = |triangles: &[u32], a: u32, b: u32| triangles.chunks_exact(3).any(|triangle| triangle.contains(&a) && triangle.contains(&b));
+        assert!(has_edge(&delaunay_2d(&kite), 2, 3));
+        let constrained = constrained_delaunay_2d(&kite, &[(0, 1)]);
+        assert!(has_edge(&constrained, 0, 1) && !has_edge(&constrained, 2, 3));
+        assert_eq!(constrained.len(), 2*3);
+    }
+
+    #[test]
+    fn voronoi_cells() {
+        let sites = [DVec2::new(1.0, 1.0), DVec2::new(3.0, 1.0), DVec2::new(1.0, 3.0), DVec2::new(3.0, 3.0), DVec2::new(3.0, 3.0)];
+        let cells = voronoi_2d(&sites, Rect::new(DVec2::new(0.0, 0.0), DVec2::new(4.0, 4.0)));
+        assert_eq!(cells.len(), 5);
+        for (cell, &site) in cells.iter().zip(sites.iter()) {
+            assert!((cell.signed_area() - 4.0).abs() < 1e-9);
+            assert!(cell.contains(site));
+        }
+    }
+
+    #[test]
+    fn delaunay_tetrahedralization() {
+        let (a, b, c, d) = (DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 0.0, 0.0), DVec3::new(0.0, 1.0, 0.0), DVec3::new(0.0, 0.0, 1.0));
+        assert!(orient3d(a, b, c, d) > 0.0);
+        assert!(insphere(a, b, c, d, DVec3::new(0.25, 0.25, 0.25)) > 0.0);
+        assert!(insphere(a, b, c, d, DVec3::new(2.0, 2.0, 2.0)) < 0.0);
+
+        // the corners of a cube are all on one sphere
+        let mut points = Vec::new();
+        for i in 0..8 {
+            points.push(DVec3::new((i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64));
+        }
+        points.extend([DVec3::new(0.5, 0.5, 0.5), DVec3::new(0.3, 0.9, 0.2), DVec3::new(1.5, 0.4, 0.6), DVec3::new(0.6, -0.7, 0.1)]);
+        let tetrahedra = delaunay_3d(&points);
+        let corner = |index: u32| points[index as usize];
+        let mut volume = 0.0;
+        for tetrahedron in tetrahedra.chunks_exact(4) {
+            let [a, b, c, d] = [tetrahedron[0], tetrahedron[1], tetrahedron[2], tetrahedron[3]].map(corner);
+            assert!(orient3d(a, b, c, d) > 0.0);
+            assert!(points.iter().all(|&point| insphere(a, b, c, d, point) <= 0.0));
+            volume += orient3d(a, b, c, d)/6.0;
+        }
+        let hull: f64 = convex_hull_3d(&points).chunks_exact(3).map(|triangle| {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(corner);
+            orient3d(DVec3::new(0.0, 0.0, 0.0), a, b, c)/6.0
+        }).sum();
+        assert!((volume - hull).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,225 @@
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, sets::Real};
+
+use crate::vector::Vector2;
+
+use super::CubicSegment2D;
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// How many times the parameters of the points are improved with
+/// Newton–Raphson before a curve is split.
+const MAX_REPARAMETERIZATIONS: usize = 4;
+
+fn bezier_point<T: Real>(curve: &[Vector2<T>; 4], t: T) -> Vector2<T> {
+    let s = T::ONE - t;
+    curve[0]*(s*s*s) + curve[1]*(T::from_f64(3.0)*s*s*t) + curve[2]*(T::from_f64(3.0)*s*t*t) + curve[3]*(t*t*t)
+}
+
+fn bezier_derivative<T: Real>(curve: &[Vector2<T>; 4], t: T) -> Vector2<T> {
+    let s = T::ONE - t;
+    let three = T::from_f64(3.0);
+    (curve[1]-curve[0])*(three*s*s) + (curve[2]-curve[1])*(three*T::from_f64(2.0)*s*t) + (curve[3]-curve[2])*(three*t*t)
+}
+
+fn bezier_second_derivative<T: Real>(curve: &[Vector2<T>; 4], t: T) -> Vector2<T> {
+    let six = T::from_f64(6.0);
+    (curve[2] - curve[1]*T::from_f64(2.0) + curve[0])*(six*(T::ONE - t)) + (curve[3] - curve[2]*T::from_f64(2.0) + curve[1])*(six*t)
+}
+
+/// Finds the cubic from the first to the last point, leaving and arriving
+/// along the given tangents, that is the least squares fit of the points at
+/// `parameters`. `tangent2` points backwards from the end of the curve.
+pub(super) fn fit_with_tangents<T: Real>(points: &[Vector2<T>], parameters: &[T], tangent1: Vector2<T>, tangent2: Vector2<T>) -> [Vector2<T>; 4] {
+    let (start, end) = (points[0], points[points.len()-1]);
+    let three = T::from_f64(3.0);
+    let mut c = [[T::ZERO; 2]; 2];
+    let mut x = [T::ZERO; 2];
+    for (&point, &u) in points.iter().zip(parameters) {
+        let s = T::ONE - u;
+        let (b0, b1, b2, b3) = (s*s*s, three*s*s*u, three*s*u*u, u*u*u);
+        let a1 = tangent1*b1;
+        let a2 = tangent2*b2;
+        c[0][0] += a1.dot(&a1);
+        c[0][1] += a1.dot(&a2);
+        c[1][1] += a2.dot(&a2);
+        let remainder = point - (start*(b0 + b1) + end*(b2 + b3));
+        x[0] += a1.dot(&remainder);
+        x[1] += a2.dot(&remainder);
+    }
+    c[1][0] = c[0][1];
+    let determinant = c[0][0]*c[1][1] - c[1][0]*c[0][1];
+    let (alpha1, alpha2) = if determinant == T::ZERO {
+        (T::ZERO, T::ZERO)
+    } else {
+        ((x[0]*c[1][1] - x[1]*c[0][1])/determinant, (c[0][0]*x[1] - c[1][0]*x[0])/determinant)
+    };
+    let length = (end - start).length();
+    // a negative or tiny alpha means the fit is degenerate, so fall back
+    // to the heuristic of placing the control points a third of the way
+    let epsilon = length*T::from_f64(1e-6);
+    let (alpha1, alpha2) = if alpha1 < epsilon || alpha2 < epsilon {
+        (length/three, length/three)
+    } else {
+        (alpha1, alpha2)
+    };
+    [start, start + tangent1*alpha1, end + tangent2*alpha2, end]
+}
+
+/// Fits cubics to sampled points with Schneider's algorithm from
+/// "An Algorithm for Automatically Fitting Digitized Curves".
+struct Fitter<'a, T: Real> {
+    points: &'a [Vector2<T>],
+    tolerance_squared: T,
+    curves: Vec<CubicSegment2D<T>>,
+}
+impl<T: Real> Fitter<'_, T> {
+    /// Parameterizes the points by their distance along the polyline.
+    fn chord_lengths(&self, first: usize, last: usize) -> Vec<T> {
+        let mut parameters = alloc::vec![T::ZERO];
+        for i in first+1..=last {
+            let length = parameters[i-first-1] + (self.points[i] - self.points[i-1]).length();
+            parameters.push(length);
+        }
+        let total = parameters[last-first];
+        parameters.iter_mut().for_each(|u| *u /= total);
+        parameters
+    }
+    fn generate(&self, first: usize, last: usize, parameters: &[T], tangent1: Vector2<T>, tangent2: Vector2<T>) -> [Vector2<T>; 4] {
+        fit_with_tangents(&self.points[first..=last], parameters, tangent1, tangent2)
+    }
+    /// The largest squared distance between a point and where the curve is
+    /// at its parameter, along with the index of that point.
+    fn max_error(&self, first: usize, last: usize, curve: &[Vector2<T>; 4], parameters: &[T]) -> (T, usize) {
+        let mut max = (T::ZERO, (first + last)/2);
+        for i in first+1..last {
+            let error = (bezier_point(curve, parameters[i-first]) - self.points[i]).length_squared();
+            if error >= max.0 {
+                max = (error, i);
+            }
+        }
+        max
+    }
+    /// Moves each parameter closer to the point on the curve nearest its
+    /// point with a step of Newton–Raphson.
+    fn reparameterize(&self, first: usize, curve: &[Vector2<T>; 4], parameters: &mut [T]) {
+        for (i, u) in parameters.iter_mut().enumerate() {
+            let difference = bezier_point(curve, *u) - self.points[first+i];
+            let derivative = bezier_derivative(curve, *u);
+            let numerator = difference.dot(&derivative);
+            let denominator = derivative.dot(&derivative) + difference.dot(&bezier_second_derivative(curve, *u));
+            if denominator != T::ZERO {
+                *u = affogato_core::clamp(*u - numerator/denominator, T::ZERO, T::ONE);
+            }
+        }
+    }
+    fn fit(&mut self, first: usize, last: usize, tangent1: Vector2<T>, tangent2: Vector2<T>) {
+        let (start, end) = (self.points[first], self.points[last]);
+        if last - first == 1 {
+            let third = (end - start).length()/T::from_f64(3.0);
+            self.curves.push(CubicSegment2D::new(start, start + tangent1*third, end + tangent2*third, end));
+            return;
+        }
+        let mut parameters = self.chord_lengths(first, last);
+        let mut curve = self.generate(first, last, &parameters, tangent1, tangent2);
+        let (mut error, mut split) = self.max_error(first, last, &curve, &parameters);
+        if error <= self.tolerance_squared {
+            self.curves.push(CubicSegment2D::new(curve[0], curve[1], curve[2], curve[3]));
+            return;
+        }
+        // close fits are usually fixed by better parameters rather than more curves
+        if error <= self.tolerance_squared*T::from_f64(16.0) {
+            for _ in 0..MAX_REPARAMETERIZATIONS {
+                self.reparameterize(first, &curve, &mut parameters);
+                curve = self.generate(first, last, &parameters, tangent1, tangent2);
+                (error, split) = self.max_error(first, last, &curve, &parameters);
+                if error <= self.tolerance_squared {
+                    self.curves.push(CubicSegment2D::new(curve[0], curve[1], curve[2], curve[3]));
+                    return;
+                }
+            }
+        }
+        let mut center = self.points[split-1] - self.points[split+1];
+        if center.length_squared() == T::ZERO {
+            center = (self.points[split-1] - self.points[split]).rotate_90();
+        }
+        let center = center.normalize();
+        self.fit(first, split, tangent1, center);
+        self.fit(split, last, -center, tangent2);
+    }
+}
+
+impl<T: Real> CubicSegment2D<T> {
+    /// Fits a sequence of connected cubics to sampled points so that no
+    /// point is further than `tolerance` from the curves, using as few cubics
+    /// as it can. Wherever the polyline turns by more than `corner_angle`
+    /// radians the curves meet at a sharp corner, elsewhere they join
+    /// smoothly.
+    ///
+    /// Noisy input, such as pen strokes, fits better after removing small
+    /// wobbles with [`simplify_rdp`](super::simplify_rdp) or
+    /// [`simplify_visvalingam`](super::simplify_visvalingam).
+    pub fn fit(points: &[Vector2<T>], tolerance: T, corner_angle: T) -> Vec<Self> {
+        let mut points: Vec<Vector2<T>> = points.to_vec();
+        points.dedup();
+        let mut fitter = Fitter {
+            points: &points,
+            tolerance_squared: tolerance*tolerance,
+            curves: Vec::new(),
+        };
+        if points.len() < 2 {
+            return fitter.curves;
+        }
+        let corner_cos = corner_angle.cos();
+        let mut first = 0;
+        for i in 1..points.len() {
+            let is_corner = i+1 < points.len() && {
+                let before = (points[i] - points[i-1]).normalize();
+                let after = (points[i+1] - points[i]).normalize();
+                before.dot(&after) < corner_cos
+            };
+            if is_corner || i+1 == points.len() {
+                let tangent1 = (points[first+1] - points[first]).normalize();
+                let tangent2 = (points[i-1] - points[i]).normalize();
+                fitter.fit(first, i, tangent1, tangent2);
+                first = i;
+            }
+        }
+        fitter.curves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::{groups::vector_spaces::NormedVectorSpace, num::FloatConsts};
+
+    use crate::{geometry::{CubicSegment2D, Segment}, vector::DVec2};
+
+    #[test]
+    fn fit_cubics() {
+        // a half circle sampled with a little noise
+        let points: super::alloc::vec::Vec<DVec2> = (0..=50).map(|i| {
+            let angle = f64::PI*i as f64/50.0;
+            let noise = if i % 2 == 0 { 0.002 } else { -0.002 };
+            DVec2::new(angle.cos(), angle.sin())*(1.0 + noise)
+        }).collect();
+        let curves = CubicSegment2D::fit(&points, 0.01, f64::FRAC_PI_2);
+        assert!(!curves.is_empty() && curves.len() <= 3);
+        assert_eq!(curves[0].start, points[0]);
+        assert_eq!(curves[curves.len()-1].end, points[50]);
+        for point in points.iter() {
+            let closest = curves.iter().flat_map(|curve| (0..=200).map(|i| Segment::get(curve, i as f64/200.0)))
+                .map(|on_curve| (on_curve - *point).length())
+                .fold(f64::INFINITY, f64::min);
+            assert!(closest <= 0.011);
+        }
+
+        // the corner of an L stays sharp
+        let l: super::alloc::vec::Vec<DVec2> = (0..=10).map(|i| DVec2::new(0.0, 1.0 - i as f64/10.0))
+            .chain((1..=10).map(|i| DVec2::new(i as f64/10.0, 0.0)))
+            .collect();
+        let curves = CubicSegment2D::fit(&l, 0.01, f64::FRAC_PI_4);
+        assert_eq!(curves.len(), 2);
+        assert_eq!(curves[0].end, DVec2::new(0.0, 0.0));
+        assert_eq!(curves[1].start, DVec2::new(0.0, 0.0));
+    }
+}
@@ -0,0 +1,122 @@
+use affogato_core::{groups::vector_spaces::VectorSpace, num::{FloatConsts, Number}, sets::Real};
+
+use crate::{lerp, vector::Vector2};
+
+use super::{ArcSegment2D, CubicSegment2D, LinearSegment2D, QuadraticSegment2D, Segment, Segment2D, SubPath2D};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// The deepest a cubic will be subdivided while flattening it, which bounds
+/// a single curve to 2^16 lines. Arcs are held to the same number of lines.
+const MAX_FLATTEN_DEPTH: usize = 16;
+
+/// Whether a cubic is within `tolerance` of the line joining its endpoints.
+/// The difference between the curve and the line parameterized as a cubic is
+/// `3t(1-t)^2 (p1-l1) + 3t^2(1-t) (p2-l2)`, which is at most 3/4 the largest
+/// distance between the control points and the points a third of the way
+/// along the line.
+fn is_flat<T: Real>(curve: &CubicSegment2D<T>, tolerance: T) -> bool {
+    let third = T::from_f64(1.0/3.0);
+    let deviation1 = curve.control1 - lerp(curve.start, curve.end, third);
+    let deviation2 = curve.control2 - lerp(curve.end, curve.start, third);
+    let deviation = deviation1.length_squared().max(deviation2.length_squared());
+    deviation*T::from_f64(9.0/16.0) <= tolerance*tolerance
+}
+
+impl<T: Number> LinearSegment2D<T> {
+    pub fn flatten_into(&self, _: T, points: &mut Vec<Vector2<T>>) {
+        points.push(self.end);
+    }
+    pub fn flatten(&self, tolerance: T) -> Vec<Vector2<T>> {
+        let mut points = alloc::vec![self.start];
+        self.flatten_into(tolerance, &mut points);
+        points
+    }
+}
+impl<T: Real> CubicSegment2D<T> {
+    /// Appends a polyline that stays within `tolerance` of the curve to
+    /// `points`, without the start of the curve. The curve is subdivided
+    /// only where it isn't flat enough, so straight parts use few lines.
+    pub fn flatten_into(&self, tolerance: T, points: &mut Vec<Vector2<T>>) {
+        let mut stack = alloc::vec![(*self, 0)];
+        while let Some((curve, depth)) = stack.pop() {
+            if depth >= MAX_FLATTEN_DEPTH || is_flat(&curve, tolerance) {
+                points.push(curve.end);
+                continue;
+            }
+            let (first, second) = curve.split(0.5);
+            stack.push((second, depth+1));
+            stack.push((first, depth+1));
+        }
+    }
+    /// A polyline that stays within `tolerance` of the curve.
+    pub fn flatten(&self, tolerance: T) -> Vec<Vector2<T>> {
+        let mut points = alloc::vec![self.start];
+        self.flatten_into(tolerance, &mut points);
+        points
+    }
+}
+impl<T: Real> QuadraticSegment2D<T> {
+    pub fn flatten_into(&self, tolerance: T, points: &mut Vec<Vector2<T>>) {
+        self.to_cubic().flatten_into(tolerance, points);
+    }
+    /// A polyline that stays within `tolerance` of the curve.
+    pub fn flatten(&self, tolerance: T) -> Vec<Vector2<T>> {
+        let mut points = alloc::vec![self.start];
+        self.flatten_into(tolerance, &mut points);
+        points
+    }
+}
+impl<T: Real> ArcSegment2D<T> {
+    /// Appends a polyline that stays within `tolerance` of the arc to
+    /// `points`, without the start of the arc.
+    pub fn flatten_into(&self, tolerance: T, points: &mut Vec<Vector2<T>>) {
+        let radius = self.radii.x().abs().max(self.radii.y().abs());
+        // the widest angle a chord can span while its sagitta stays within
+        // tolerance, a tolerance of zero or less makes as many lines as a cubic can
+        let step = if tolerance >= radius {
+            T::FRAC_PI_2
+        } else {
+            (T::ONE - tolerance.max(T::ZERO)/radius).acos()*T::from_f64(2.0)
+        };
+        let count = (self.sweep_angle.abs()/step).ceil().max(T::ONE).min(T::from_usize(1 << MAX_FLATTEN_DEPTH)).to_usize();
+        for i in 1..=count {
+            points.push(self.get(i as f64/count as f64));
+        }
+    }
+    /// A polyline that stays within `tolerance` of the arc.
+    pub fn flatten(&self, tolerance: T) -> Vec<Vector2<T>> {
+        let mut points = alloc::vec![self.start()];
+        self.flatten_into(tolerance, &mut points);
+        points
+    }
+}
+impl<T: Real> Segment2D<T> {
+    /// Appends a polyline that stays within `tolerance` of the segment to
+    /// `points`, without the start of the segment.
+    pub fn flatten_into(&self, tolerance: T, points: &mut Vec<Vector2<T>>) {
+        match self {
+            Segment2D::Linear(linear) => linear.flatten_into(tolerance, points),
+            Segment2D::Quadratic(quadratic) => quadratic.flatten_into(tolerance, points),
+            Segment2D::Cubic(cubic) => cubic.flatten_into(tolerance, points),
+            Segment2D::Arc(arc) => arc.flatten_into(tolerance, points),
+        }
+    }
+    /// A polyline that stays within `tolerance` of the segment.
+    pub fn flatten(&self, tolerance: T) -> Vec<Vector2<T>> {
+        let mut points = alloc::vec![self.start()];
+        self.flatten_into(tolerance, &mut points);
+        points
+    }
+}
+impl<T: Real> SubPath2D<T> {
+    /// A polyline that stays within `tolerance` of every segment in the
+    /// subpath. The line closing the subpath isn't included.
+    pub fn flatten(&self, tolerance: T) -> Vec<Vector2<T>> {
+        let mut points = alloc::vec![self.start];
+        for segment in self.segments.iter() {
+            segment.flatten_into(tolerance, &mut points);
+        }
+        points
+    }
+}
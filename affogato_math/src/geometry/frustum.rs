@@ -0,0 +1,150 @@
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::Signed, sets::Real};
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{matrix::Matrix4, vector::{Vector3, Vector4}};
+
+use super::{OrientedBox3D, Plane, Rect3D, Sphere};
+
+/// Where a shape is relative to a [`Frustum`].
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Containment {
+    Inside,
+    Outside,
+    /// The shape crosses at least one of the planes. The tests only look at
+    /// one plane at a time, so shapes just outside a corner of the frustum
+    /// can be reported here too.
+    Intersecting,
+}
+
+/// The volume a camera can see, bounded by six planes whose normals point
+/// inwards.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug)]
+pub struct Frustum<T: Real> {
+    /// In the order left, right, bottom, top, near and far.
+    pub planes: [Plane<T>; 6],
+}
+impl<T: Real> Frustum<T> {
+    pub const LEFT: usize = 0;
+    pub const RIGHT: usize = 1;
+    pub const BOTTOM: usize = 2;
+    pub const TOP: usize = 3;
+    pub const NEAR: usize = 4;
+    pub const FAR: usize = 5;
+    /// Extracts the planes from a view projection matrix using the
+    /// Gribb-Hartmann method, a point is inside when its clip coordinates
+    /// have `-w <= x <= w`, `-w <= y <= w` and `0 <= z <= w`, which is the
+    /// depth range the camera projections use. The planes are in the space
+    /// the matrix takes points from, world space for `projection*view`.
+    pub fn from_matrix(matrix: &Matrix4<T>) -> Self {
+        let row = |index: usize| Vector4::new(matrix.x[index], matrix.y[index], matrix.z[index], matrix.w[index]);
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        let plane = |row: Vector4<T>| {
+            let normal = row.xyz();
+            Plane::from_normal_distance(normal, -row.w()/normal.length())
+        };
+        Self { planes: [plane(w + x), plane(w - x), plane(w + y), plane(w - y), plane(z), plane(w - z)] }
+    }
+    /// The point where three planes meet.
+    fn meet(a: &Plane<T>, b: &Plane<T>, c: &Plane<T>) -> Vector3<T> {
+        let (bc, ca, ab) = (b.normal().cross(&c.normal()), c.normal().cross(&a.normal()), a.normal().cross(&b.normal()));
+        (bc*a.distance() + ca*b.distance() + ab*c.distance())/a.normal().dot(&bc)
+    }
+    /// The corners of the near face and then the far face, each going
+    /// bottom left, bottom right, top right and top left.
+    pub fn corners(&self) -> [Vector3<T>; 8] {
+        let [left, right, bottom, top, near, far] = &self.planes;
+        [
+            Self::meet(left, bottom, near), Self::meet(right, bottom, near), Self::meet(right, top, near), Self::meet(left, top, near),
+            Self::meet(left, bottom, far), Self::meet(right, bottom, far), Self::meet(right, top, far), Self::meet(left, top, far),
+        ]
+    }
+    /// Points on the boundary count as inside, a point is never
+    /// [`Containment::Intersecting`].
+    pub fn classify_point(&self, point: Vector3<T>) -> Containment {
+        if self.planes.iter().all(|plane| plane.is_point_on_positive_side(point)) {
+            Containment::Inside
+        } else {
+            Containment::Outside
+        }
+    }
+    /// Compares the distance from every plane to `center` with how far the
+    /// shape reaches towards that plane.
+    fn classify(&self, center: Vector3<T>, reach: impl Fn(Vector3<T>) -> T) -> Containment {
+        let mut containment = Containment::Inside;
+        for plane in &self.planes {
+            let distance = plane.normal().dot(&center) - plane.distance();
+            let reach = reach(plane.normal());
+            if distance < -reach {
+                return Containment::Outside;
+            }
+            if distance < reach {
+                containment = Containment::Intersecting;
+            }
+        }
+        containment
+    }
+    pub fn classify_sphere(&self, sphere: &Sphere<T>) -> Containment {
+        self.classify(sphere.center, |_| sphere.radius)
+    }
+    pub fn classify_rect(&self, rect: &Rect3D<T>) -> Containment {
+        let half_size = (rect.max - rect.min)*T::from_f64(0.5);
+        self.classify((rect.min + rect.max)*T::from_f64(0.5), |normal| {
+            normal.x().abs()*half_size.x() + normal.y().abs()*half_size.y() + normal.z().abs()*half_size.z()
+        })
+    }
+    pub fn classify_oriented_box(&self, oriented_box: &OrientedBox3D<T>) -> Containment {
+        let axes = oriented_box.axes();
+        let half_extents = oriented_box.half_extents;
+        self.classify(oriented_box.center, |normal| {
+            normal.dot(&axes[0]).abs()*half_extents.x() + normal.dot(&axes[1]).abs()*half_extents.y() + normal.dot(&axes[2]).abs()*half_extents.z()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use affogato_core::groups::vector_spaces::NormedVectorSpace;
+
+    use crate::{algebra::Quaternion, geometry::{OrientedBox3D, Rect3D, Sphere}, matrix::Matrix4, vector::DVec3};
+
+    use super::{Containment, Frustum};
+
+    #[test]
+    fn frustum_culling() {
+        // a 90 degree perspective looking down +z from 1 to 10
+        let projection = Matrix4::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 10.0/9.0, 1.0,
+            0.0, 0.0, -10.0/9.0, 0.0,
+        );
+        let frustum = Frustum::from_matrix(&projection);
+        let corners = frustum.corners();
+        assert!((corners[0] - DVec3::new(-1.0, -1.0, 1.0)).length() < 1e-12);
+        assert!((corners[6] - DVec3::new(10.0, 10.0, 10.0)).length() < 1e-12);
+
+        assert_eq!(frustum.classify_point(DVec3::new(0.0, 0.0, 5.0)), Containment::Inside);
+        assert_eq!(frustum.classify_point(DVec3::new(6.0, 0.0, 5.0)), Containment::Outside);
+        assert_eq!(frustum.classify_point(DVec3::new(0.0, 0.0, 11.0)), Containment::Outside);
+
+        assert_eq!(frustum.classify_sphere(&Sphere::new(DVec3::new(0.0, 0.0, 5.0), 1.0)), Containment::Inside);
+        assert_eq!(frustum.classify_sphere(&Sphere::new(DVec3::new(0.0, 0.0, -5.0), 1.0)), Containment::Outside);
+        assert_eq!(frustum.classify_sphere(&Sphere::new(DVec3::new(0.0, 0.0, 1.0), 0.5)), Containment::Intersecting);
+
+        assert_eq!(frustum.classify_rect(&Rect3D::new(DVec3::new(-1.0, -1.0, 4.0), DVec3::new(1.0, 1.0, 6.0))), Containment::Inside);
+        assert_eq!(frustum.classify_rect(&Rect3D::new(DVec3::new(4.0, -1.0, 4.0), DVec3::new(6.0, 1.0, 6.0))), Containment::Intersecting);
+        assert_eq!(frustum.classify_rect(&Rect3D::new(DVec3::new(7.0, -1.0, 4.0), DVec3::new(9.0, 1.0, 6.0))), Containment::Outside);
+
+        // turned 45 degrees the cube's faces line up with the side planes, so it reaches less far towards them
+        let turned = Quaternion::angle_axis(PI/4.0, DVec3::new(0.0, 1.0, 0.0));
+        let half = DVec3::new(1.0, 1.0, 1.0);
+        assert_eq!(frustum.classify_oriented_box(&OrientedBox3D::new(DVec3::new(3.5, 0.0, 5.0), half, Quaternion::identity())), Containment::Intersecting);
+        assert_eq!(frustum.classify_oriented_box(&OrientedBox3D::new(DVec3::new(3.5, 0.0, 5.0), half, turned)), Containment::Inside);
+        assert_eq!(frustum.classify_oriented_box(&OrientedBox3D::new(DVec3::new(20.0, 0.0, 5.0), half, turned)), Containment::Outside);
+    }
+}
@@ -0,0 +1,248 @@
+use core::cmp::Ordering;
+
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::Zero, sets::Real};
+
+use crate::vector::{Vector2, Vector3};
+
+use super::{orient2d, Polygon2D};
+extern crate alloc;
+use alloc::vec::Vec;
+
+fn compare_points<T: Real>(a: Vector2<T>, b: Vector2<T>) -> Ordering {
+    a.x().partial_cmp(&b.x()).unwrap_or(Ordering::Equal)
+        .then(a.y().partial_cmp(&b.y()).unwrap_or(Ordering::Equal))
+}
+
+/// Adds the points of `order` that keep turning left, leaving out the last
+/// one where the next chain starts.
+fn monotone_chain<T: Real>(points: &[Vector2<T>], order: impl Iterator<Item = u32>, hull: &mut Vec<u32>) {
+    let start = hull.len();
+    for index in order {
+        if hull.len() > start && points[hull[hull.len()-1] as usize] == points[index as usize] {
+            continue;
+        }
+        while hull.len() >= start + 2 && orient2d(points[hull[hull.len()-2] as usize], points[hull[hull.len()-1] as usize], points[index as usize]) <= T::ZERO {
+            hull.pop();
+        }
+        hull.push(index);
+    }
+    hull.pop();
+}
+
+/// The indices of the points on the convex hull winding counterclockwise,
+/// using Andrew's monotone chain. Points on the edges of the hull are left
+/// out, collinear points give the two ends of the line and identical points
+/// give just one of them.
+pub fn convex_hull_2d<T: Real>(points: &[Vector2<T>]) -> Vec<u32> {
+    let mut sorted: Vec<u32> = (0..points.len() as u32).collect();
+    sorted.sort_by(|&a, &b| compare_points(points[a as usize], points[b as usize]));
+    let mut hull: Vec<u32> = Vec::with_capacity(points.len() + 1);
+    // the lower chain going right, then the upper chain coming back
+    monotone_chain(points, sorted.iter().copied(), &mut hull);
+    monotone_chain(points, sorted.iter().copied().rev(), &mut hull);
+    if hull.is_empty() {
+        hull.extend(sorted.first());
+    }
+    hull
+}
+
+/// Adds the points strictly right of the edge from `a` to `b` that are on the
+/// hull, in order along it.
+fn quickhull_side<T: Real>(points: &[Vector2<T>], candidates: &[u32], a: u32, b: u32, hull: &mut Vec<u32>) {
+    let (start, end) = (points[a as usize], points[b as usize]);
+    // of the points tied for farthest, which lie on a line parallel to the
+    // edge, only the ones at the ends of that line are on the hull
+    let along = |index: u32| (points[index as usize]-start).dot(&(end-start));
+    let farthest = candidates.iter().copied().map(|index| (orient2d(start, end, points[index as usize]), index))
+        .filter(|&(side, _)| side < T::ZERO)
+        .reduce(|best, candidate| if candidate.0 < best.0 || (candidate.0 == best.0 && along(candidate.1) > along(best.1)) { candidate } else { best });
+    let Some((_, farthest)) = farthest else {
+        return;
+    };
+    let outside: Vec<u32> = candidates.iter().copied().filter(|&index| orient2d(start, end, points[index as usize]) < T::ZERO).collect();
+    quickhull_side(points, &outside, a, farthest, hull);
+    hull.push(farthest);
+    quickhull_side(points, &outside, farthest, b, hull);
+}
+
+/// The same hull as [`convex_hull_2d`] using QuickHull, which splits off the
+/// points that can't be on the hull early and is faster when most of them
+/// are inside of it.
+pub fn quickhull_2d<T: Real>(points: &[Vector2<T>]) -> Vec<u32> {
+    let indices: Vec<u32> = (0..points.len() as u32).collect();
+    let compare = |a: &u32, b: &u32| compare_points(points[*a as usize], points[*b as usize]);
+    let (Some(left), Some(right)) = (indices.iter().copied().min_by(compare), indices.iter().copied().max_by(compare)) else {
+        return Vec::new();
+    };
+    let mut hull = alloc::vec![left];
+    if points[left as usize] == points[right as usize] {
+        return hull;
+    }
+    quickhull_side(points, &indices, left, right, &mut hull);
+    hull.push(right);
+    quickhull_side(points, &indices, right, left, &mut hull);
+    hull
+}
+
+/// How far `point` is in front of the triangle, where the front is the side
+/// it winds counterclockwise on.
+fn plane_distance<T: Real>(points: &[Vector3<T>], face: [u32; 3], point: Vector3<T>) -> T {
+    let [a, b, c] = face.map(|index| points[index as usize]);
+    let normal = (b-a).cross(&(c-a));
+    let length = normal.length();
+    if length == T::ZERO { T::ZERO } else { normal.dot(&(point-a))/length }
+}
+
+/// The triangles of the convex hull as indices into `points`, each winding
+/// counterclockwise seen from outside. The hull is built by adding one point
+/// at a time and replacing the faces it can see.
+///
+/// Points closer to a face than a small tolerance relative to the size of
+/// the point cloud count as on it, so nearly coplanar points don't make
+/// slivers. Coplanar points give the flat hull as triangles facing both ways,
+/// and collinear points give no triangles at all.
+pub fn convex_hull_3d<T: Real>(points: &[Vector3<T>]) -> Vec<u32> {
+    let Some(&first) = points.first() else {
+        return Vec::new();
+    };
+    let (min, max) = points.iter().fold((first, first), |(min, max), &point| {
+        (Vector3::new(min.x().min(point.x()), min.y().min(point.y()), min.z().min(point.z())),
+         Vector3::new(max.x().max(point.x()), max.y().max(point.y()), max.z().max(point.z())))
+    });
+    let tolerance = T::EPSILON.sqrt()*(max-min).length();
+    let farthest = |distance: &dyn Fn(Vector3<T>) -> T| -> (T, u32) {
+        (0..points.len() as u32).map(|index| (distance(points[index as usize]), index))
+            .fold((-T::INFINITY, 0), |best, candidate| if candidate.0 > best.0 { candidate } else { best })
+    };
+    // start from the largest tetrahedron that is quick to find
+    let a = farthest(&|point| -point.x()).1;
+    let origin = points[a as usize];
+    let (distance, b) = farthest(&|point| (point-origin).length());
+    if distance <= tolerance {
+        return Vec::new();
+    }
+    let axis = (points[b as usize]-origin).normalize();
+    let (distance, c) = farthest(&|point| (point-origin).cross(&axis).length());
+    if distance <= tolerance {
+        return Vec::new();
+    }
+    let normal = (points[b as usize]-origin).cross(&(points[c as usize]-origin)).normalize();
+    let (distance, d) = farthest(&|point| (point-origin).dot(&normal).abs());
+    if distance <= tolerance {
+        return flat_hull(points, origin, axis, normal);
+    }
+    let mut faces = alloc::vec![[a, b, c], [a, c, d], [a, d, b], [b, d, c]];
+    if plane_distance(points, faces[0], points[d as usize]) > T::ZERO {
+        for face in faces.iter_mut() {
+            face.swap(1, 2);
+        }
+    }
+    // going from the outside in, points on the faces of the hull are mostly
+    // added after its corners and don't split them up
+    let center = [a, b, c, d].iter().fold(Vector3::ZERO, |sum, &index| sum + points[index as usize])/T::from_f64(4.0);
+    let mut order: Vec<(T, u32)> = (0..points.len() as u32).map(|index| ((points[index as usize]-center).length_squared(), index)).collect();
+    order.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+    let mut visible_edges: Vec<(u32, u32)> = Vec::new();
+    for (_, index) in order {
+        let point = points[index as usize];
+        if [a, b, c, d].contains(&index) || faces.iter().all(|&face| plane_distance(points, face, point) <= tolerance) {
+            continue;
+        }
+        visible_edges.clear();
+        faces.retain(|&face| {
+            let visible = plane_distance(points, face, point) > tolerance;
+            if visible {
+                visible_edges.extend([(face[0], face[1]), (face[1], face[2]), (face[2], face[0])]);
+            }
+            !visible
+        });
+        visible_edges.sort_unstable();
+        // the edges between the faces the point sees and those it doesn't
+        for &(from, to) in visible_edges.iter() {
+            if visible_edges.binary_search(&(to, from)).is_err() {
+                faces.push([from, to, index]);
+            }
+        }
+    }
+    faces.into_iter().flatten().collect()
+}
+
+/// The hull of coplanar points as a fan on each side of their plane.
+fn flat_hull<T: Real>(points: &[Vector3<T>], origin: Vector3<T>, axis: Vector3<T>, normal: Vector3<T>) -> Vec<u32> {
+    let other = normal.cross(&axis);
+    let flat: Vec<Vector2<T>> = points.iter().map(|&point| Vector2::new((point-origin).dot(&axis), (point-origin).dot(&other))).collect();
+    let ring = convex_hull_2d(&flat);
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2)*6);
+    for i in 1..ring.len().saturating_sub(1) {
+        triangles.extend([ring[0], ring[i], ring[i+1]]);
+        triangles.extend([ring[0], ring[i+1], ring[i]]);
+    }
+    triangles
+}
+
+impl<T: Real> Polygon2D<T> {
+    /// The convex hull of the vertices, see [`convex_hull_2d`].
+    pub fn convex_hull(&self) -> Polygon2D<T> {
+        Polygon2D::new(convex_hull_2d(&self.vertices).into_iter().map(|index| self.vertices[index as usize]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::groups::vector_spaces::VectorSpace;
+
+    use crate::{geometry::{convex_hull_2d, convex_hull_3d, quickhull_2d}, vector::{DVec2, DVec3}};
+
+    use super::alloc::vec::Vec;
+
+    #[test]
+    fn hulls_2d() {
+        let mut points = Vec::new();
+        for x in 0..5 {
+            for y in 0..4 {
+                points.push(DVec2::new(x as f64, y as f64));
+            }
+        }
+        // corners are at 0, 3, 16 and 19, counterclockwise from the bottom left
+        assert_eq!(convex_hull_2d(&points), [0, 16, 19, 3]);
+        assert_eq!(quickhull_2d(&points), [0, 16, 19, 3]);
+
+        let line = [DVec2::new(2.0, 2.0), DVec2::new(0.0, 0.0), DVec2::new(1.0, 1.0)];
+        assert_eq!(convex_hull_2d(&line), [1, 0]);
+        assert_eq!(quickhull_2d(&line), [1, 0]);
+        let same = [DVec2::new(1.0, 1.0); 3];
+        assert_eq!(convex_hull_2d(&same).len(), 1);
+        assert_eq!(quickhull_2d(&same).len(), 1);
+
+        // (1, 2) ties with (2, 3) and (0, 1) for farthest from the bottom edge but lies between them
+        let tied = [DVec2::new(0.0, 0.0), DVec2::new(3.0, 0.0), DVec2::new(3.0, 3.0), DVec2::new(1.0, 2.0), DVec2::new(0.0, 1.0), DVec2::new(2.0, 3.0)];
+        assert_eq!(convex_hull_2d(&tied), [0, 1, 2, 5, 4]);
+        assert_eq!(quickhull_2d(&tied), convex_hull_2d(&tied));
+        let mirrored: Vec<DVec2> = tied.iter().map(|point| DVec2::new(-point.x(), point.y())).collect();
+        assert_eq!(quickhull_2d(&mirrored), convex_hull_2d(&mirrored));
+    }
+
+    #[test]
+    fn hulls_3d() {
+        // a cube with points inside it and on its faces and edges
+        let mut points = Vec::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                for z in 0..3 {
+                    points.push(DVec3::new(x as f64, y as f64, z as f64));
+                }
+            }
+        }
+        let triangles = convex_hull_3d(&points);
+        assert_eq!(triangles.len(), 12*3);
+        let volume: f64 = triangles.chunks_exact(3).map(|triangle| {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|index| points[index as usize]);
+            a.dot(&b.cross(&c))/6.0
+        }).sum();
+        assert!((volume - 8.0).abs() < 1e-9);
+
+        let square = [DVec3::new(0.0, 0.0, 1.0), DVec3::new(1.0, 0.0, 1.0), DVec3::new(1.0, 1.0, 1.0), DVec3::new(0.0, 1.0, 1.0), DVec3::new(0.5, 0.5, 1.0)];
+        assert_eq!(convex_hull_3d(&square).len(), 4*3);
+        assert!(convex_hull_3d(&[DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 1.0, 1.0), DVec3::new(2.0, 2.0, 2.0)]).is_empty());
+    }
+}
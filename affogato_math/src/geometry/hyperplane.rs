@@ -1,5 +1,9 @@
+use core::cmp::Ordering;
+
 use crate::{vector::Vector3, IsNormalized};
 
+use super::plane_side;
+
 use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::Number, sets::Real};
 #[cfg(feature="serde")]
 use serde::{Serialize, Deserialize};
@@ -54,6 +58,14 @@ impl<T: Number> Plane<T> {
     pub fn is_point_contained(&self, point: Vector3<T>) -> bool {
         self.normal.dot(&point) - self.distance == T::ZERO
     }
+    /// Which side of the plane `point` is on worked out exactly, `Greater`
+    /// for the side the normal points to and `Equal` only when it is exactly
+    /// on the plane. The other side tests round the distance first, so they
+    /// can get points very close to the plane wrong.
+    pub fn side_exact(&self, point: Vector3<T>) -> Ordering 
+        where T: Real {
+        plane_side(self.normal, self.distance, point)
+    }
     pub fn normal(&self) -> Vector3<T> {
         self.normal
     }
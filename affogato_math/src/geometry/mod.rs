@@ -2,16 +2,78 @@ mod rect;
 mod triangle;
 mod sphere;
 mod segment;
+mod segment3d;
+mod bezier;
+mod arc;
+mod segment_intersection;
+#[cfg(feature="alloc")]
+mod path;
+#[cfg(feature="alloc")]
+mod flatten;
+#[cfg(feature="alloc")]
+mod stroke;
+#[cfg(feature="alloc")]
+mod simplify;
+#[cfg(feature="alloc")]
+mod fit;
+#[cfg(feature="alloc")]
+mod offset;
+#[cfg(feature="alloc")]
+mod polygon;
+#[cfg(feature="alloc")]
+mod triangulate;
+#[cfg(feature="alloc")]
+mod boolean;
+#[cfg(feature="alloc")]
+mod decompose;
+#[cfg(feature="alloc")]
+mod hull;
+#[cfg(feature="alloc")]
+mod delaunay;
+mod predicates;
+mod bounding;
+mod shape;
+mod solid;
 mod ray;
 mod hyperplane;
+mod frustum;
 
 use affogato_core::{groups::vector_spaces::VectorSpace, num::Number, sets::Real};
 pub use rect::*;
 pub use triangle::*;
 pub use sphere::*;
 pub use segment::*;
+pub use segment3d::*;
+pub use bezier::*;
+pub use arc::*;
+pub use segment_intersection::*;
+#[cfg(feature="alloc")]
+pub use path::*;
+#[cfg(feature="alloc")]
+pub use stroke::*;
+#[cfg(feature="alloc")]
+pub use simplify::*;
+#[cfg(feature="alloc")]
+pub use offset::*;
+#[cfg(feature="alloc")]
+pub use polygon::*;
+#[cfg(feature="alloc")]
+pub use triangulate::*;
+#[cfg(feature="alloc")]
+pub use boolean::*;
+#[cfg(feature="alloc")]
+pub use decompose::*;
+#[cfg(feature="alloc")]
+pub use hull::*;
+#[cfg(feature="alloc")]
+pub use delaunay::*;
+pub use predicates::*;
+pub use bounding::*;
+pub use shape::*;
+pub use solid::*;
 pub use ray::*;
 pub use hyperplane::*;
+pub use frustum::*;
 
 use crate::{algebra::Quaternion, matrix::{Matrix2, Matrix3, Matrix4}, vector::{Vector2, Vector3, Vector4}};
 
@@ -20,14 +82,47 @@ pub trait CalculateCentroid {
     /// The centroid is the center of a geometric object.
     fn centroid(&self) -> Self::Vector;
 }
+/// Shapes that can give their point farthest along a direction, which is
+/// all GJK needs to know about a convex shape.
+pub trait Support {
+    type Vector: VectorSpace;
+    /// The point of the shape farthest along `direction`.
+    fn support(&self, direction: Self::Vector) -> Self::Vector;
+}
 pub type FLinearSegment2D = LinearSegment2D<f32>;
 pub type DLinearSegment2D = LinearSegment2D<f64>;
 pub type FQuadraticSegment2D = QuadraticSegment2D<f32>;
 pub type DQuadraticSegment2D = QuadraticSegment2D<f64>;
 pub type FCubicSegment2D = CubicSegment2D<f32>;
 pub type DCubicSegment2D = CubicSegment2D<f64>;
+pub type FArcSegment2D = ArcSegment2D<f32>;
+pub type DArcSegment2D = ArcSegment2D<f64>;
 pub type FSegment2D = Segment2D<f32>;
 pub type DSegment2D = Segment2D<f64>;
+pub type FLinearSegment3D = LinearSegment3D<f32>;
+pub type DLinearSegment3D = LinearSegment3D<f64>;
+pub type FQuadraticSegment3D = QuadraticSegment3D<f32>;
+pub type DQuadraticSegment3D = QuadraticSegment3D<f64>;
+pub type FCubicSegment3D = CubicSegment3D<f32>;
+pub type DCubicSegment3D = CubicSegment3D<f64>;
+pub type FSegment3D = Segment3D<f32>;
+pub type DSegment3D = Segment3D<f64>;
+#[cfg(feature="alloc")]
+pub type FPath2D = Path2D<f32>;
+#[cfg(feature="alloc")]
+pub type DPath2D = Path2D<f64>;
+#[cfg(feature="alloc")]
+pub type FPolygon2D = Polygon2D<f32>;
+#[cfg(feature="alloc")]
+pub type DPolygon2D = Polygon2D<f64>;
+#[cfg(feature="alloc")]
+pub type FPolygon3D = Polygon3D<f32>;
+#[cfg(feature="alloc")]
+pub type DPolygon3D = Polygon3D<f64>;
+#[cfg(feature="alloc")]
+pub type FMultiPolygon2D = MultiPolygon2D<f32>;
+#[cfg(feature="alloc")]
+pub type DMultiPolygon2D = MultiPolygon2D<f64>;
 
 pub trait Dimension {
     const DIMENSION: usize;
@@ -48,6 +143,18 @@ impl<T: Number> Dimension for Rect<T> {
 impl<T: Number> Dimension for Triangle2D<T> {
     const DIMENSION: usize = 2;
 }
+impl<T: Number> Dimension for Ellipse<T> {
+    const DIMENSION: usize = 2;
+}
+impl<T: Number> Dimension for Capsule2D<T> {
+    const DIMENSION: usize = 2;
+}
+impl<T: Number> Dimension for OrientedRect<T> {
+    const DIMENSION: usize = 2;
+}
+impl<T: Number> Dimension for RoundedRect<T> {
+    const DIMENSION: usize = 2;
+}
 impl<T: Number> Dimension for Vector3<T> {
     const DIMENSION: usize = 3;
 }
@@ -63,12 +170,27 @@ impl<T: Number> Dimension for Rect3D<T> {
 impl<T: Number> Dimension for Triangle3D<T> {
     const DIMENSION: usize = 3;
 }
+impl<T: Number> Dimension for Capsule<T> {
+    const DIMENSION: usize = 3;
+}
+impl<T: Number> Dimension for Cylinder<T> {
+    const DIMENSION: usize = 3;
+}
+impl<T: Number> Dimension for Cone<T> {
+    const DIMENSION: usize = 3;
+}
+impl<T: Number> Dimension for Torus<T> {
+    const DIMENSION: usize = 3;
+}
+impl<T: Number> Dimension for OrientedBox3D<T> {
+    const DIMENSION: usize = 3;
+}
 impl<T: Number> Dimension for Vector4<T> {
     const DIMENSION: usize = 4;
 }
 impl<T: Number> Dimension for Matrix4<T> {
     const DIMENSION: usize = 4;
 }
-impl<T: Real> Dimension for Quaternion<T> {
+impl<T: Number> Dimension for Quaternion<T> {
     const DIMENSION: usize = 4;
 }
\ No newline at end of file
@@ -0,0 +1,260 @@
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, sets::Real};
+
+use crate::vector::Vector2;
+
+use super::{fit::fit_with_tangents, ArcSegment2D, CubicSegment2D, LineJoin, Segment, Segment2D};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// The deepest a curve will be subdivided while offsetting it.
+const MAX_OFFSET_DEPTH: usize = 10;
+/// The amount of points on the offset curve each cubic is fit to.
+const OFFSET_SAMPLES: usize = 8;
+
+/// Twice the signed area of a polygon, positive when it winds
+/// counterclockwise.
+fn doubled_signed_area<T: Real>(points: &[Vector2<T>]) -> T {
+    points.iter().zip(points.iter().cycle().skip(1))
+        .fold(T::ZERO, |area, (a, b)| area + a.cross(b))
+}
+
+impl<T: Real> Segment2D<T> {
+    /// The unit normal at t, on the left of the direction of travel.
+    fn normal(&self, t: f64) -> Vector2<T> {
+        let mut direction = self.derivative(t);
+        if direction.length_squared() == T::ZERO {
+            direction = self.direction_at(t);
+        }
+        direction.normalize().rotate_90()
+    }
+    /// The curve that stays `distance` away from the segment, on the left of
+    /// its direction of travel for positive distances and on the right for
+    /// negative ones. Lines and circular arcs are offset exactly, other
+    /// curves are approximated by cubics that stay within `tolerance` of the
+    /// true offset.
+    ///
+    /// Where the distance is larger than the radius of the curve the offset
+    /// has cusps and loops, which are left in.
+    pub fn offset(&self, distance: T, tolerance: T) -> Vec<Segment2D<T>> {
+        match self {
+            Segment2D::Linear(linear) => {
+                if linear.start == linear.end {
+                    return alloc::vec![self.clone()];
+                }
+                let normal = self.normal(0.0)*distance;
+                alloc::vec![Segment2D::linear(linear.start + normal, linear.end + normal)]
+            },
+            // the left of a counterclockwise arc is toward its center
+            Segment2D::Arc(arc) if arc.is_circular() && arc.radii.x() - distance*arc.sweep_angle.signum() > T::ZERO => {
+                let radius = arc.radii.x() - distance*arc.sweep_angle.signum();
+                alloc::vec![Segment2D::Arc(ArcSegment2D::new(arc.center, Vector2::new(radius, radius), arc.rotation, arc.start_angle, arc.sweep_angle))]
+            },
+            _ => self.offset_cubics(distance, tolerance).into_iter().map(Segment2D::Cubic).collect(),
+        }
+    }
+    /// The offset of the segment approximated by cubics, see [`Self::offset`].
+    pub fn offset_cubics(&self, distance: T, tolerance: T) -> Vec<CubicSegment2D<T>> {
+        let offset_at = |t: f64| Segment::get(self, t) + self.normal(t)*distance;
+        let mut curves = Vec::new();
+        let mut stack = alloc::vec![(0.0, 1.0, 0)];
+        while let Some((t0, t1, depth)) = stack.pop() {
+            let samples: Vec<(T, Vector2<T>)> = (0..=OFFSET_SAMPLES).map(|i| {
+                let u = i as f64/OFFSET_SAMPLES as f64;
+                (T::from_f64(u), offset_at(t0 + (t1 - t0)*u))
+            }).collect();
+            let (parameters, points): (Vec<T>, Vec<Vector2<T>>) = samples.into_iter().unzip();
+            // the offset runs parallel to the curve
+            let tangent1 = self.normal(t0).rotate_270();
+            let tangent2 = self.normal(t1).rotate_90();
+            let [start, control1, control2, end] = fit_with_tangents(&points, &parameters, tangent1, tangent2);
+            let curve = CubicSegment2D::new(start, control1, control2, end);
+            // check between the samples, where the fit wasn't pulled toward the offset
+            let error = (0..OFFSET_SAMPLES).map(|i| {
+                let u = (i as f64 + 0.5)/OFFSET_SAMPLES as f64;
+                (Segment::get(&curve, u) - offset_at(t0 + (t1 - t0)*u)).length()
+            }).fold(T::ZERO, |max, error| max.max(error));
+            if error <= tolerance || depth >= MAX_OFFSET_DEPTH {
+                curves.push(curve);
+            } else {
+                let middle = (t0 + t1)/2.0;
+                stack.push((middle, t1, depth+1));
+                stack.push((t0, middle, depth+1));
+            }
+        }
+        curves
+    }
+}
+
+/// Grows a closed polygon outward by `distance`, or shrinks it for negative
+/// distances, whichever way it winds. This is the Minkowski sum of the
+/// polygon with a disc when `join` is [`LineJoin::Round`], which stays within
+/// `tolerance` of the true arcs. Miters longer than `miter_limit` times the
+/// distance are beveled.
+///
+/// Shrinking by more than the size of a feature of the polygon makes the
+/// result fold over itself there.
+pub fn offset_polygon<T: Real>(points: &[Vector2<T>], distance: T, join: LineJoin, miter_limit: T, tolerance: T) -> Vec<Vector2<T>> {
+    let mut points = points.to_vec();
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 3 || distance == T::ZERO {
+        return points;
+    }
+    let winding = doubled_signed_area(&points).signum();
+    let count = points.len();
+    let direction = |i: usize| (points[(i+1)%count] - points[i]).normalize();
+    let mut offset = Vec::with_capacity(count);
+    for (i, &point) in points.iter().enumerate() {
+        let incoming = direction((i + count - 1)%count);
+        let outgoing = direction(i);
+        // the outside of a counterclockwise polygon is on the right
+        let from = incoming.rotate_90()*(-winding*distance);
+        let to = outgoing.rotate_90()*(-winding*distance);
+        let dot = incoming.dot(&outgoing);
+        let turn = incoming.cross(&outgoing)*winding;
+        // where the offset moves into the corner the two offset edges cross,
+        // which is where the miter point would be
+        let is_outside = turn*distance > T::ZERO;
+        let within_miter_limit = dot > -T::ONE && T::from_f64(2.0) <= miter_limit*miter_limit*(T::ONE + dot);
+        if turn == T::ZERO && dot > T::ZERO {
+            offset.push(point + from);
+        } else if !is_outside && dot > -T::ONE {
+            offset.push(point + (from + to)/(T::ONE + dot));
+        } else {
+            match join {
+                LineJoin::Miter if within_miter_limit => offset.push(point + (from + to)/(T::ONE + dot)),
+                LineJoin::Round => {
+                    let radius = distance.abs();
+                    let step = if tolerance >= radius || tolerance <= T::ZERO {
+                        T::FRAC_PI_2
+                    } else {
+                        (T::ONE - tolerance/radius).acos()*T::from_f64(2.0)
+                    };
+                    let angle = from.cross(&to).atan2(from.dot(&to));
+                    let steps = (angle.abs()/step).ceil().max(T::ONE).to_usize();
+                    for step in 0..=steps {
+                        let (sin, cos) = (angle*T::from_usize(step)/T::from_usize(steps)).sin_cos();
+                        offset.push(point + Vector2::new(from.x()*cos - from.y()*sin, from.x()*sin + from.y()*cos));
+                    }
+                },
+                LineJoin::Miter | LineJoin::Bevel => {
+                    offset.push(point + from);
+                    offset.push(point + to);
+                },
+            }
+        }
+    }
+    offset
+}
+
+/// The Minkowski sum of two convex polygons, which is the convex polygon
+/// covering every point of `a` moved by every point of `b`. The polygons can
+/// wind either way, and the sum winds counterclockwise.
+pub fn minkowski_sum<T: Real>(a: &[Vector2<T>], b: &[Vector2<T>]) -> Vec<Vector2<T>> {
+    // both polygons counterclockwise, starting from their lowest point
+    let normalize = |points: &[Vector2<T>]| {
+        let mut points = points.to_vec();
+        if doubled_signed_area(&points) < T::ZERO {
+            points.reverse();
+        }
+        let lowest = points.iter().enumerate().fold(0, |lowest, (i, point)| {
+            let current = points[lowest];
+            if point.y() < current.y() || (point.y() == current.y() && point.x() < current.x()) { i } else { lowest }
+        });
+        points.rotate_left(lowest);
+        points
+    };
+    let (a, b) = (normalize(a), normalize(b));
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let (n, m) = (a.len(), b.len());
+    let mut sum = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    // merge the edges of both polygons in order of their angle
+    while i < n || j < m {
+        sum.push(a[i%n] + b[j%m]);
+        let turn = (a[(i+1)%n] - a[i%n]).cross(&(b[(j+1)%m] - b[j%m]));
+        if j == m || (i < n && turn > T::ZERO) {
+            i += 1;
+        } else if i == n || turn < T::ZERO {
+            j += 1;
+        } else {
+            i += 1;
+            j += 1;
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::{groups::vector_spaces::NormedVectorSpace, num::FloatConsts};
+
+    use crate::{geometry::{minkowski_sum, offset_polygon, LineJoin, Segment, Segment2D}, vector::DVec2};
+
+    #[test]
+    fn offset_curves() {
+        let curve = Segment2D::cubic(DVec2::new(0.0, 0.0), DVec2::new(1.0, 2.0), DVec2::new(3.0, 2.0), DVec2::new(4.0, 0.0));
+        let offset = curve.offset(0.25, 0.001);
+        let fine: super::alloc::vec::Vec<DVec2> = (0..=1000).map(|i| Segment::get(&curve, i as f64/1000.0)).collect();
+        for segment in offset.iter() {
+            for i in 0..=20 {
+                let point = Segment::get(segment, i as f64/20.0);
+                let distance = fine.iter().map(|on_curve| (*on_curve - point).length()).fold(f64::INFINITY, f64::min);
+                assert!((distance - 0.25).abs() < 0.002);
+            }
+        }
+        assert_eq!(offset[0].start(), DVec2::new(0.0, 0.0) + DVec2::new(-2.0, 1.0).normalize()*0.25);
+
+        let arc = Segment2D::arc(DVec2::new(0.0, 0.0), DVec2::new(2.0, 2.0), 0.0, 0.0, f64::FRAC_PI_2);
+        let Segment2D::Arc(inner) = &arc.offset(0.5, 0.001)[0] else {
+            panic!("a circular arc should offset to an arc");
+        };
+        assert_eq!(inner.radii, DVec2::new(1.5, 1.5));
+    }
+
+    #[test]
+    fn offset_coincident_control_points() {
+        // the derivative is zero at the start, the direction comes from the next control point instead
+        let cubic = Segment2D::cubic(DVec2::new(0.0, 0.0), DVec2::new(0.0, 0.0), DVec2::new(3.0, 2.0), DVec2::new(4.0, 0.0));
+        let offset = cubic.offset(0.25, 0.001);
+        assert!((offset[0].start() - DVec2::new(-2.0, 3.0).normalize()*0.25).length() < 1e-12);
+        let end = offset.last().unwrap().end();
+        assert!(end.x().is_finite() && end.y().is_finite());
+
+        let quadratic = Segment2D::quadratic(DVec2::new(0.0, 0.0), DVec2::new(0.0, 0.0), DVec2::new(4.0, 0.0));
+        let offset = quadratic.offset(0.25, 0.001);
+        assert!((offset[0].start() - DVec2::new(0.0, 0.25)).length() < 1e-12);
+        assert!((offset.last().unwrap().end() - DVec2::new(4.0, 0.25)).length() < 1e-12);
+
+        // with both controls on the start the direction is towards the end
+        let cubic = Segment2D::cubic(DVec2::new(0.0, 0.0), DVec2::new(0.0, 0.0), DVec2::new(0.0, 0.0), DVec2::new(4.0, 0.0));
+        let offset = cubic.offset(0.25, 0.001);
+        assert!((offset[0].start() - DVec2::new(0.0, 0.25)).length() < 1e-12);
+    }
+
+    #[test]
+    fn offset_polygons() {
+        let square = [DVec2::new(0.0, 0.0), DVec2::new(0.0, 2.0), DVec2::new(2.0, 2.0), DVec2::new(2.0, 0.0)];
+        let grown = offset_polygon(&square, 1.0, LineJoin::Miter, 4.0, 0.01);
+        assert_eq!(grown, [DVec2::new(-1.0, -1.0), DVec2::new(-1.0, 3.0), DVec2::new(3.0, 3.0), DVec2::new(3.0, -1.0)]);
+        let shrunk = offset_polygon(&square, -0.5, LineJoin::Round, 4.0, 0.01);
+        assert_eq!(shrunk, [DVec2::new(0.5, 0.5), DVec2::new(0.5, 1.5), DVec2::new(1.5, 1.5), DVec2::new(1.5, 0.5)]);
+        assert_eq!(offset_polygon(&square, 1.0, LineJoin::Bevel, 4.0, 0.01).len(), 8);
+        let rounded = offset_polygon(&square, 1.0, LineJoin::Round, 4.0, 0.01);
+        for point in rounded {
+            let nearest = DVec2::new(point.x().clamp(0.0, 2.0), point.y().clamp(0.0, 2.0));
+            assert!(((point - nearest).length() - 1.0).abs() < 1e-9);
+        }
+
+        let triangle = [DVec2::new(0.0, 0.0), DVec2::new(1.0, 0.0), DVec2::new(0.0, 1.0)];
+        let sum = minkowski_sum(&square, &triangle);
+        assert_eq!(sum, [
+            DVec2::new(0.0, 0.0), DVec2::new(3.0, 0.0), DVec2::new(3.0, 2.0),
+            DVec2::new(2.0, 3.0), DVec2::new(0.0, 3.0),
+        ]);
+    }
+}
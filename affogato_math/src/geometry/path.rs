@@ -0,0 +1,596 @@
+use core::{fmt::{Debug, Display}, str::FromStr};
+
+use affogato_core::{groups::vector_spaces::VectorSpace, num::{Bounds, Number, Zero}, sets::Real};
+
+use crate::{matrix::Matrix3, vector::{Vector2, Vector3}};
+
+use super::{ArcSegment2D, LinearSegment2D, Rect, Segment, Segment2D};
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
+/// The amount of bisection steps used to find where a monotonic piece
+/// of a segment crosses a horizontal line.
+const BISECTION_ITERATIONS: usize = 48;
+
+/// The rule used to decide whether a point is inside of a [`Path2D`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FillRule {
+    /// A point is inside when the path winds around it a non zero amount of times.
+    #[default]
+    NonZero,
+    /// A point is inside when the path winds around it an odd amount of times.
+    EvenOdd,
+}
+impl FillRule {
+    /// Whether a point with the given winding number is filled under this rule.
+    pub fn is_inside(&self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// An error produced while parsing SVG path data. Each variant carries
+/// the byte offset in the source at which the error was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathParseError {
+    /// A character that isn't a path command or a number.
+    UnexpectedCharacter(usize),
+    /// A number or flag that could not be parsed.
+    InvalidNumber(usize),
+    /// The path data ended in the middle of a command.
+    UnexpectedEnd(usize),
+    /// The path data doesn't begin with a move to command.
+    MissingMoveTo(usize),
+}
+impl Display for PathParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PathParseError::UnexpectedCharacter(at) => write!(f, "unexpected character at byte {at}"),
+            PathParseError::InvalidNumber(at) => write!(f, "invalid number at byte {at}"),
+            PathParseError::UnexpectedEnd(at) => write!(f, "path data ended unexpectedly at byte {at}"),
+            PathParseError::MissingMoveTo(at) => write!(f, "expected a move to command at byte {at}"),
+        }
+    }
+}
+impl core::error::Error for PathParseError {}
+
+/// A connected run of [`Segment2D`] starting at `start`, where each segment
+/// begins where the previous one ended.
+#[derive(Clone, Debug)]
+pub struct SubPath2D<T: Number> {
+    pub start: Vector2<T>,
+    pub segments: Vec<Segment2D<T>>,
+    pub closed: bool,
+}
+impl<T: Number> SubPath2D<T> {
+    pub const fn new(start: Vector2<T>) -> Self {
+        Self { start, segments: Vec::new(), closed: false }
+    }
+    /// The point where the last segment ends.
+    pub fn end(&self) -> Vector2<T> {
+        self.segments.last().map_or(self.start, |segment| segment.end())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+    /// The line that joins the end of the subpath back to its start, this is
+    /// [`None`] when the subpath already ends where it starts.
+    pub fn closing_segment(&self) -> Option<LinearSegment2D<T>> {
+        let end = self.end();
+        if end == self.start {
+            None
+        } else {
+            Some(LinearSegment2D::new(end, self.start))
+        }
+    }
+}
+impl<T: Real> SubPath2D<T> {
+    /// The area enclosed by the subpath treating it as closed. The area is
+    /// positive when the subpath winds counter clockwise.
+    pub fn signed_area(&self) -> T {
+        let closing = self.closing_segment().map(Segment2D::Linear);
+        self.segments.iter()
+            .chain(closing.iter())
+            .fold(T::ZERO, |area, segment| area + segment_area(segment))
+    }
+    /// The amount of times the subpath winds counter clockwise around
+    /// `point`, treating it as closed.
+    pub fn winding_number(&self, point: Vector2<T>) -> i32 {
+        let closing = self.closing_segment().map(Segment2D::Linear);
+        self.segments.iter()
+            .chain(closing.iter())
+            .map(|segment| segment_winding(segment, point))
+            .sum()
+    }
+    /// The smallest axis aligned [`Rect`] containing the subpath.
+    pub fn bounds(&self) -> Rect<T> {
+        self.segments.iter()
+            .fold(Rect::new(self.start, self.start), |bounds, segment| bounds.merge(&segment.bounds()))
+    }
+}
+
+/// A 2D path made out of any amount of [`SubPath2D`], built with the same
+/// commands used by SVG path data and the canvas API.
+/// ```
+/// use affogato_math::{geometry::{FillRule, Path2D}, vector::Vector2};
+///
+/// let path: Path2D<f64> = "M0 0 L4 0 L4 4 L0 4 Z".parse().unwrap();
+/// assert_eq!(path.signed_area(), 16.0);
+/// assert!(path.contains(Vector2::new(2.0, 2.0), FillRule::NonZero));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Path2D<T: Number> {
+    subpaths: Vec<SubPath2D<T>>,
+}
+impl<T: Number> Default for Path2D<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Number> Path2D<T> {
+    pub const fn new() -> Self {
+        Self { subpaths: Vec::new() }
+    }
+    pub fn subpaths(&self) -> &[SubPath2D<T>] {
+        &self.subpaths
+    }
+    /// Iterates over the segments of every subpath in order.
+    pub fn segments(&self) -> impl Iterator<Item = &Segment2D<T>> {
+        self.subpaths.iter().flat_map(|subpath| subpath.segments.iter())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.subpaths.iter().all(SubPath2D::is_empty)
+    }
+    /// The point the next segment will start from, closing a subpath moves
+    /// the current point back to the start of that subpath.
+    pub fn current_point(&self) -> Option<Vector2<T>> {
+        self.subpaths.last().map(|subpath| {
+            if subpath.closed {
+                subpath.start
+            } else {
+                subpath.end()
+            }
+        })
+    }
+    /// Begins a new subpath at `point`.
+    pub fn move_to(&mut self, point: Vector2<T>) -> &mut Self {
+        match self.subpaths.last_mut() {
+            Some(subpath) if subpath.is_empty() && !subpath.closed => subpath.start = point,
+            _ => self.subpaths.push(SubPath2D::new(point)),
+        }
+        self
+    }
+    pub fn line_to(&mut self, end: Vector2<T>) -> &mut Self {
+        self.push(|start| Segment2D::linear(start, end))
+    }
+    pub fn quadratic_to(&mut self, control: Vector2<T>, end: Vector2<T>) -> &mut Self {
+        self.push(|start| Segment2D::quadratic(start, control, end))
+    }
+    pub fn cubic_to(&mut self, control1: Vector2<T>, control2: Vector2<T>, end: Vector2<T>) -> &mut Self {
+        self.push(|start| Segment2D::cubic(start, control1, control2, end))
+    }
+    /// Closes the current subpath, the next segment starts a new subpath
+    /// from where the closed one started.
+    pub fn close(&mut self) -> &mut Self {
+        if let Some(subpath) = self.subpaths.last_mut() {
+            subpath.closed = true;
+        }
+        self
+    }
+    fn push(&mut self, segment: impl FnOnce(Vector2<T>) -> Segment2D<T>) -> &mut Self {
+        let start = self.current_point().unwrap_or(Vector2::ZERO);
+        if self.subpaths.last().is_none_or(|subpath| subpath.closed) {
+            self.subpaths.push(SubPath2D::new(start));
+        }
+        if let Some(subpath) = self.subpaths.last_mut() {
+            subpath.segments.push(segment(start));
+        }
+        self
+    }
+}
+impl<T: Real> Path2D<T> {
+    /// Adds an elliptical arc from the current point to `end` using the SVG
+    /// endpoint parameterization, see [`ArcSegment2D::from_endpoints`].
+    /// `x_rotation` is in radians.
+    pub fn arc_to(&mut self, radii: Vector2<T>, x_rotation: T, large_arc: bool, sweep: bool, end: Vector2<T>) -> &mut Self {
+        let start = self.current_point().unwrap_or(Vector2::ZERO);
+        if start == end {
+            return self;
+        }
+        match ArcSegment2D::from_endpoints(start, radii, x_rotation, large_arc, sweep, end) {
+            Some(arc) => self.push(|_| Segment2D::Arc(arc)),
+            None => self.line_to(end),
+        }
+    }
+    /// The smallest axis aligned [`Rect`] containing every subpath, or
+    /// [`None`] when the path has no subpaths.
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        self.subpaths.iter()
+            .map(SubPath2D::bounds)
+            .reduce(|a, b| a.merge(&b))
+    }
+    /// The sum of the signed areas of every subpath. Counter clockwise
+    /// subpaths add area and clockwise subpaths remove it.
+    pub fn signed_area(&self) -> T {
+        self.subpaths.iter().fold(T::ZERO, |area, subpath| area + subpath.signed_area())
+    }
+    /// The amount of times the path winds counter clockwise around `point`.
+    pub fn winding_number(&self, point: Vector2<T>) -> i32 {
+        self.subpaths.iter().map(|subpath| subpath.winding_number(point)).sum()
+    }
+    pub fn contains(&self, point: Vector2<T>, rule: FillRule) -> bool {
+        rule.is_inside(self.winding_number(point))
+    }
+    /// Applies the affine transformation in `matrix` to every control point
+    /// of the path.
+    pub fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        let subpaths = self.subpaths.iter().map(|subpath| SubPath2D {
+            start: transform_point(subpath.start, matrix),
+            segments: subpath.segments.iter().map(|segment| transform_segment(segment, matrix)).collect(),
+            closed: subpath.closed,
+        }).collect();
+        Self { subpaths }
+    }
+    /// Parses SVG path data, supporting every command from the SVG 1.1
+    /// specification in both absolute and relative forms.
+    pub fn from_svg(data: &str) -> Result<Self, PathParseError> {
+        PathParser { data, position: 0 }.parse()
+    }
+}
+impl<T: Number + Display> Path2D<T> {
+    /// Writes the path as SVG path data using absolute commands.
+    pub fn to_svg(&self) -> String {
+        alloc::format!("{self}")
+    }
+}
+impl<T: Number + Display> Display for Path2D<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut separator = "";
+        for subpath in &self.subpaths {
+            write!(f, "{separator}M{} {}", subpath.start.x(), subpath.start.y())?;
+            separator = " ";
+            for segment in &subpath.segments {
+                match segment {
+                    Segment2D::Linear(linear) => write!(f, " L{} {}", linear.end.x(), linear.end.y())?,
+                    Segment2D::Quadratic(quadratic) => write!(f, " Q{} {} {} {}",
+                        quadratic.control.x(), quadratic.control.y(),
+                        quadratic.end.x(), quadratic.end.y())?,
+                    Segment2D::Cubic(cubic) => write!(f, " C{} {} {} {} {} {}",
+                        cubic.control1.x(), cubic.control1.y(),
+                        cubic.control2.x(), cubic.control2.y(),
+                        cubic.end.x(), cubic.end.y())?,
+                    Segment2D::Arc(arc) => {
+                        let end = arc.end();
+                        let rotation = T::from_f64(arc.rotation.to_f64().to_degrees());
+                        write!(f, " A{} {} {} {} {} {} {}",
+                            arc.radii.x(), arc.radii.y(), rotation,
+                            arc.large_arc() as u8, arc.sweep() as u8,
+                            end.x(), end.y())?
+                    },
+                }
+            }
+            if subpath.closed {
+                f.write_str(" Z")?;
+            }
+        }
+        Ok(())
+    }
+}
+impl<T: Real> FromStr for Path2D<T> {
+    type Err = PathParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_svg(s)
+    }
+}
+
+fn transform_point<T: Real>(point: Vector2<T>, matrix: &Matrix3<T>) -> Vector2<T> {
+    let point = *matrix*Vector3::new(point.x(), point.y(), T::ONE);
+    Vector2::new(point.x(), point.y())
+}
+fn transform_segment<T: Real>(segment: &Segment2D<T>, matrix: &Matrix3<T>) -> Segment2D<T> {
+    let f = |point| transform_point(point, matrix);
+    match segment {
+        Segment2D::Linear(linear) => Segment2D::linear(f(linear.start), f(linear.end)),
+        Segment2D::Quadratic(quadratic) => Segment2D::quadratic(f(quadratic.start), f(quadratic.control), f(quadratic.end)),
+        Segment2D::Cubic(cubic) => Segment2D::cubic(f(cubic.start), f(cubic.control1), f(cubic.control2), f(cubic.end)),
+        Segment2D::Arc(arc) => Segment2D::Arc(arc.transform_by(matrix)),
+    }
+}
+/// The contribution of a segment to the area of a closed path using green's
+/// theorem. For bezier curves the integrand is a polynomial of at most degree
+/// 5, so 3 point gauss-legendre quadrature is exact.
+fn segment_area<T: Real>(segment: &Segment2D<T>) -> T {
+    const NODES: [f64; 3] = [0.11270166537925831, 0.5, 0.8872983346207417];
+    const WEIGHTS: [f64; 3] = [5.0/18.0, 8.0/18.0, 5.0/18.0];
+    match segment {
+        Segment2D::Linear(linear) => return linear.start.cross(&linear.end)/T::from_f64(2.0),
+        Segment2D::Arc(arc) => {
+            // the area swept from the center plus the triangle between the origin, the center and the chord
+            let swept = arc.radii.x()*arc.radii.y()*arc.sweep_angle;
+            return (arc.center.cross(&(arc.end()-arc.start())) + swept)/T::from_f64(2.0);
+        },
+        _ => {},
+    }
+    let integral = NODES.iter().zip(WEIGHTS).fold(T::ZERO, |sum, (&t, weight)| {
+        sum + Segment::get(segment, t).cross(&segment.derivative(t))*T::from_f64(weight)
+    });
+    integral/T::from_f64(2.0)
+}
+/// The values of t where the vertical direction of the segment changes,
+/// including both ends, in increasing order.
+fn monotonic_splits<T: Real>(segment: &Segment2D<T>) -> ([f64; 6], usize) {
+    let mut splits = [0.0; 6];
+    let mut total = 1;
+    let mut push = |t: T| {
+        if t > T::ZERO && t < T::ONE {
+            splits[total] = t.to_f64();
+            total += 1;
+        }
+    };
+    match segment {
+        Segment2D::Linear(_) => {},
+        Segment2D::Quadratic(quadratic) => {
+            let denominator = quadratic.start.y() - quadratic.control.y()*T::from_f64(2.0) + quadratic.end.y();
+            if denominator != T::ZERO {
+                push((quadratic.start.y() - quadratic.control.y())/denominator);
+            }
+        },
+        Segment2D::Cubic(cubic) => {
+            let [p0, p1, p2, p3] = [cubic.start.y(), cubic.control1.y(), cubic.control2.y(), cubic.end.y()];
+            let a = p3 - p0 + (p1 - p2)*T::from_f64(3.0);
+            let b = (p0 - p1*T::from_f64(2.0) + p2)*T::from_f64(2.0);
+            let c = p1 - p0;
+            if let Some(roots) = crate::algebra::solve_quadratic(a, b, c) {
+                roots.for_each(&mut push);
+            }
+        },
+        Segment2D::Arc(arc) => {
+            let (extrema, total) = arc.axis_extrema(1);
+            extrema[..total].iter().copied().for_each(&mut push);
+        },
+    }
+    splits[total] = 1.0;
+    total += 1;
+    splits[..total].sort_by(|a, b| a.total_cmp(b));
+    (splits, total)
+}
+/// The signed amount of times a segment crosses the horizontal ray going
+/// from `point` towards positive x, upward crossings count as +1.
+fn segment_winding<T: Real>(segment: &Segment2D<T>, point: Vector2<T>) -> i32 {
+    let bounds = segment.bounds();
+    if point.y() < bounds.minimum().y() || point.y() > bounds.maximum().y() || point.x() > bounds.maximum().x() {
+        return 0;
+    }
+    let (splits, total) = monotonic_splits(segment);
+    let mut winding = 0;
+    for window in splits[..total].windows(2) {
+        let (t0, t1) = (window[0], window[1]);
+        let y0 = Segment::get(segment, t0).y();
+        let y1 = Segment::get(segment, t1).y();
+        let direction = if y0 <= point.y() && y1 > point.y() {
+            1
+        } else if y0 > point.y() && y1 <= point.y() {
+            -1
+        } else {
+            continue;
+        };
+        let (mut low, mut high) = (t0, t1);
+        for _ in 0..BISECTION_ITERATIONS {
+            let middle = (low + high)*0.5;
+            let below = Segment::get(segment, middle).y() <= point.y();
+            if below == (direction == 1) {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        if Segment::get(segment, (low + high)*0.5).x() > point.x() {
+            winding += direction;
+        }
+    }
+    winding
+}
+
+struct PathParser<'a> {
+    data: &'a str,
+    position: usize,
+}
+impl PathParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.data.as_bytes().get(self.position).copied()
+    }
+    fn skip_separators(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace() || c == b',') {
+            self.position += 1;
+        }
+    }
+    fn at_number(&mut self) -> bool {
+        self.skip_separators();
+        self.peek().is_some_and(|c| c.is_ascii_digit() || matches!(c, b'.' | b'-' | b'+'))
+    }
+    fn skip_digits(&mut self) -> usize {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.position - start
+    }
+    fn number<T: Real>(&mut self) -> Result<T, PathParseError> {
+        self.skip_separators();
+        let start = self.position;
+        if self.peek().is_none() {
+            return Err(PathParseError::UnexpectedEnd(start));
+        }
+        if matches!(self.peek(), Some(b'-' | b'+')) {
+            self.position += 1;
+        }
+        let mut digits = self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            digits += self.skip_digits();
+        }
+        if digits == 0 {
+            return Err(PathParseError::InvalidNumber(start));
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let exponent = self.position;
+            self.position += 1;
+            if matches!(self.peek(), Some(b'-' | b'+')) {
+                self.position += 1;
+            }
+            if self.skip_digits() == 0 {
+                // not an exponent, the 'e' belongs to whatever comes next
+                self.position = exponent;
+            }
+        }
+        self.data[start..self.position].parse::<f64>()
+            .map(T::from_f64)
+            .map_err(|_| PathParseError::InvalidNumber(start))
+    }
+    fn flag(&mut self) -> Result<bool, PathParseError> {
+        self.skip_separators();
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            Some(_) => return Err(PathParseError::InvalidNumber(self.position)),
+            None => return Err(PathParseError::UnexpectedEnd(self.position)),
+        };
+        self.position += 1;
+        Ok(flag)
+    }
+    fn point<T: Real>(&mut self, relative_to: Option<Vector2<T>>) -> Result<Vector2<T>, PathParseError> {
+        let point = Vector2::new(self.number()?, self.number()?);
+        Ok(relative_to.map_or(point, |origin| origin + point))
+    }
+    fn parse<T: Real>(mut self) -> Result<Path2D<T>, PathParseError> {
+        let mut path = Path2D::new();
+        let mut command = None;
+        // the last control point of the previous segment, used by the smooth commands
+        let mut last_cubic_control = None;
+        let mut last_quadratic_control = None;
+        loop {
+            self.skip_separators();
+            let Some(c) = self.peek() else {
+                break;
+            };
+            if c.is_ascii_alphabetic() {
+                command = Some(c);
+                self.position += 1;
+            } else if !self.at_number() || matches!(command, None | Some(b'Z' | b'z')) {
+                return Err(PathParseError::UnexpectedCharacter(self.position));
+            }
+            let Some(current) = command else {
+                return Err(PathParseError::UnexpectedCharacter(self.position));
+            };
+            if path.subpaths.is_empty() && !matches!(current, b'M' | b'm') {
+                return Err(PathParseError::MissingMoveTo(self.position-1));
+            }
+            let origin = path.current_point().unwrap_or(Vector2::ZERO);
+            let relative = current.is_ascii_lowercase().then_some(origin);
+            let (mut cubic_control, mut quadratic_control) = (None, None);
+            match current.to_ascii_uppercase() {
+                b'M' => {
+                    path.move_to(self.point(relative)?);
+                    // any coordinates following a move to are line to commands
+                    command = Some(if relative.is_some() { b'l' } else { b'L' });
+                },
+                b'L' => {
+                    path.line_to(self.point(relative)?);
+                },
+                b'H' => {
+                    let x = self.number::<T>()? + relative.map_or(T::ZERO, |origin| origin.x());
+                    path.line_to(Vector2::new(x, origin.y()));
+                },
+                b'V' => {
+                    let y = self.number::<T>()? + relative.map_or(T::ZERO, |origin| origin.y());
+                    path.line_to(Vector2::new(origin.x(), y));
+                },
+                b'C' => {
+                    let control1 = self.point(relative)?;
+                    let control2 = self.point(relative)?;
+                    path.cubic_to(control1, control2, self.point(relative)?);
+                    cubic_control = Some(control2);
+                },
+                b'S' => {
+                    let control1 = last_cubic_control.map_or(origin, |control| origin*T::from_f64(2.0) - control);
+                    let control2 = self.point(relative)?;
+                    path.cubic_to(control1, control2, self.point(relative)?);
+                    cubic_control = Some(control2);
+                },
+                b'Q' => {
+                    let control = self.point(relative)?;
+                    path.quadratic_to(control, self.point(relative)?);
+                    quadratic_control = Some(control);
+                },
+                b'T' => {
+                    let control = last_quadratic_control.map_or(origin, |control| origin*T::from_f64(2.0) - control);
+                    path.quadratic_to(control, self.point(relative)?);
+                    quadratic_control = Some(control);
+                },
+                b'A' => {
+                    let radii = Vector2::new(self.number()?, self.number()?);
+                    let x_rotation = self.number::<T>()?.to_radians();
+                    let large_arc = self.flag()?;
+                    let sweep = self.flag()?;
+                    path.arc_to(radii, x_rotation, large_arc, sweep, self.point(relative)?);
+                },
+                b'Z' => {
+                    path.close();
+                },
+                _ => return Err(PathParseError::UnexpectedCharacter(self.position-1)),
+            }
+            last_cubic_control = cubic_control;
+            last_quadratic_control = quadratic_control;
+        }
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::alloc::string::ToString;
+
+    use affogato_core::num::FloatConsts;
+
+    use crate::{geometry::{FillRule, Path2D, PathParseError}, matrix::Matrix3, vector::Vector2};
+
+    #[test]
+    fn svg_round_trip() {
+        let path: Path2D<f64> = "M10,20l5-5 h 5v10 Q0 0 1 1t 2 2 C1 2 3 4 5 6s1 1 2 2z m1 1 1.5.5 2e1 0".parse().unwrap();
+        assert_eq!(path.subpaths().len(), 2);
+        assert_eq!(
+            path.to_svg(),
+            "M10 20 L15 15 L20 15 L20 25 Q0 0 1 1 Q2 2 3 3 C1 2 3 4 5 6 C7 8 6 7 7 8 Z M11 21 L12.5 21.5 L32.5 21.5"
+        );
+        let reparsed: Path2D<f64> = path.to_svg().parse().unwrap();
+        assert_eq!(reparsed.to_string(), path.to_string());
+
+        assert_eq!("L1 1".parse::<Path2D<f64>>().unwrap_err(), PathParseError::MissingMoveTo(0));
+        assert_eq!("M1 1 L2".parse::<Path2D<f64>>().unwrap_err(), PathParseError::UnexpectedEnd(7));
+        assert_eq!("M1 1 X2".parse::<Path2D<f64>>().unwrap_err(), PathParseError::UnexpectedCharacter(5));
+    }
+    #[test]
+    fn area_and_containment() {
+        // a circle of radius 2 made from two arcs
+        let circle: Path2D<f64> = "M2 0 A2 2 0 0 1 -2 0 A2 2 0 0 1 2 0 Z".parse().unwrap();
+        assert!((circle.signed_area() - 4.0*f64::PI).abs() < 1e-9);
+        let bounds = circle.bounds().unwrap();
+        assert!((bounds.minimum().x() + 2.0).abs() < 1e-9 && (bounds.maximum().y() - 2.0).abs() < 1e-9);
+        assert!(circle.contains(Vector2::new(1.0, 1.0), FillRule::NonZero));
+        assert!(!circle.contains(Vector2::new(1.5, 1.5), FillRule::NonZero));
+
+        // two nested squares winding the same way
+        let squares: Path2D<f64> = "M0 0 H4 V4 H0 Z M1 1 H3 V3 H1 Z".parse().unwrap();
+        assert_eq!(squares.winding_number(Vector2::new(2.0, 2.0)), 2);
+        assert!(squares.contains(Vector2::new(2.0, 2.0), FillRule::NonZero));
+        assert!(!squares.contains(Vector2::new(2.0, 2.0), FillRule::EvenOdd));
+        assert!(squares.contains(Vector2::new(0.5, 2.0), FillRule::EvenOdd));
+        assert!(!squares.contains(Vector2::new(5.0, 2.0), FillRule::NonZero));
+
+        let moved = squares.transform_by(&Matrix3::from_transform(Vector2::new(10.0, 0.0), Vector2::new(1.0, 1.0), 0.0));
+        assert_eq!(moved.signed_area(), 20.0);
+        assert!(moved.contains(Vector2::new(10.5, 2.0), FillRule::EvenOdd));
+    }
+}
@@ -0,0 +1,420 @@
+use core::ops::{Index, IndexMut};
+
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{Number, Zero}, sets::Real};
+
+use crate::{sdf::SignedDistance, vector::{Vector2, Vector3}};
+
+use super::{orient2d, CalculateCentroid, Dimension, LinearSegment2D, Rect, SegmentIntersect, SegmentIntersection};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// The direction a polygon's vertices go around it, with the y axis pointing
+/// up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// A closed polygon in the plane, the last vertex connects back to the
+/// first. The polygon can be concave, and can wind either way.
+#[derive(Clone, Debug, Default)]
+pub struct Polygon2D<T: Number> {
+    pub vertices: Vec<Vector2<T>>,
+}
+impl<T: Number> Polygon2D<T> {
+    pub fn new(vertices: Vec<Vector2<T>>) -> Self {
+        Self { vertices }
+    }
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+    /// Every edge of the polygon, including the one closing it.
+    pub fn edges(&self) -> impl Iterator<Item = LinearSegment2D<T>> + '_ {
+        self.vertices.iter().zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&start, &end)| LinearSegment2D::new(start, end))
+    }
+    /// The area of the polygon, positive when it winds counterclockwise.
+    pub fn signed_area(&self) -> T {
+        self.edges().fold(T::ZERO, |area, edge| area + edge.start.cross(&edge.end))/T::from_u32(2)
+    }
+    /// Which way the vertices go around the polygon, [`None`] when it has no
+    /// area.
+    pub fn winding(&self) -> Option<Winding> {
+        let area = self.signed_area();
+        if area > T::ZERO {
+            Some(Winding::CounterClockwise)
+        } else if area < T::ZERO {
+            Some(Winding::Clockwise)
+        } else {
+            None
+        }
+    }
+    /// Flips the direction the vertices go around the polygon.
+    pub fn reverse(&mut self) {
+        self.vertices.reverse();
+    }
+    /// How many times the polygon winds counterclockwise around `point`,
+    /// negative for clockwise.
+    pub fn winding_number(&self, point: Vector2<T>) -> i32 {
+        let mut winding = 0;
+        for edge in self.edges() {
+            let side = (edge.end-edge.start).cross(&(point-edge.start));
+            if edge.start.y() <= point.y() {
+                if edge.end.y() > point.y() && side > T::ZERO {
+                    winding += 1;
+                }
+            } else if edge.end.y() <= point.y() && side < T::ZERO {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+    /// Whether `point` is inside the polygon, using the non zero rule so
+    /// self overlapping parts count as inside.
+    pub fn contains(&self, point: Vector2<T>) -> bool {
+        self.winding_number(point) != 0
+    }
+    /// [`Polygon2D::winding_number`] deciding which side of each edge the
+    /// point is on with the exact [`orient2d`], so points right next to an
+    /// edge are never counted on the wrong side of it.
+    pub fn winding_number_exact(&self, point: Vector2<T>) -> i32 
+        where T: Real {
+        let mut winding = 0;
+        for edge in self.edges() {
+            let side = orient2d(edge.start, edge.end, point);
+            if edge.start.y() <= point.y() {
+                if edge.end.y() > point.y() && side > T::ZERO {
+                    winding += 1;
+                }
+            } else if edge.end.y() <= point.y() && side < T::ZERO {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+    /// [`Polygon2D::contains`] with [`Polygon2D::winding_number_exact`].
+    pub fn contains_exact(&self, point: Vector2<T>) -> bool 
+        where T: Real {
+        self.winding_number_exact(point) != 0
+    }
+    /// The smallest axis aligned [`Rect`] containing the polygon.
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        let (&first, rest) = self.vertices.split_first()?;
+        let (min, max) = rest.iter().fold((first, first), |(min, max), &vertex| {
+            (
+                Vector2::new(if vertex.x() < min.x() { vertex.x() } else { min.x() }, if vertex.y() < min.y() { vertex.y() } else { min.y() }),
+                Vector2::new(if vertex.x() > max.x() { vertex.x() } else { max.x() }, if vertex.y() > max.y() { vertex.y() } else { max.y() }),
+            )
+        });
+        Some(Rect::new(min, max))
+    }
+}
+impl<T: Real> Polygon2D<T> {
+    pub fn area(&self) -> T {
+        self.signed_area().abs()
+    }
+    /// Whether every corner of the polygon turns the same way, and it goes
+    /// around only once.
+    pub fn is_convex(&self) -> bool {
+        let count = self.vertices.len();
+        if count < 3 {
+            return false;
+        }
+        let mut direction = T::ZERO;
+        for i in 0..count {
+            let a = self.vertices[i];
+            let b = self.vertices[(i+1)%count];
+            let c = self.vertices[(i+2)%count];
+            let turn = (b-a).cross(&(c-b));
+            if turn == T::ZERO {
+                continue;
+            }
+            if direction == T::ZERO {
+                direction = turn;
+            } else if (turn > T::ZERO) != (direction > T::ZERO) {
+                return false;
+            }
+        }
+        // a star shape turns the same way at each corner, but goes around twice
+        direction != T::ZERO && self.is_simple()
+    }
+    pub fn perimeter(&self) -> T {
+        self.edges().fold(T::ZERO, |perimeter, edge| perimeter + edge.length())
+    }
+    /// Every point where two edges of the polygon cross, along with the
+    /// indices of both edges, where edge `i` starts at vertex `i`. Neighbouring
+    /// edges only count when they overlap past the vertex they share.
+    pub fn self_intersections(&self) -> Vec<(usize, usize, SegmentIntersection<T>)> {
+        let mut intersections = Vec::new();
+        self.find_self_intersections(|i, j, intersection| {
+            intersections.push((i, j, intersection));
+            true
+        });
+        intersections
+    }
+    /// Whether no two edges of the polygon cross.
+    pub fn is_simple(&self) -> bool {
+        let mut simple = true;
+        self.find_self_intersections(|_, _, _| {
+            simple = false;
+            false
+        });
+        simple
+    }
+    /// Calls `found` with each self intersection until it returns false.
+    fn find_self_intersections(&self, mut found: impl FnMut(usize, usize, SegmentIntersection<T>) -> bool) {
+        let edges: Vec<LinearSegment2D<T>> = self.edges().collect();
+        let count = edges.len();
+        let epsilon = T::EPSILON.sqrt();
+        for i in 0..count {
+            for j in i+1..count {
+                let next = j == i+1;
+                let previous = i == 0 && j == count-1;
+                for intersection in edges[i].intersections(&edges[j]).iter() {
+                    // the vertex shared by neighbouring edges isn't a crossing
+                    let shared = (next && intersection.t0 >= T::ONE-epsilon && intersection.t1 <= epsilon)
+                        || (previous && intersection.t0 <= epsilon && intersection.t1 >= T::ONE-epsilon);
+                    if !shared && !found(i, j, *intersection) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+impl<T: Real> CalculateCentroid for Polygon2D<T> {
+    type Vector = Vector2<T>;
+    /// The center of mass of the area inside the polygon, or the average of
+    /// its vertices when it has no area.
+    fn centroid(&self) -> Self::Vector {
+        let mut area = T::ZERO;
+        let mut centroid = Vector2::ZERO;
+        for edge in self.edges() {
+            let cross = edge.start.cross(&edge.end);
+            area += cross;
+            centroid += (edge.start+edge.end)*cross;
+        }
+        if area == T::ZERO {
+            if self.vertices.is_empty() {
+                return Vector2::ZERO;
+            }
+            return self.vertices.iter().fold(Vector2::ZERO, |sum, &vertex| sum+vertex)/T::from_usize(self.vertices.len());
+        }
+        centroid/(area*T::from_f64(3.0))
+    }
+}
+impl<T: Real> SignedDistance<Vector2<T>> for Polygon2D<T> {
+    type Distance = T;
+    /// The distance to the nearest edge, negative inside the polygon.
+    fn sdf(&self, object: &Vector2<T>) -> Self::Distance {
+        let distance = self.edges().map(|edge| {
+            let line = edge.end-edge.start;
+            let length = line.length_squared();
+            let t = if length == T::ZERO {
+                T::ZERO
+            } else {
+                affogato_core::clamp((*object-edge.start).dot(&line)/length, T::ZERO, T::ONE)
+            };
+            (*object-(edge.start+line*t)).length_squared()
+        }).fold(T::INFINITY, |min, distance| min.min(distance)).sqrt();
+        if self.contains(*object) { -distance } else { distance }
+    }
+}
+impl<T: Number> Index<usize> for Polygon2D<T> {
+    type Output = Vector2<T>;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.vertices[index]
+    }
+}
+impl<T: Number> IndexMut<usize> for Polygon2D<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.vertices[index]
+    }
+}
+impl<T: Number> From<Vec<Vector2<T>>> for Polygon2D<T> {
+    fn from(vertices: Vec<Vector2<T>>) -> Self {
+        Self::new(vertices)
+    }
+}
+impl<T: Number> Dimension for Polygon2D<T> {
+    const DIMENSION: usize = 2;
+}
+
+/// A closed polygon whose vertices all lie on one plane in space.
+#[derive(Clone, Debug, Default)]
+pub struct Polygon3D<T: Number> {
+    pub vertices: Vec<Vector3<T>>,
+}
+impl<T: Number> Polygon3D<T> {
+    pub fn new(vertices: Vec<Vector3<T>>) -> Self {
+        Self { vertices }
+    }
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+    /// Twice the area of the polygon in the direction of its normal, found
+    /// with Newell's method so it stays stable for nearly collinear vertices.
+    pub fn area_vector(&self) -> Vector3<T> {
+        self.vertices.iter().zip(self.vertices.iter().cycle().skip(1))
+            .fold(Vector3::ZERO, |sum, (a, b)| sum + a.cross(b))
+    }
+}
+impl<T: Real> Polygon3D<T> {
+    /// The normal of the plane the polygon is on, facing the side from which
+    /// the polygon winds counterclockwise.
+    pub fn normal(&self) -> Vector3<T> {
+        let area = self.area_vector();
+        if area.is_zero() {
+            return area;
+        }
+        area.normalize()
+    }
+    pub fn area(&self) -> T {
+        self.area_vector().length()/T::from_f64(2.0)
+    }
+    pub fn perimeter(&self) -> T {
+        self.vertices.iter().zip(self.vertices.iter().cycle().skip(1))
+            .fold(T::ZERO, |perimeter, (&a, &b)| perimeter + (b-a).length())
+    }
+    /// Which way the polygon winds when looking against `view`, so a polygon
+    /// facing the viewer winds counterclockwise.
+    pub fn winding(&self, view: Vector3<T>) -> Option<Winding> {
+        let facing = self.area_vector().dot(&view);
+        if facing > T::ZERO {
+            Some(Winding::CounterClockwise)
+        } else if facing < T::ZERO {
+            Some(Winding::Clockwise)
+        } else {
+            None
+        }
+    }
+    /// Whether every vertex is within `tolerance` of the plane of the polygon.
+    pub fn is_planar(&self, tolerance: T) -> bool {
+        let normal = self.normal();
+        let Some(&origin) = self.vertices.first() else {
+            return true;
+        };
+        self.vertices.iter().all(|&vertex| (vertex-origin).dot(&normal).abs() <= tolerance)
+    }
+    /// The origin and two perpendicular axes of the plane of the polygon, so
+    /// that the polygon winds counterclockwise in them.
+    fn plane_basis(&self) -> (Vector3<T>, Vector3<T>, Vector3<T>) {
+        let normal = self.normal();
+        let origin = self.vertices.first().copied().unwrap_or(Vector3::ZERO);
+        let helper = if normal.x().abs() < T::from_f64(0.9) { Vector3::right() } else { Vector3::top() };
+        let u = helper.cross(&normal);
+        if u.is_zero() {
+            return (origin, Vector3::right(), Vector3::top());
+        }
+        let u = u.normalize();
+        (origin, normal.cross(&u), u)
+    }
+    /// The polygon in the coordinates of its own plane, where it winds
+    /// counterclockwise.
+    pub fn to_plane(&self) -> Polygon2D<T> {
+        let (origin, u, v) = self.plane_basis();
+        Polygon2D::new(self.vertices.iter().map(|&vertex| {
+            let offset = vertex-origin;
+            Vector2::new(offset.dot(&u), offset.dot(&v))
+        }).collect())
+    }
+    pub fn is_convex(&self) -> bool {
+        self.to_plane().is_convex()
+    }
+    /// Whether `point` is within `tolerance` of the plane and inside the
+    /// polygon on it.
+    pub fn contains(&self, point: Vector3<T>, tolerance: T) -> bool {
+        let (origin, u, v) = self.plane_basis();
+        let offset = point-origin;
+        if offset.dot(&self.normal()).abs() > tolerance {
+            return false;
+        }
+        self.to_plane().contains(Vector2::new(offset.dot(&u), offset.dot(&v)))
+    }
+    /// Every point where two edges of the polygon cross, see
+    /// [`Polygon2D::self_intersections`]. The points are on the plane of the
+    /// polygon.
+    pub fn self_intersections(&self) -> Vec<(usize, usize, Vector3<T>)> {
+        let (origin, u, v) = self.plane_basis();
+        self.to_plane().self_intersections().into_iter()
+            .map(|(i, j, intersection)| (i, j, origin + u*intersection.point.x() + v*intersection.point.y()))
+            .collect()
+    }
+    pub fn is_simple(&self) -> bool {
+        self.to_plane().is_simple()
+    }
+}
+impl<T: Real> CalculateCentroid for Polygon3D<T> {
+    type Vector = Vector3<T>;
+    /// The center of mass of the area inside the polygon, or the average of
+    /// its vertices when it has no area.
+    fn centroid(&self) -> Self::Vector {
+        let (origin, u, v) = self.plane_basis();
+        let centroid = self.to_plane().centroid();
+        origin + u*centroid.x() + v*centroid.y()
+    }
+}
+impl<T: Number> Index<usize> for Polygon3D<T> {
+    type Output = Vector3<T>;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.vertices[index]
+    }
+}
+impl<T: Number> IndexMut<usize> for Polygon3D<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.vertices[index]
+    }
+}
+impl<T: Number> From<Vec<Vector3<T>>> for Polygon3D<T> {
+    fn from(vertices: Vec<Vector3<T>>) -> Self {
+        Self::new(vertices)
+    }
+}
+impl<T: Number> Dimension for Polygon3D<T> {
+    const DIMENSION: usize = 3;
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::groups::vector_spaces::NormedVectorSpace;
+
+    use crate::{geometry::{CalculateCentroid, Polygon2D, Polygon3D, Winding}, sdf::SignedDistance, vector::{DVec2, DVec3}};
+
+    #[test]
+    fn polygon_properties() {
+        // an L shape, wound counterclockwise
+        let l = Polygon2D::new(super::alloc::vec![
+            DVec2::new(0.0, 0.0), DVec2::new(2.0, 0.0), DVec2::new(2.0, 1.0),
+            DVec2::new(1.0, 1.0), DVec2::new(1.0, 2.0), DVec2::new(0.0, 2.0),
+        ]);
+        assert_eq!(l.signed_area(), 3.0);
+        assert_eq!(l.winding(), Some(Winding::CounterClockwise));
+        assert_eq!(l.perimeter(), 8.0);
+        assert!(!l.is_convex() && l.is_simple());
+        assert!(l.contains(DVec2::new(0.5, 1.5)) && !l.contains(DVec2::new(1.5, 1.5)));
+        assert!((l.centroid() - DVec2::new(5.0/6.0, 5.0/6.0)).length() < 1e-12);
+        assert_eq!(l.sdf(&DVec2::new(0.5, 0.25)), -0.25);
+        assert_eq!(l.sdf(&DVec2::new(2.0, 2.0)), 1.0);
+
+        let mut square = Polygon2D::new(super::alloc::vec![DVec2::new(0.0, 0.0), DVec2::new(0.0, 1.0), DVec2::new(1.0, 1.0), DVec2::new(1.0, 0.0)]);
+        assert_eq!(square.winding(), Some(Winding::Clockwise));
+        assert!(square.is_convex());
+        square.vertices.swap(1, 2);
+        let crossings = square.self_intersections();
+        assert_eq!(crossings.len(), 1);
+        assert_eq!(crossings[0].2.point, DVec2::new(0.5, 0.5));
+
+        let tilted = Polygon3D::new(super::alloc::vec![DVec3::new(0.0, 0.0, 0.0), DVec3::new(2.0, 0.0, 2.0), DVec3::new(2.0, 2.0, 2.0), DVec3::new(0.0, 2.0, 0.0)]);
+        assert!((tilted.area() - 8.0f64.sqrt()*2.0).abs() < 1e-12);
+        assert!(tilted.is_planar(1e-12) && tilted.is_convex());
+        assert!(tilted.contains(DVec3::new(1.0, 1.0, 1.0), 1e-9) && !tilted.contains(DVec3::new(1.0, 1.0, 0.0), 1e-9));
+        assert!((tilted.centroid() - DVec3::new(1.0, 1.0, 1.0)).length() < 1e-12);
+    }
+}
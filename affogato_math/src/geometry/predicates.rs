@@ -0,0 +1,414 @@
+use core::cmp::Ordering;
+
+use affogato_core::sets::Real;
+
+use crate::vector::{Vector2, Vector3};
+#[cfg(feature="alloc")]
+extern crate alloc;
+#[cfg(feature="alloc")]
+use alloc::vec::Vec;
+
+// Adaptive precision predicates after Jonathan Shewchuk's "Adaptive Precision
+// Floating-Point Arithmetic and Fast Robust Geometric Predicates". Each one
+// first evaluates its determinant with plain floating point and returns that
+// when it is further from zero than the rounding error could be, which is
+// almost always. Otherwise the determinant is worked out exactly as an
+// expansion, a sum of floats that don't overlap, whose largest term has the
+// sign of the whole sum.
+
+/// The relative error of rounding, half of the machine epsilon.
+fn rounding_error<T: Real>() -> T {
+    T::EPSILON/(T::ONE + T::ONE)
+}
+
+/// Bounds the rounding error of a determinant relative to its permanent,
+/// `(a + b*epsilon)*epsilon`.
+fn error_bound<T: Real>(a: f64, b: f64) -> T {
+    let epsilon = rounding_error::<T>();
+    (T::from_f64(a) + T::from_f64(b)*epsilon)*epsilon
+}
+
+/// `2^ceil(p/2) + 1` for a float with `p` bits of precision, used to split a
+/// float into two halves that can be multiplied without rounding.
+fn splitter<T: Real>() -> T {
+    let two = T::ONE + T::ONE;
+    let (mut splitter, mut epsilon, mut every_other) = (T::ONE, T::ONE, true);
+    loop {
+        epsilon /= two;
+        if every_other {
+            splitter *= two;
+        }
+        every_other = !every_other;
+        if T::ONE + epsilon == T::ONE {
+            return splitter + T::ONE;
+        }
+    }
+}
+
+/// `a + b` and its rounding error.
+fn two_sum<T: Real>(a: T, b: T) -> (T, T) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Splits `a` into a high and a low half that each fit in half the bits.
+fn split<T: Real>(a: T, splitter: T) -> (T, T) {
+    let c = splitter*a;
+    let high = c - (c - a);
+    (high, a - high)
+}
+
+/// `a*b` and its rounding error, with Dekker's product.
+fn two_product<T: Real>(a: T, b: T, splitter: T) -> (T, T) {
+    let product = a*b;
+    let (a_high, a_low) = split(a, splitter);
+    let (b_high, b_low) = split(b, splitter);
+    let error = ((product - a_high*b_high) - a_low*b_high) - a_high*b_low;
+    (product, a_low*b_low - error)
+}
+
+/// A sum of up to `N` floats going from the smallest to the largest, where
+/// none of them overlap and zeros are left out. With `alloc` the terms are on
+/// the heap and only as many as are used, the exact [`insphere`] can need
+/// thousands, which would be around 100KB of stack for `f64`.
+#[derive(Clone)]
+struct Expansion<T, const N: usize> {
+    #[cfg(feature="alloc")]
+    terms: Vec<T>,
+    #[cfg(not(feature="alloc"))]
+    terms: [T; N],
+    len: usize,
+}
+
+impl<T: Real, const N: usize> Expansion<T, N> {
+    #[cfg(feature="alloc")]
+    fn new() -> Self {
+        Self { terms: alloc::vec![T::ZERO], len: 0 }
+    }
+    #[cfg(not(feature="alloc"))]
+    fn new() -> Self {
+        Self { terms: [T::ZERO; N], len: 0 }
+    }
+    /// Appends `term` even when it is zero.
+    fn append(&mut self, term: T) {
+        #[cfg(feature="alloc")]
+        if self.len == self.terms.len() {
+            debug_assert!(self.len < N);
+            self.terms.push(term);
+            self.len += 1;
+            return;
+        }
+        self.terms[self.len] = term;
+        self.len += 1;
+    }
+    fn push(&mut self, term: T) {
+        if term != T::ZERO {
+            self.append(term);
+        }
+    }
+    /// The terms, with a single zero for an empty sum.
+    fn terms(&self) -> &[T] {
+        &self.terms[..self.len.max(1)]
+    }
+    /// The largest term, which has the sign of the whole sum.
+    fn estimate(&self) -> T {
+        if self.len == 0 { T::ZERO } else { self.terms[self.len-1] }
+    }
+    fn neg(mut self) -> Self {
+        for term in self.terms[..self.len].iter_mut() {
+            *term = -*term;
+        }
+        self
+    }
+    /// `px*qy - qx*py` exactly.
+    fn minor(p: Vector2<T>, q: Vector2<T>, splitter: T) -> Self {
+        let (a, a_error) = two_product(p.x(), q.y(), splitter);
+        let (b, b_error) = two_product(q.x(), p.y(), splitter);
+        Self::sum(&[a_error, a], &[-b_error, -b])
+    }
+    /// Shewchuk's fast expansion sum, merging the terms by size and carrying
+    /// the running sum through them.
+    fn sum(e: &[T], f: &[T]) -> Self {
+        let mut result = Self::new();
+        let (mut i, mut j, mut running) = (0, 0, T::ZERO);
+        while i < e.len() || j < f.len() {
+            let next = if j == f.len() || (i < e.len() && e[i].abs() <= f[j].abs()) {
+                i += 1;
+                e[i-1]
+            } else {
+                j += 1;
+                f[j-1]
+            };
+            let (sum, error) = two_sum(running, next);
+            running = sum;
+            result.push(error);
+        }
+        if running != T::ZERO || result.len == 0 {
+            result.append(running);
+        }
+        result
+    }
+    /// The terms of `e` multiplied by `b`.
+    fn scale(e: &[T], b: T, splitter: T) -> Self {
+        let mut result = Self::new();
+        let (mut running, error) = two_product(e[0], b, splitter);
+        result.push(error);
+        for &term in &e[1..] {
+            let (product, product_error) = two_product(term, b, splitter);
+            let (sum, error) = two_sum(running, product_error);
+            result.push(error);
+            let (sum, error) = two_sum(product, sum);
+            result.push(error);
+            running = sum;
+        }
+        if running != T::ZERO || result.len == 0 {
+            result.append(running);
+        }
+        result
+    }
+}
+
+/// `pq + qr + rp` from the minors of three points, the orientation of the
+/// triangle they make.
+fn triangle_minors<T: Real>(pq: &Expansion<T, 4>, qr: &Expansion<T, 4>, rp: &Expansion<T, 4>) -> Expansion<T, 12> {
+    let sum: Expansion<T, 8> = Expansion::sum(pq.terms(), qr.terms());
+    Expansion::sum(sum.terms(), rp.terms())
+}
+
+/// `e*a*a` exactly.
+fn scale_twice<T: Real, const N: usize, const M: usize>(e: &[T], a: T, splitter: T) -> Expansion<T, M> {
+    let once: Expansion<T, N> = Expansion::scale(e, a, splitter);
+    Expansion::scale(once.terms(), a, splitter)
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `c`, positive when it
+/// winds counterclockwise, negative when it winds clockwise and zero exactly
+/// when the points are collinear. The sign is always right, the magnitude is
+/// an approximation.
+pub fn orient2d<T: Real>(a: Vector2<T>, b: Vector2<T>, c: Vector2<T>) -> T {
+    let left = (a.x() - c.x())*(b.y() - c.y());
+    let right = (a.y() - c.y())*(b.x() - c.x());
+    let determinant = left - right;
+    let permanent = left.abs() + right.abs();
+    if determinant.abs() > error_bound::<T>(3.0, 16.0)*permanent {
+        return determinant;
+    }
+    let splitter = splitter::<T>();
+    let ab = Expansion::minor(a, b, splitter);
+    let bc = Expansion::minor(b, c, splitter);
+    let ca = Expansion::minor(c, a, splitter);
+    triangle_minors(&ab, &bc, &ca).estimate()
+}
+
+/// Six times the signed volume of the tetrahedron `a`, `b`, `c`, `d`,
+/// positive when `d` is on the side of the triangle `a`, `b`, `c` that it
+/// winds counterclockwise on and zero exactly when the points are coplanar.
+/// This is the sign of `(b - a).cross(c - a).dot(d - a)`, the opposite of
+/// Shewchuk's convention. The sign is always right, the magnitude is an
+/// approximation.
+pub fn orient3d<T: Real>(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>, d: Vector3<T>) -> T {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let (bdx_cdy, cdx_bdy) = (bd.x()*cd.y(), cd.x()*bd.y());
+    let (cdx_ady, adx_cdy) = (cd.x()*ad.y(), ad.x()*cd.y());
+    let (adx_bdy, bdx_ady) = (ad.x()*bd.y(), bd.x()*ad.y());
+    let determinant = ad.z()*(bdx_cdy - cdx_bdy) + bd.z()*(cdx_ady - adx_cdy) + cd.z()*(adx_bdy - bdx_ady);
+    let permanent = (bdx_cdy.abs() + cdx_bdy.abs())*ad.z().abs()
+        + (cdx_ady.abs() + adx_cdy.abs())*bd.z().abs()
+        + (adx_bdy.abs() + bdx_ady.abs())*cd.z().abs();
+    if determinant.abs() > error_bound::<T>(7.0, 56.0)*permanent {
+        return -determinant;
+    }
+    orient3d_exact([a, b, c, d], splitter::<T>()).estimate()
+}
+
+/// The exact [`orient3d`], expanding the 4 by 4 determinant of the points
+/// along their z coordinates.
+fn orient3d_exact<T: Real>(points: [Vector3<T>; 4], splitter: T) -> Expansion<T, 96> {
+    let flat = points.map(|point| Vector2::new(point.x(), point.y()));
+    let minor = |i: usize, j: usize| Expansion::<T, 4>::minor(flat[i], flat[j], splitter);
+    let (ab, bc, cd, da, ac, bd) = (minor(0, 1), minor(1, 2), minor(2, 3), minor(3, 0), minor(0, 2), minor(1, 3));
+    let (ca, db) = (ac.clone().neg(), bd.clone().neg());
+    let bcd = triangle_minors(&bc, &cd, &db);
+    let acd = triangle_minors(&ac, &cd, &da);
+    let abd = triangle_minors(&ab, &bd, &da);
+    let abc = triangle_minors(&ab, &bc, &ca);
+    let [a, b, c, d] = points.map(|point| point.z());
+    let terms: [Expansion<T, 24>; 4] = [
+        Expansion::scale(bcd.terms(), -a, splitter),
+        Expansion::scale(acd.terms(), b, splitter),
+        Expansion::scale(abd.terms(), -c, splitter),
+        Expansion::scale(abc.terms(), d, splitter),
+    ];
+    let first: Expansion<T, 48> = Expansion::sum(terms[0].terms(), terms[1].terms());
+    let second: Expansion<T, 48> = Expansion::sum(terms[2].terms(), terms[3].terms());
+    Expansion::sum(first.terms(), second.terms())
+}
+
+/// Positive when `d` is inside the circle through `a`, `b` and `c`, negative
+/// when it is outside and zero exactly when the four points are cocircular.
+/// The triangle `a`, `b`, `c` has to wind counterclockwise, otherwise the
+/// sign flips. The sign is always right, the magnitude is an approximation.
+pub fn incircle<T: Real>(a: Vector2<T>, b: Vector2<T>, c: Vector2<T>, d: Vector2<T>) -> T {
+    let (ad, bd, cd) = (a - d, b - d, c - d);
+    let (bdx_cdy, cdx_bdy) = (bd.x()*cd.y(), cd.x()*bd.y());
+    let (cdx_ady, adx_cdy) = (cd.x()*ad.y(), ad.x()*cd.y());
+    let (adx_bdy, bdx_ady) = (ad.x()*bd.y(), bd.x()*ad.y());
+    let a_lift = ad.x()*ad.x() + ad.y()*ad.y();
+    let b_lift = bd.x()*bd.x() + bd.y()*bd.y();
+    let c_lift = cd.x()*cd.x() + cd.y()*cd.y();
+    let determinant = a_lift*(bdx_cdy - cdx_bdy) + b_lift*(cdx_ady - adx_cdy) + c_lift*(adx_bdy - bdx_ady);
+    let permanent = (bdx_cdy.abs() + cdx_bdy.abs())*a_lift
+        + (cdx_ady.abs() + adx_cdy.abs())*b_lift
+        + (adx_bdy.abs() + bdx_ady.abs())*c_lift;
+    if determinant.abs() > error_bound::<T>(10.0, 96.0)*permanent {
+        return determinant;
+    }
+    incircle_exact([a, b, c, d], splitter::<T>()).estimate()
+}
+
+/// The exact [`incircle`], expanding the 4 by 4 determinant of the points
+/// lifted onto a paraboloid along the lifted coordinate.
+fn incircle_exact<T: Real>(points: [Vector2<T>; 4], splitter: T) -> Expansion<T, 384> {
+    let minor = |i: usize, j: usize| Expansion::<T, 4>::minor(points[i], points[j], splitter);
+    let (ab, bc, cd, da, ac, bd) = (minor(0, 1), minor(1, 2), minor(2, 3), minor(3, 0), minor(0, 2), minor(1, 3));
+    let (ca, db) = (ac.clone().neg(), bd.clone().neg());
+    // alternating signs along the column of lifted coordinates
+    let triangles = [
+        triangle_minors(&bc, &cd, &db),
+        triangle_minors(&ac, &cd, &da).neg(),
+        triangle_minors(&ab, &bd, &da),
+        triangle_minors(&ab, &bc, &ca).neg(),
+    ];
+    let terms: [Expansion<T, 96>; 4] = core::array::from_fn(|i| {
+        let x: Expansion<T, 48> = scale_twice::<T, 24, 48>(triangles[i].terms(), points[i].x(), splitter);
+        let y: Expansion<T, 48> = scale_twice::<T, 24, 48>(triangles[i].terms(), points[i].y(), splitter);
+        Expansion::sum(x.terms(), y.terms())
+    });
+    let first: Expansion<T, 192> = Expansion::sum(terms[0].terms(), terms[1].terms());
+    let second: Expansion<T, 192> = Expansion::sum(terms[2].terms(), terms[3].terms());
+    Expansion::sum(first.terms(), second.terms())
+}
+
+/// Positive when `e` is inside the sphere through `a`, `b`, `c` and `d`,
+/// negative when it is outside and zero exactly when the five points are
+/// cospherical. The tetrahedron has to have a positive [`orient3d`],
+/// otherwise the sign flips. The sign is always right, the magnitude is an
+/// approximation. Without the `alloc` feature the exact fallback keeps its
+/// terms on the stack, around 100KB for `f64`.
+pub fn insphere<T: Real>(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>, d: Vector3<T>, e: Vector3<T>) -> T {
+    let (ae, be, ce, de) = (a - e, b - e, c - e, d - e);
+    let (aex_bey, bex_aey) = (ae.x()*be.y(), be.x()*ae.y());
+    let (bex_cey, cex_bey) = (be.x()*ce.y(), ce.x()*be.y());
+    let (cex_dey, dex_cey) = (ce.x()*de.y(), de.x()*ce.y());
+    let (dex_aey, aex_dey) = (de.x()*ae.y(), ae.x()*de.y());
+    let (aex_cey, cex_aey) = (ae.x()*ce.y(), ce.x()*ae.y());
+    let (bex_dey, dex_bey) = (be.x()*de.y(), de.x()*be.y());
+    let (ab, bc, cd) = (aex_bey - bex_aey, bex_cey - cex_bey, cex_dey - dex_cey);
+    let (da, ac, bd) = (dex_aey - aex_dey, aex_cey - cex_aey, bex_dey - dex_bey);
+    let abc = ae.z()*bc - be.z()*ac + ce.z()*ab;
+    let bcd = be.z()*cd - ce.z()*bd + de.z()*bc;
+    let cda = ce.z()*da + de.z()*ac + ae.z()*cd;
+    let dab = de.z()*ab + ae.z()*bd + be.z()*da;
+    let [a_lift, b_lift, c_lift, d_lift] = [ae, be, ce, de].map(|p| p.x()*p.x() + p.y()*p.y() + p.z()*p.z());
+    let determinant = (d_lift*abc - c_lift*dab) + (b_lift*cda - a_lift*bcd);
+    let [aez, bez, cez, dez] = [ae, be, ce, de].map(|p| p.z().abs());
+    let permanent = ((cex_dey.abs() + dex_cey.abs())*bez + (dex_bey.abs() + bex_dey.abs())*cez + (bex_cey.abs() + cex_bey.abs())*dez)*a_lift
+        + ((dex_aey.abs() + aex_dey.abs())*cez + (aex_cey.abs() + cex_aey.abs())*dez + (cex_dey.abs() + dex_cey.abs())*aez)*b_lift
+        + ((aex_bey.abs() + bex_aey.abs())*dez + (bex_dey.abs() + dex_bey.abs())*aez + (dex_aey.abs() + aex_dey.abs())*bez)*c_lift
+        + ((bex_cey.abs() + cex_bey.abs())*aez + (cex_aey.abs() + aex_cey.abs())*bez + (aex_bey.abs() + bex_aey.abs())*cez)*d_lift;
+    if determinant.abs() > error_bound::<T>(16.0, 224.0)*permanent {
+        return -determinant;
+    }
+    insphere_exact([a, b, c, d, e], splitter::<T>())
+}
+
+/// The exact [`insphere`], expanding the 5 by 5 determinant of the points
+/// lifted onto a paraboloid along the lifted coordinate. The minors of the
+/// remaining four points are exact [`orient3d`]s.
+fn insphere_exact<T: Real>(points: [Vector3<T>; 5], splitter: T) -> T {
+    let mut total: Expansion<T, 5760> = Expansion::new();
+    for skipped in 0..points.len() {
+        let mut rest = [points[0]; 4];
+        for (slot, index) in (0..points.len()).filter(|&index| index != skipped).enumerate() {
+            rest[slot] = points[index];
+        }
+        let mut volume = orient3d_exact(rest, splitter);
+        if skipped % 2 == 0 {
+            volume = volume.neg();
+        }
+        let point = points[skipped];
+        let x: Expansion<T, 384> = scale_twice::<T, 192, 384>(volume.terms(), point.x(), splitter);
+        let y: Expansion<T, 384> = scale_twice::<T, 192, 384>(volume.terms(), point.y(), splitter);
+        let z: Expansion<T, 384> = scale_twice::<T, 192, 384>(volume.terms(), point.z(), splitter);
+        let xy: Expansion<T, 768> = Expansion::sum(x.terms(), y.terms());
+        let term: Expansion<T, 1152> = Expansion::sum(xy.terms(), z.terms());
+        total = Expansion::sum(total.terms(), term.terms());
+    }
+    total.estimate()
+}
+
+/// The sign of `normal.dot(point) - distance` worked out exactly.
+pub(crate) fn plane_side<T: Real>(normal: Vector3<T>, distance: T, point: Vector3<T>) -> Ordering {
+    let splitter = splitter::<T>();
+    let (x, x_error) = two_product(normal.x(), point.x(), splitter);
+    let (y, y_error) = two_product(normal.y(), point.y(), splitter);
+    let (z, z_error) = two_product(normal.z(), point.z(), splitter);
+    let xy: Expansion<T, 4> = Expansion::sum(&[x_error, x], &[y_error, y]);
+    let xyz: Expansion<T, 6> = Expansion::sum(xy.terms(), &[z_error, z]);
+    let side: Expansion<T, 7> = Expansion::sum(xyz.terms(), &[-distance]);
+    side.estimate().partial_cmp(&T::ZERO).unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+
+    use crate::{geometry::Plane, vector::{DVec2, DVec3}};
+
+    use super::{incircle, insphere, orient2d, orient3d};
+
+    #[test]
+    fn predicates() {
+        // points nudged off a line by the smallest steps a double can take
+        let (a, c) = (DVec2::new(0.5, 0.5), DVec2::new(24.0, 24.0));
+        let mut signs = [0; 3];
+        for i in 0..64 {
+            for j in 0..64 {
+                let b = DVec2::new(0.5 + i as f64*f64::EPSILON, 0.5 + j as f64*f64::EPSILON);
+                let sign = orient2d(a, b, c);
+                // below the line, on it and above it
+                let expected = (i as i32 - j as i32).signum();
+                assert_eq!(sign.partial_cmp(&0.0).map(|ordering| ordering as i32), Some(expected));
+                signs[(expected + 1) as usize] += 1;
+            }
+        }
+        assert!(signs.iter().all(|&count| count > 0));
+
+        // cocircular and cospherical points a third apart, which doubles
+        // can't hold exactly
+        let third = 1.0/3.0;
+        let square = [DVec2::new(0.0, 0.0), DVec2::new(third, 0.0), DVec2::new(third, third), DVec2::new(0.0, third)];
+        assert_eq!(incircle(square[0], square[1], square[2], square[3]), 0.0);
+        assert!(incircle(square[0], square[1], square[2], DVec2::new(third/2.0, third/2.0)) > 0.0);
+        assert!(incircle(square[0], square[1], square[2], DVec2::new(2.0, 2.0)) < 0.0);
+
+        let corner = |x: f64, y: f64, z: f64| DVec3::new(x*third, y*third, z*third);
+        let (a, b, c, d) = (corner(0.0, 0.0, 0.0), corner(1.0, 0.0, 0.0), corner(0.0, 1.0, 0.0), corner(0.0, 0.0, 1.0));
+        assert!(orient3d(a, b, c, d) > 0.0);
+        assert!(orient3d(a, c, b, d) < 0.0);
+        assert_eq!(orient3d(a, b, c, corner(1.0, 1.0, 0.0)), 0.0);
+        assert_eq!(insphere(a, b, c, d, corner(1.0, 1.0, 1.0)), 0.0);
+        assert!(insphere(a, b, c, d, corner(0.25, 0.25, 0.25)) > 0.0);
+        assert!(insphere(a, b, c, d, corner(2.0, 2.0, 2.0)) < 0.0);
+
+        // halfway between two corners of a slanted triangle
+        let (a, b, c) = (corner(1.0, 0.0, 0.0), corner(0.0, 1.0, 0.0), corner(0.0, 0.0, 1.0));
+        assert_eq!(corner(0.5, 0.5, 0.0).plane_side_exact(a, b, c), Ordering::Equal);
+        assert_eq!(corner(0.5, 0.5, 0.1).plane_side_exact(a, b, c), Ordering::Greater);
+        let plane = Plane::from_normal_distance(DVec3::new(0.0, 0.0, 1.0), third);
+        assert_eq!(plane.side_exact(corner(5.0, 7.0, 1.0)), Ordering::Equal);
+        assert_eq!(plane.side_exact(corner(5.0, 7.0, 0.9)), Ordering::Less);
+    }
+}
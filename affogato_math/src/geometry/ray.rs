@@ -4,9 +4,9 @@ use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num
 #[cfg(feature="serde")]
 use serde::{Serialize, Deserialize};
 
-use crate::vector::Vector3;
+use crate::{algebra::{solve_cubic, solve_quadratic, solve_quartic}, vector::{Vector2, Vector3}};
 
-use super::{Sphere, Triangle3D};
+use super::{solid::radial, Capsule, Circle, Cone, CubicSegment2D, Cylinder, LinearSegment2D, LinearSegment3D, OrientedBox3D, Plane, QuadraticSegment2D, Rect, Rect3D, Sphere, Tetrahedron, Torus, Triangle2D, Triangle3D};
 
 #[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
 #[derive(Default, Clone, Copy, Debug)]
@@ -102,53 +102,237 @@ impl<T: Real> Ray3D<T>  {
             None
         }
     }
-    // This is still in production
-    // pub fn intersect_cube(&self, rect: &Rect3D<T>) -> bool 
-    //     where T: Debug {
-    //     let mut tmin = (rect.min.x - self.origin.x) / self.direction.x; 
-    //     let mut tmax = (rect.max.x - self.origin.x) / self.direction.x; 
-
-    //     if tmin > tmax {
-    //         core::mem::swap(&mut tmin, &mut tmax);
-    //     } 
-
-    //     let mut tymin = (rect.min.y - self.origin.y) / self.direction.y; 
-    //     let mut tymax = (rect.max.y - self.origin.y) / self.direction.y; 
-
-    //     if tymin > tymax {
-    //         core::mem::swap(&mut tymin, &mut tymax);
-    //     } 
-
-    //     if tmin > tymax || tymin > tmax {
-    //         return false; 
-    //     }
-
-    //     if tymin > tmin {
-    //         tmin = tymin
-    //     } 
-    //     if tymax < tmax {
-    //         tmax = tymax
-    //     } 
-
-    //     let mut tzmin = (rect.min.z - self.origin.z) / self.direction.z; 
-    //     let mut tzmax = (rect.max.z - self.origin.z) / self.direction.z; 
-
-    //     if tzmin > tzmax {
-    //         core::mem::swap(&mut tzmin, &mut tzmax);
-    //     } 
-
-    //     if tmin > tzmax || tzmin > tmax {
-    //         return false;
-    //     }
-
-    //     if tzmin > tmin {
-    //         tmin = tzmin
-    //     }
-    //     if tzmax < tmax {
-    //         tmax = tzmax
-    //     } 
-    //     true
-    // }
+    /// The nearest of `hits` in front of the origin where the ray goes into
+    /// the surface rather than out of it.
+    fn nearest_entry(&self, hits: impl IntoIterator<Item = Option<(T, Vector3<T>)>>) -> Option<RayHitInfo<Vector3<T>>> {
+        hits.into_iter().flatten()
+            .filter(|&(distance, normal)| distance >= T::ZERO && normal.dot(&self.direction) < T::ZERO)
+            .reduce(|best, hit| if hit.0 < best.0 { hit } else { best })
+            .map(|(distance, normal)| RayHitInfo { distance, normal, point: self.at(distance) })
+    }
+    /// Where the ray crosses the round side of the cylinder of `radius`
+    /// between `start` and `end`, with the normals pointing out of it.
+    fn cylinder_side(&self, start: Vector3<T>, end: Vector3<T>, radius: T) -> [Option<(T, Vector3<T>)>; 2] {
+        let mut hits = [None; 2];
+        let height = (end-start).length();
+        if height == T::ZERO {
+            return hits;
+        }
+        let axis = (end-start)/height;
+        let offset = self.origin-start;
+        let (direction, across) = (radial(self.direction, axis), radial(offset, axis));
+        let roots = solve_quadratic(direction.dot(&direction), T::from_f64(2.0)*direction.dot(&across), across.dot(&across) - radius*radius);
+        for (hit, distance) in hits.iter_mut().zip(roots.into_iter().flatten()) {
+            let along = (offset + self.direction*distance).dot(&axis);
+            if along >= T::ZERO && along <= height {
+                *hit = Some((distance, (across + direction*distance)/radius));
+            }
+        }
+        hits
+    }
+    /// Where the ray goes into the flat disc facing `normal`.
+    fn disc(&self, center: Vector3<T>, normal: Vector3<T>, radius: T) -> Option<(T, Vector3<T>)> {
+        let facing = self.direction.dot(&normal);
+        if facing >= T::ZERO {
+            return None;
+        }
+        let distance = (center-self.origin).dot(&normal)/facing;
+        ((self.at(distance)-center).length_squared() <= radius*radius).then_some((distance, normal))
+    }
+    pub fn intersect_capsule(&self, capsule: &Capsule<T>) -> Option<RayHitInfo<Vector3<T>>> {
+        let [side1, side2] = self.cylinder_side(capsule.start, capsule.end, capsule.radius);
+        let end = |center: Vector3<T>| self.intersect_sphere(&Sphere::new(center, capsule.radius)).map(|hit| (hit.distance, hit.normal));
+        self.nearest_entry([side1, side2, end(capsule.start), end(capsule.end)])
+    }
+    pub fn intersect_cylinder(&self, cylinder: &Cylinder<T>) -> Option<RayHitInfo<Vector3<T>>> {
+        let [side1, side2] = self.cylinder_side(cylinder.start, cylinder.end, cylinder.radius);
+        let axis = cylinder.axis();
+        self.nearest_entry([side1, side2, self.disc(cylinder.start, -axis, cylinder.radius), self.disc(cylinder.end, axis, cylinder.radius)])
+    }
+    pub fn intersect_cone(&self, cone: &Cone<T>) -> Option<RayHitInfo<Vector3<T>>> {
+        let height = cone.height();
+        if height == T::ZERO {
+            return None;
+        }
+        // points on the side are at the same angle to the axis going down
+        // from the apex
+        let axis = (cone.base-cone.apex)/height;
+        let cos2 = height*height/(height*height + cone.radius*cone.radius);
+        let offset = self.origin-cone.apex;
+        let (direction_along, offset_along) = (self.direction.dot(&axis), offset.dot(&axis));
+        let roots = solve_quadratic(
+            direction_along*direction_along - cos2,
+            T::from_f64(2.0)*(direction_along*offset_along - cos2*self.direction.dot(&offset)),
+            offset_along*offset_along - cos2*offset.dot(&offset),
+        );
+        let mut hits = [None; 3];
+        for (hit, distance) in hits.iter_mut().zip(roots.into_iter().flatten()) {
+            let point = offset + self.direction*distance;
+            let along = point.dot(&axis);
+            if along >= T::ZERO && along <= height {
+                *hit = Some((distance, (point*cos2 - axis*along).normalize()));
+            }
+        }
+        hits[2] = self.disc(cone.base, axis, cone.radius);
+        self.nearest_entry(hits)
+    }
+    pub fn intersect_torus(&self, torus: &Torus<T>) -> Option<RayHitInfo<Vector3<T>>> {
+        // in units of the size of the torus, starting from where the ray goes
+        // into the sphere around it to keep the quartic's coefficients small
+        let scale = torus.major_radius + torus.minor_radius;
+        let offset = (self.origin-torus.center)/scale;
+        let along = -offset.dot(&self.direction);
+        let closest = (offset + self.direction*along).length_squared();
+        if closest > T::ONE {
+            return None;
+        }
+        let skipped = (along - (T::ONE - closest).sqrt()).max(T::ZERO);
+        let offset = offset + self.direction*skipped;
+        let (major, minor) = (torus.major_radius/scale, torus.minor_radius/scale);
+        let (offset_axial, direction_axial) = (offset.dot(&torus.axis), self.direction.dot(&torus.axis));
+        let b = T::from_f64(2.0)*offset.dot(&self.direction);
+        let c = offset.dot(&offset) + major*major - minor*minor;
+        let ring = T::from_f64(4.0)*major*major;
+        let roots = solve_quartic(
+            T::ONE,
+            T::from_f64(2.0)*b,
+            b*b + T::from_f64(2.0)*c - ring*(T::ONE - direction_axial*direction_axial),
+            T::from_f64(2.0)*b*c - T::from_f64(2.0)*ring*(offset.dot(&self.direction) - offset_axial*direction_axial),
+            c*c - ring*(offset.dot(&offset) - offset_axial*offset_axial),
+        );
+        let mut hits = [None; 4];
+        for (hit, distance) in hits.iter_mut().zip(roots.into_iter().flatten()) {
+            let point = offset + self.direction*distance;
+            let around = radial(point, torus.axis);
+            if around.length_squared() > T::ZERO {
+                *hit = Some(((skipped + distance)*scale, (point - around.normalize()*major).normalize()));
+            }
+        }
+        self.nearest_entry(hits)
+    }
+    /// Clips the line of the ray against the faces of a convex shape, each
+    /// given by a point on it and its unit normal pointing out. Gives the
+    /// distances where the line goes in and comes out, with the normal of the
+    /// face it goes in through, as long as that isn't all behind the origin.
+    fn convex_span(&self, faces: impl IntoIterator<Item = (Vector3<T>, Vector3<T>)>) -> Option<(T, T, Vector3<T>)> {
+        let (mut near, mut far) = (-T::INFINITY, T::INFINITY);
+        let mut normal = Vector3::ZERO;
+        for (point, face_normal) in faces {
+            let facing = self.direction.dot(&face_normal);
+            // how far the origin is behind the face
+            let depth = (point-self.origin).dot(&face_normal);
+            if facing == T::ZERO {
+                if depth < T::ZERO {
+                    return None;
+                }
+                continue;
+            }
+            let distance = depth/facing;
+            if facing < T::ZERO {
+                if distance > near {
+                    near = distance;
+                    normal = face_normal;
+                }
+            } else {
+                far = far.min(distance);
+            }
+        }
+        (near <= far && far >= T::ZERO).then_some((near, far, normal))
+    }
+    /// The hit where the ray goes into a convex shape, which misses when the
+    /// origin is already inside of it.
+    fn convex_entry(&self, span: Option<(T, T, Vector3<T>)>) -> Option<RayHitInfo<Vector3<T>>> {
+        let (near, _, normal) = span?;
+        (near >= T::ZERO).then(|| RayHitInfo { distance: near, normal, point: self.at(near) })
+    }
+    fn rect_faces(rect: &Rect3D<T>) -> [(Vector3<T>, Vector3<T>); 6] {
+        [
+            (rect.min, Vector3::left()), (rect.min, Vector3::bottom()), (rect.min, Vector3::backward()),
+            (rect.max, Vector3::right()), (rect.max, Vector3::top()), (rect.max, Vector3::forward()),
+        ]
+    }
+    /// The distances along the ray where it goes into and comes out of
+    /// `rect`, using the slab method. The first is negative when the origin
+    /// is inside of it.
+    pub fn rect_span(&self, rect: &Rect3D<T>) -> Option<(T, T)> {
+        self.convex_span(Self::rect_faces(rect)).map(|(near, far, _)| (near, far))
+    }
+    /// Where the ray goes into `rect`, with the normal of the face it hits.
+    pub fn intersect_rect(&self, rect: &Rect3D<T>) -> Option<RayHitInfo<Vector3<T>>> {
+        self.convex_entry(self.convex_span(Self::rect_faces(rect)))
+    }
+    pub fn intersect_oriented_box(&self, oriented_box: &OrientedBox3D<T>) -> Option<RayHitInfo<Vector3<T>>> {
+        let (axes, extents) = (oriented_box.axes(), oriented_box.half_extents);
+        let faces = (0..6).map(|face| {
+            let (axis, extent) = (axes[face%3], extents[face%3]);
+            let normal = if face < 3 { axis } else { -axis };
+            (oriented_box.center + normal*extent, normal)
+        });
+        self.convex_entry(self.convex_span(faces))
+    }
+    pub fn intersect_tetrahedron(&self, tetrahedron: &Tetrahedron<T>) -> Option<RayHitInfo<Vector3<T>>> {
+        let mut faces = [(Vector3::ZERO, Vector3::ZERO); 4];
+        for (opposite, face) in faces.iter_mut().enumerate() {
+            let [a, b, c] = [1, 2, 3].map(|i| tetrahedron[(opposite+i)%4]);
+            let normal = (b-a).cross(&(c-a));
+            let length = normal.length();
+            if length == T::ZERO {
+                return None;
+            }
+            let normal = normal/length;
+            *face = (a, if (tetrahedron[opposite]-a).dot(&normal) > T::ZERO { -normal } else { normal });
+        }
+        self.convex_entry(self.convex_span(faces))
+    }
+    /// Where the ray crosses `plane` from either side, with the normal turned
+    /// to face the ray.
+    pub fn intersect_plane(&self, plane: &Plane<T>) -> Option<RayHitInfo<Vector3<T>>> {
+        let facing = self.direction.dot(&plane.normal());
+        if facing == T::ZERO {
+            return None;
+        }
+        let distance = (plane.distance() - self.origin.dot(&plane.normal()))/facing;
+        let normal = if facing < T::ZERO { plane.normal() } else { -plane.normal() };
+        (distance >= T::ZERO).then(|| RayHitInfo { distance, normal, point: self.at(distance) })
+    }
+    /// Where the ray crosses the flat disc of `radius` around `center` facing
+    /// the unit `normal`, from either side, with the normal turned to face
+    /// the ray.
+    pub fn intersect_disc(&self, center: Vector3<T>, normal: Vector3<T>, radius: T) -> Option<RayHitInfo<Vector3<T>>> {
+        let normal = if self.direction.dot(&normal) > T::ZERO { -normal } else { normal };
+        self.nearest_entry([self.disc(center, normal, radius)])
+    }
+    /// The closest points between the ray and the line through `origin`
+    /// along `direction`, kept between `range` times `direction` from it.
+    ///
+    /// from: Real-Time Collision Detection, Christer Ericson, 5.1.9
+    fn closest_to_line(&self, origin: Vector3<T>, direction: Vector3<T>, range: (T, T)) -> (Vector3<T>, Vector3<T>) {
+        let offset = self.origin-origin;
+        let (b, c) = (self.direction.dot(&direction), self.direction.dot(&offset));
+        let (e, f) = (direction.dot(&direction), direction.dot(&offset));
+        if e == T::ZERO {
+            return (self.at((-c).max(T::ZERO)), origin);
+        }
+        // the ray's direction is unit length, so its own term drops out
+        let denominator = e - b*b;
+        let mut along = if denominator > T::ZERO { ((b*f - c*e)/denominator).max(T::ZERO) } else { T::ZERO };
+        let mut other = (b*along + f)/e;
+        if other < range.0 || other > range.1 {
+            other = other.max(range.0).min(range.1);
+            along = (other*b - c).max(T::ZERO);
+        }
+        (self.at(along), origin + direction*other)
+    }
+    /// The closest point on this ray to `ray`, followed by the closest point
+    /// on `ray` to this one.
+    pub fn closest_points(&self, ray: &Self) -> (Vector3<T>, Vector3<T>) {
+        self.closest_to_line(ray.origin, ray.direction, (T::ZERO, T::INFINITY))
+    }
+    /// The closest point on the ray to `segment`, followed by the closest
+    /// point on `segment` to the ray.
+    pub fn closest_points_segment(&self, segment: &LinearSegment3D<T>) -> (Vector3<T>, Vector3<T>) {
+        self.closest_to_line(segment.start, segment.end-segment.start, (T::ZERO, T::ONE))
+    }
 }
 
 impl<T: Real> Ray for Ray3D<T>  {
@@ -168,4 +352,283 @@ impl<T: Real> Ray for Ray3D<T>  {
     fn at(&self, distance: <Self::Vector as VectorSpace>::Scalar) -> Self::Vector {
         return self.origin + self.direction*distance;
     }
-}
\ No newline at end of file
+}
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy)]
+pub struct Ray2D<T: Real> {
+    origin: Vector2<T>,
+    direction: Vector2<T>,
+}
+impl<T: Real> Ray2D<T> {
+    pub fn new(orig: Vector2<T>, look_at: Vector2<T>) -> Self {
+        Self { origin: orig, direction: (look_at-orig).normalize() }
+    }
+    /// Returns a ray pointing to the left of the graph <-1, 0>
+    pub fn left() -> Self {
+        Self { origin: Vector2::ZERO, direction: Vector2::left() }
+    }
+    /// Returns a ray pointing to the right of the graph <1, 0>
+    pub fn right() -> Self {
+        Self { origin: Vector2::ZERO, direction: Vector2::right() }
+    }
+    /// Returns a ray pointing to the top of the graph <0, 1>
+    pub fn top() -> Self {
+        Self { origin: Vector2::ZERO, direction: Vector2::top() }
+    }
+    /// Returns a ray pointing to the bottom of the graph <0, -1>
+    pub fn bottom() -> Self {
+        Self { origin: Vector2::ZERO, direction: Vector2::bottom() }
+    }
+    /// The nearest of `hits` in front of the origin where the ray goes into
+    /// the shape rather than out of it.
+    fn nearest_entry(&self, hits: impl IntoIterator<Item = Option<(T, Vector2<T>)>>) -> Option<RayHitInfo<Vector2<T>>> {
+        hits.into_iter().flatten()
+            .filter(|&(distance, normal)| distance >= T::ZERO && normal.dot(&self.direction) < T::ZERO)
+            .reduce(|best, hit| if hit.0 < best.0 { hit } else { best })
+            .map(|(distance, normal)| RayHitInfo { distance, normal, point: self.at(distance) })
+    }
+    /// Clips the line of the ray against the edges of a convex shape, the
+    /// same way as for a [`Ray3D`] against its faces.
+    fn convex_span(&self, edges: impl IntoIterator<Item = (Vector2<T>, Vector2<T>)>) -> Option<(T, T, Vector2<T>)> {
+        let (mut near, mut far) = (-T::INFINITY, T::INFINITY);
+        let mut normal = Vector2::ZERO;
+        for (point, edge_normal) in edges {
+            let facing = self.direction.dot(&edge_normal);
+            let depth = (point-self.origin).dot(&edge_normal);
+            if facing == T::ZERO {
+                if depth < T::ZERO {
+                    return None;
+                }
+                continue;
+            }
+            let distance = depth/facing;
+            if facing < T::ZERO {
+                if distance > near {
+                    near = distance;
+                    normal = edge_normal;
+                }
+            } else {
+                far = far.min(distance);
+            }
+        }
+        (near <= far && far >= T::ZERO).then_some((near, far, normal))
+    }
+    fn convex_entry(&self, span: Option<(T, T, Vector2<T>)>) -> Option<RayHitInfo<Vector2<T>>> {
+        let (near, _, normal) = span?;
+        (near >= T::ZERO).then(|| RayHitInfo { distance: near, normal, point: self.at(near) })
+    }
+    /// Where the ray first crosses the curve with the coefficients of
+    /// `coefficients[i]*t^i`, given the roots in `t` of its distance from the
+    /// line of the ray. The normal is turned to face the ray, so the curve is
+    /// hit from either side.
+    fn curve_crossing(&self, coefficients: &[Vector2<T>], roots: impl IntoIterator<Item = T>) -> Option<RayHitInfo<Vector2<T>>> {
+        roots.into_iter()
+            .filter(|&t| t >= T::ZERO && t <= T::ONE)
+            .map(|t| {
+                let (mut point, mut tangent, mut power) = (Vector2::ZERO, Vector2::ZERO, T::ONE);
+                for (i, &coefficient) in coefficients.iter().enumerate() {
+                    if i > 0 {
+                        tangent += coefficient*(power*T::from_f64(i as f64));
+                        power *= t;
+                    }
+                    point += coefficient*power;
+                }
+                let normal = if tangent.length_squared() == T::ZERO { -self.direction } else { tangent.rotate_90().normalize() };
+                let normal = if normal.dot(&self.direction) > T::ZERO { -normal } else { normal };
+                ((point-self.origin).dot(&self.direction), normal)
+            })
+            .filter(|&(distance, _)| distance >= T::ZERO)
+            .reduce(|best, hit| if hit.0 < best.0 { hit } else { best })
+            .map(|(distance, normal)| RayHitInfo { distance, normal, point: self.at(distance) })
+    }
+    /// How far each coefficient of a curve puts it across the line of the ray.
+    fn across(&self, coefficient: Vector2<T>) -> T {
+        self.direction.rotate_90().dot(&coefficient)
+    }
+    pub fn intersect_circle(&self, circle: &Circle<T>) -> Option<RayHitInfo<Vector2<T>>> {
+        let offset = self.origin-circle.center;
+        let roots = solve_quadratic(T::ONE, T::from_f64(2.0)*offset.dot(&self.direction), offset.dot(&offset) - circle.radius*circle.radius);
+        let mut hits = [None; 2];
+        for (hit, distance) in hits.iter_mut().zip(roots.into_iter().flatten()) {
+            *hit = Some((distance, (offset + self.direction*distance)/circle.radius));
+        }
+        self.nearest_entry(hits)
+    }
+    fn rect_edges(rect: &Rect<T>) -> [(Vector2<T>, Vector2<T>); 4] {
+        [
+            (rect.min, Vector2::left()), (rect.min, Vector2::bottom()),
+            (rect.max, Vector2::right()), (rect.max, Vector2::top()),
+        ]
+    }
+    /// The distances along the ray where it goes into and comes out of
+    /// `rect`, using the slab method. The first is negative when the origin
+    /// is inside of it.
+    pub fn rect_span(&self, rect: &Rect<T>) -> Option<(T, T)> {
+        self.convex_span(Self::rect_edges(rect)).map(|(near, far, _)| (near, far))
+    }
+    /// Where the ray goes into `rect`, with the normal of the edge it hits.
+    pub fn intersect_rect(&self, rect: &Rect<T>) -> Option<RayHitInfo<Vector2<T>>> {
+        self.convex_entry(self.convex_span(Self::rect_edges(rect)))
+    }
+    /// Where the ray goes into `triangle`, which can wind either way.
+    pub fn intersect_triangle(&self, triangle: &Triangle2D<T>) -> Option<RayHitInfo<Vector2<T>>> {
+        let winding = (triangle[1]-triangle[0]).cross(&(triangle[2]-triangle[0]));
+        if winding == T::ZERO {
+            return None;
+        }
+        let edges = (0..3).map(|i| {
+            let (start, end) = (triangle[i], triangle[(i+1)%3]);
+            let normal = (end-start).rotate_270().normalize();
+            (start, if winding > T::ZERO { normal } else { -normal })
+        });
+        self.convex_entry(self.convex_span(edges))
+    }
+    /// Where the ray crosses `segment` from either side, with the normal
+    /// turned to face the ray.
+    pub fn intersect_segment(&self, segment: &LinearSegment2D<T>) -> Option<RayHitInfo<Vector2<T>>> {
+        let coefficients = [segment.start, segment.end-segment.start];
+        let slope = self.across(coefficients[1]);
+        if slope == T::ZERO {
+            return None;
+        }
+        self.curve_crossing(&coefficients, [-self.across(coefficients[0]-self.origin)/slope])
+    }
+    /// The first place the ray crosses the curve, from the roots of the
+    /// quadratic for its distance across the ray.
+    pub fn intersect_quadratic(&self, segment: &QuadraticSegment2D<T>) -> Option<RayHitInfo<Vector2<T>>> {
+        let coefficients = [
+            segment.start,
+            (segment.control-segment.start)*T::from_f64(2.0),
+            segment.start - segment.control*T::from_f64(2.0) + segment.end,
+        ];
+        let [c0, c1, c2] = [coefficients[0]-self.origin, coefficients[1], coefficients[2]].map(|c| self.across(c));
+        self.curve_crossing(&coefficients, solve_quadratic(c2, c1, c0).into_iter().flatten())
+    }
+    /// The first place the ray crosses the curve, from the roots of the
+    /// cubic for its distance across the ray.
+    pub fn intersect_cubic(&self, segment: &CubicSegment2D<T>) -> Option<RayHitInfo<Vector2<T>>> {
+        let three = T::from_f64(3.0);
+        let coefficients = [
+            segment.start,
+            (segment.control1-segment.start)*three,
+            (segment.start - segment.control1*T::from_f64(2.0) + segment.control2)*three,
+            segment.end - segment.start + (segment.control1-segment.control2)*three,
+        ];
+        let [c0, c1, c2, c3] = [coefficients[0]-self.origin, coefficients[1], coefficients[2], coefficients[3]].map(|c| self.across(c));
+        self.curve_crossing(&coefficients, solve_cubic(c3, c2, c1, c0).into_iter().flatten())
+    }
+}
+
+impl<T: Real> Ray for Ray2D<T>  {
+    type Vector = Vector2<T>;
+    fn direction(&self) -> &Self::Vector {
+        &self.direction
+    }
+    fn origin(&self) -> &Self::Vector {
+        &self.origin
+    }
+    fn look(&mut self, at: Self::Vector) {
+        self.direction = (at-self.origin).normalize();
+    }
+    fn set_origin(&mut self, origin: Self::Vector) {
+        self.origin = origin;
+    }
+    fn at(&self, distance: <Self::Vector as VectorSpace>::Scalar) -> Self::Vector {
+        self.origin + self.direction*distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::{groups::vector_spaces::NormedVectorSpace, num::Zero};
+
+    use crate::{geometry::{Circle, CubicSegment2D, LinearSegment2D, LinearSegment3D, Plane, QuadraticSegment2D, Ray2D, Ray3D, Rect, Rect3D, Tetrahedron, Triangle2D}, vector::{DVec2, DVec3}};
+
+    #[test]
+    fn flat_sided_hits() {
+        let rect = Rect3D::new(DVec3::new(-1.0, -1.0, -1.0), DVec3::new(1.0, 2.0, 1.0));
+        let ray = Ray3D::new(DVec3::new(0.0, 5.0, 0.0), DVec3::new(0.0, 0.0, 0.0));
+        assert_eq!(ray.rect_span(&rect), Some((3.0, 6.0)));
+        let hit = ray.intersect_rect(&rect).unwrap();
+        assert!((hit.point - DVec3::new(0.0, 2.0, 0.0)).length() < 1e-12 && (hit.normal - DVec3::new(0.0, 1.0, 0.0)).length() < 1e-12);
+        let inside = Ray3D::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 0.0, 0.0));
+        assert_eq!(inside.rect_span(&rect), Some((-1.0, 1.0)));
+        assert!(inside.intersect_rect(&rect).is_none());
+        assert!(Ray3D::new(DVec3::new(0.0, 5.0, 0.0), DVec3::new(0.0, 6.0, 0.0)).rect_span(&rect).is_none());
+        assert!(Ray3D::new(DVec3::new(3.0, 5.0, 0.0), DVec3::new(3.0, 0.0, 0.0)).intersect_rect(&rect).is_none());
+
+        let plane = Plane::from_normal_distance(DVec3::new(0.0, 0.0, 1.0), 2.0);
+        let hit = Ray3D::new(DVec3::new(1.0, 0.0, 5.0), DVec3::new(1.0, 0.0, 0.0)).intersect_plane(&plane).unwrap();
+        assert!((hit.distance - 3.0).abs() < 1e-12 && hit.normal == DVec3::new(0.0, 0.0, 1.0));
+        let hit = Ray3D::new(DVec3::new(1.0, 0.0, -5.0), DVec3::new(1.0, 0.0, 0.0)).intersect_plane(&plane).unwrap();
+        assert!((hit.distance - 7.0).abs() < 1e-12 && hit.normal == DVec3::new(0.0, 0.0, -1.0));
+        assert!(Ray3D::new(DVec3::new(1.0, 0.0, 5.0), DVec3::new(1.0, 0.0, 6.0)).intersect_plane(&plane).is_none());
+
+        let tetrahedron = Tetrahedron::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 0.0, 0.0), DVec3::new(0.0, 1.0, 0.0), DVec3::new(0.0, 0.0, 1.0));
+        let hit = Ray3D::new(DVec3::new(0.2, 0.2, -3.0), DVec3::new(0.2, 0.2, 0.0)).intersect_tetrahedron(&tetrahedron).unwrap();
+        assert!((hit.distance - 3.0).abs() < 1e-12 && (hit.normal - DVec3::new(0.0, 0.0, -1.0)).length() < 1e-12);
+        let hit = Ray3D::new(DVec3::new(2.0, 2.0, 2.0), DVec3::new(0.0, 0.0, 0.0)).intersect_tetrahedron(&tetrahedron).unwrap();
+        assert!((hit.point - DVec3::from(1.0/3.0)).length() < 1e-12 && (hit.normal - DVec3::from(1.0).normalize()).length() < 1e-12);
+        assert!(Ray3D::new(DVec3::new(0.8, 0.8, -3.0), DVec3::new(0.8, 0.8, 0.0)).intersect_tetrahedron(&tetrahedron).is_none());
+
+        let hit = Ray3D::new(DVec3::new(0.5, -2.0, 0.0), DVec3::new(0.5, 0.0, 0.0)).intersect_disc(DVec3::ZERO, DVec3::new(0.0, 1.0, 0.0), 1.0).unwrap();
+        assert!((hit.distance - 2.0).abs() < 1e-12 && hit.normal == DVec3::new(0.0, -1.0, 0.0));
+        assert!(Ray3D::new(DVec3::new(1.5, -2.0, 0.0), DVec3::new(1.5, 0.0, 0.0)).intersect_disc(DVec3::ZERO, DVec3::new(0.0, 1.0, 0.0), 1.0).is_none());
+    }
+
+    #[test]
+    fn closest_points() {
+        let ray = Ray3D::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 0.0, 0.0));
+        let crossing = Ray3D::new(DVec3::new(3.0, -4.0, 2.0), DVec3::new(3.0, 0.0, 2.0));
+        let (on_ray, on_other) = ray.closest_points(&crossing);
+        assert!((on_ray - DVec3::new(3.0, 0.0, 0.0)).length() < 1e-12 && (on_other - DVec3::new(3.0, 0.0, 2.0)).length() < 1e-12);
+        // behind the origin the ray stops at it
+        let behind = Ray3D::new(DVec3::new(-3.0, -4.0, 0.0), DVec3::new(-3.0, 0.0, 0.0));
+        let (on_ray, on_other) = ray.closest_points(&behind);
+        assert!(on_ray.length() < 1e-12 && (on_other - DVec3::new(-3.0, 0.0, 0.0)).length() < 1e-12);
+
+        let segment = LinearSegment3D { start: DVec3::new(2.0, 1.0, 0.0), end: DVec3::new(2.0, 3.0, 0.0) };
+        let (on_ray, on_segment) = ray.closest_points_segment(&segment);
+        assert!((on_ray - DVec3::new(2.0, 0.0, 0.0)).length() < 1e-12 && (on_segment - DVec3::new(2.0, 1.0, 0.0)).length() < 1e-12);
+        let parallel = LinearSegment3D { start: DVec3::new(-2.0, 1.0, 0.0), end: DVec3::new(-1.0, 1.0, 0.0) };
+        let (on_ray, on_segment) = ray.closest_points_segment(&parallel);
+        assert!(on_ray.length() < 1e-12 && (on_segment - DVec3::new(-1.0, 1.0, 0.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn hits_2d() {
+        let ray = Ray2D::new(DVec2::new(-5.0, 0.0), DVec2::new(0.0, 0.0));
+        let hit = ray.intersect_circle(&Circle::new(DVec2::new(0.0, 0.0), 2.0)).unwrap();
+        assert!((hit.distance - 3.0).abs() < 1e-12 && (hit.normal - DVec2::new(-1.0, 0.0)).length() < 1e-12);
+        assert!(ray.intersect_circle(&Circle::new(DVec2::new(0.0, 3.0), 2.0)).is_none());
+
+        let rect = Rect::new(DVec2::new(-1.0, -2.0), DVec2::new(1.0, 2.0));
+        assert_eq!(ray.rect_span(&rect), Some((4.0, 6.0)));
+        let hit = ray.intersect_rect(&rect).unwrap();
+        assert!((hit.point - DVec2::new(-1.0, 0.0)).length() < 1e-12 && hit.normal == DVec2::new(-1.0, 0.0));
+
+        let clockwise = Triangle2D::new(DVec2::new(0.0, 0.0), DVec2::new(0.0, 2.0), DVec2::new(2.0, 0.0));
+        let hit = Ray2D::new(DVec2::new(3.0, 3.0), DVec2::new(0.0, 0.0)).intersect_triangle(&clockwise).unwrap();
+        assert!((hit.point - DVec2::new(1.0, 1.0)).length() < 1e-12 && (hit.normal - DVec2::new(1.0, 1.0).normalize()).length() < 1e-12);
+        assert!(Ray2D::new(DVec2::new(3.0, 3.0), DVec2::new(4.0, 3.0)).intersect_triangle(&clockwise).is_none());
+
+        let segment = LinearSegment2D { start: DVec2::new(2.0, -1.0), end: DVec2::new(2.0, 1.0) };
+        let hit = Ray2D::new(DVec2::new(5.0, 0.5), DVec2::new(0.0, 0.5)).intersect_segment(&segment).unwrap();
+        assert!((hit.distance - 3.0).abs() < 1e-12 && (hit.normal - DVec2::new(1.0, 0.0)).length() < 1e-12);
+        assert!(Ray2D::new(DVec2::new(5.0, 1.5), DVec2::new(0.0, 1.5)).intersect_segment(&segment).is_none());
+
+        // y = 1 - x^2 between -1 and 1
+        let quadratic = QuadraticSegment2D::new(DVec2::new(-1.0, 0.0), DVec2::new(0.0, 2.0), DVec2::new(1.0, 0.0));
+        let hit = Ray2D::new(DVec2::new(0.5, -3.0), DVec2::new(0.5, 0.0)).intersect_quadratic(&quadratic).unwrap();
+        assert!((hit.distance - 3.75).abs() < 1e-12 && (hit.normal + DVec2::new(1.0, 1.0).normalize()).length() < 1e-12);
+        let hit = Ray2D::new(DVec2::new(0.5, 3.0), DVec2::new(0.5, 0.0)).intersect_quadratic(&quadratic).unwrap();
+        assert!((hit.point - DVec2::new(0.5, 0.75)).length() < 1e-12 && (hit.normal - DVec2::new(1.0, 1.0).normalize()).length() < 1e-12);
+
+        let cubic = CubicSegment2D::new(DVec2::new(0.0, 0.0), DVec2::new(1.0, 1.0), DVec2::new(2.0, -1.0), DVec2::new(3.0, 0.0));
+        let hit = Ray2D::new(DVec2::new(-1.0, 0.0), DVec2::new(0.0, 0.0)).intersect_cubic(&cubic).unwrap();
+        assert!((hit.distance - 1.0).abs() < 1e-9);
+        let hit = Ray2D::new(DVec2::new(0.5, 0.0), DVec2::new(1.0, 0.0)).intersect_cubic(&cubic).unwrap();
+        assert!((hit.point - DVec2::new(1.5, 0.0)).length() < 1e-9);
+        assert!(Ray2D::new(DVec2::new(0.0, 2.0), DVec2::new(1.0, 2.0)).intersect_cubic(&cubic).is_none());
+    }
+}
@@ -9,7 +9,9 @@ use bytemuck::{Pod, Zeroable};
 
 use crate::{sdf::{RoundSignedDistance, SignedDistance}, vector::{DVec3, FVec3, Vector2, Vector3}};
 
-use super::{CalculateCentroid, Triangle2D, Triangle3D};
+use crate::algebra::Quaternion;
+
+use super::{CalculateCentroid, OrientedBox3D, OrientedRect, Triangle2D, Triangle3D};
 
 macro_rules! impl_ops_rect {
     ($structure:tt, $vector:tt) => {
@@ -160,7 +162,7 @@ impl<T: Number> Rect3D<T> {
     pub fn vector_adjust_bounds(&self, v: Vector3<T>) -> Self {
         let mut t = *self;
         t.min = t.min.min(v);
-        t.max = t.min.max(v);
+        t.max = t.max.max(v);
         t
     }
     /// Using a [`Triangle3D`], adjust the bounds of the [`Rect3D`] to fit at least the triangle.
@@ -242,6 +244,14 @@ impl<T: Real> CalculateCentroid for Rect3D<T> {
     }
 }
 
+impl<T: Real> Rect3D<T> {
+    /// The rect turned by `rotation` around its center. A turned rect is no
+    /// longer aligned with the axes, so it becomes an [`OrientedBox3D`].
+    pub fn rotated(&self, rotation: Quaternion<T>) -> OrientedBox3D<T> {
+        OrientedBox3D { rotation, ..OrientedBox3D::from_rect(self) }
+    }
+}
+
 impl<T: Number> From<Vector3<T>> for Rect3D<T> {
     fn from(value: Vector3<T>) -> Self {
         let min = Vector3::ZERO.min(value);
@@ -325,13 +335,13 @@ impl<T: Number> Rect<T> {
     pub fn merge(&self, aabb: &Self) -> Self {
         let mut t = *self;
         t.min = t.min.min(aabb.min);
-        t.max = t.min.min(aabb.max);
+        t.max = t.max.max(aabb.max);
         t
     }
     pub fn vector_adjust_bounds(&self, v: Vector2<T>) -> Self {
         let mut t = *self;
         t.min = t.min.min(v);
-        t.max = t.min.max(v);
+        t.max = t.max.max(v);
         t
     }
     pub fn triangle_adjust_bounds(&self, triangle: &Triangle2D<T>) -> Self {
@@ -425,6 +435,13 @@ impl<T: Real> CalculateCentroid for Rect<T> {
     }
 }
 
+impl<T: Real> Rect<T> {
+    /// The rect turned by `rotation` radians around its center. A turned rect
+    /// is no longer aligned with the axes, so it becomes an [`OrientedRect`].
+    pub fn rotated(&self, rotation: T) -> OrientedRect<T> {
+        OrientedRect::from_rect(self, rotation)
+    }
+}
 impl<T: Real> From<Triangle3D<T>> for Rect3D<T> {
     fn from(value: Triangle3D<T>) -> Self {
         
@@ -658,4 +675,23 @@ mod alloc_feature {
             ]
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::vector::{DVec2, DVec3};
+
+    use super::{Rect, Rect3D};
+
+    #[test]
+    fn growing_bounds() {
+        // the max corner used to be taken from the min corner
+        let rect = Rect::new(DVec2::new(0.0, 0.0), DVec2::new(2.0, 2.0));
+        let merged = rect.merge(&Rect::new(DVec2::new(1.0, -1.0), DVec2::new(3.0, 1.0)));
+        assert_eq!((merged.min, merged.max), (DVec2::new(0.0, -1.0), DVec2::new(3.0, 2.0)));
+        let grown = rect.vector_adjust_bounds(DVec2::new(1.0, 5.0));
+        assert_eq!((grown.min, grown.max), (DVec2::new(0.0, 0.0), DVec2::new(2.0, 5.0)));
+        let rect = Rect3D::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(2.0, 2.0, 2.0));
+        let grown = rect.vector_adjust_bounds(DVec3::new(1.0, -1.0, 1.0));
+        assert_eq!((grown.min, grown.max), (DVec3::new(0.0, -1.0, 0.0), DVec3::new(2.0, 2.0, 2.0)));
+    }
+}
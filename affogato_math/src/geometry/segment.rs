@@ -5,9 +5,9 @@ use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num
 #[cfg(feature="serde")]
 use serde::{Serialize, Deserialize};
 
-use crate::{geometry::CalculateCentroid, lerp, vector::Vector2};
+use crate::{algebra::solve_quadratic, geometry::CalculateCentroid, lerp, vector::Vector2};
 
-use super::Dimension;
+use super::{ArcSegment2D, Dimension, Rect};
 #[cfg(feature="alloc")]
 extern crate alloc;
 
@@ -126,6 +126,15 @@ impl<T: Number> LinearSegment2D<T> {
             Self::new(b, self[1])
         ]
     }
+    /// The derivative of the segment with respect to t, which is constant
+    /// for a line.
+    pub fn derivative(&self, _: f64) -> Vector2<T> {
+        self.end-self.start
+    }
+    /// The smallest axis aligned [`Rect`] containing the segment.
+    pub fn bounds(&self) -> Rect<T> {
+        Rect::new(self.start.min(self.end), self.start.max(self.end))
+    }
 }
 impl<T: Number> Segment for LinearSegment2D<T> {
     type VectorType = Vector2<T>;
@@ -222,12 +231,50 @@ impl<T: Number> QuadraticSegment2D<T> {
             part3,
         ]
     }
-    // pub fn intersection(&self, segment: LinearSegment2D<T>) -> bool {
-    //     let min = segment.start.min(segment.end);
-    //     let max = segment.start.max(segment.end);
-    //     let a = self.control *T::from_i32 (-2);
-    //     let c2 = self.start + a + self.end;
-    // }
+    /// The derivative of the segment with respect to t.
+    pub fn derivative(&self, t: f64) -> Vector2<T>
+        where T: Real {
+        lerp(self.control-self.start, self.end-self.control, T::from_f64(t))*T::from_f64(2.0)
+    }
+    /// Splits the segment at t into two segments using de casteljau's algorithm.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        let t = T::from_f64(t);
+        let a = lerp(self.start, self.control, t);
+        let b = lerp(self.control, self.end, t);
+        let middle = lerp(a, b, t);
+        (Self::new(self.start, a, middle), Self::new(middle, b, self.end))
+    }
+    /// The [`CubicSegment2D`] tracing the exact same curve.
+    pub fn to_cubic(&self) -> CubicSegment2D<T> {
+        let two_thirds = T::from_f64(2.0/3.0);
+        CubicSegment2D::new(
+            self.start,
+            lerp(self.start, self.control, two_thirds),
+            lerp(self.end, self.control, two_thirds),
+            self.end
+        )
+    }
+    /// The smallest axis aligned [`Rect`] containing the segment, found
+    /// by including the extremas of the curve along each axis.
+    pub fn bounds(&self) -> Rect<T>
+        where T: Real {
+        let mut min = self.start.min(self.end);
+        let mut max = self.start.max(self.end);
+        // the derivative of each axis is linear, so each axis has at most one extrema
+        let denominator = self.start-self.control*T::from_f64(2.0)+self.end;
+        for axis in 0..2 {
+            if denominator[axis] == T::ZERO {
+                continue;
+            }
+            let t = (self.start[axis]-self.control[axis])/denominator[axis];
+            if t > T::ZERO && t < T::ONE {
+                let point = self.get(t.to_f64());
+                min = min.min(point);
+                max = max.max(point);
+            }
+        }
+        Rect::new(min, max)
+    }
 }
 impl<T: Number> Segment for QuadraticSegment2D<T> {
     type VectorType = Vector2<T>;
@@ -250,7 +297,7 @@ impl<T: Number> Segment for QuadraticSegment2D<T> {
         where <Self::VectorType as VectorSpace>::Scalar: Real {
         let t = T::from_f64(t);
         let tangent = lerp(self.control-self.start, self.end-self.control, t);
-        if !tangent.is_zero() {
+        if tangent.is_zero() {
             return self.end-self.start;
         }
         tangent
@@ -364,6 +411,49 @@ impl<T: Number> CubicSegment2D<T> {
             part3,
         ]
     }
+    /// The derivative of the segment with respect to t.
+    pub fn derivative(&self, t: f64) -> Vector2<T>
+        where T: Real {
+        let t = T::from_f64(t);
+        let a = lerp(self.control1-self.start, self.control2-self.control1, t);
+        let b = lerp(self.control2-self.control1, self.end-self.control2, t);
+        lerp(a, b, t)*T::from_f64(3.0)
+    }
+    /// Splits the segment at t into two segments using de casteljau's algorithm.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        let t = T::from_f64(t);
+        let a = lerp(self.start, self.control1, t);
+        let b = lerp(self.control1, self.control2, t);
+        let c = lerp(self.control2, self.end, t);
+        let ab = lerp(a, b, t);
+        let bc = lerp(b, c, t);
+        let middle = lerp(ab, bc, t);
+        (Self::new(self.start, a, ab, middle), Self::new(middle, bc, c, self.end))
+    }
+    /// The smallest axis aligned [`Rect`] containing the segment, found
+    /// by including the extremas of the curve along each axis.
+    pub fn bounds(&self) -> Rect<T>
+        where T: Real {
+        let mut min = self.start.min(self.end);
+        let mut max = self.start.max(self.end);
+        // coefficients of the derivative divided by 3
+        let a = self.end-self.start+(self.control1-self.control2)*T::from_f64(3.0);
+        let b = (self.start-self.control1*T::from_f64(2.0)+self.control2)*T::from_f64(2.0);
+        let c = self.control1-self.start;
+        for axis in 0..2 {
+            let Some(roots) = solve_quadratic(a[axis], b[axis], c[axis]) else {
+                continue;
+            };
+            for t in roots {
+                if t > T::ZERO && t < T::ONE {
+                    let point = self.get(t.to_f64());
+                    min = min.min(point);
+                    max = max.max(point);
+                }
+            }
+        }
+        Rect::new(min, max)
+    }
 }
 
 impl<T: Number> Segment for CubicSegment2D<T> {
@@ -392,9 +482,11 @@ impl<T: Number> Segment for CubicSegment2D<T> {
         where <Self::VectorType as VectorSpace>::Scalar: Real {
         let t = T::from_f64(t);
         let tangent = lerp(lerp(self.control1-self.start, self.control2-self.control1, t), lerp(self.control2-self.control1, self.end-self.control2, t), t);
-        if !tangent.is_zero() {
-            if t == T::ZERO { return self.control2-self.start; }
-            if t == T::ONE { return self.end-self.control1; }
+        if tangent.is_zero() {
+            // a control point on top of an endpoint leaves the derivative zero there
+            let direction = if t == T::ZERO { self.control2-self.start } else if t == T::ONE { self.end-self.control1 } else { tangent };
+            if direction.is_zero() { return self.end-self.start; }
+            return direction;
         }
         tangent
     }
@@ -466,6 +558,7 @@ pub enum Segment2D<T: Number> {
     Linear(LinearSegment2D<T>),
     Quadratic(QuadraticSegment2D<T>),
     Cubic(CubicSegment2D<T>),
+    Arc(ArcSegment2D<T>),
 }
 impl<T: Debug + Number> Display for Segment2D<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -473,6 +566,7 @@ impl<T: Debug + Number> Display for Segment2D<T> {
             Segment2D::Linear(linear) => f.write_fmt(format_args!("{:?}", linear)),
             Segment2D::Quadratic(quadratic) => f.write_fmt(format_args!("{:?}", quadratic)),
             Segment2D::Cubic(cubic) => f.write_fmt(format_args!("{:?}", cubic)),
+            Segment2D::Arc(arc) => f.write_fmt(format_args!("{:?}", arc)),
         }
     }
 }
@@ -482,6 +576,7 @@ impl<T: Number> Segment2D<T> {
             Segment2D::Linear(linear) => linear,
             Segment2D::Quadratic(quadratic) => quadratic,
             Segment2D::Cubic(cubic) => cubic,
+            Segment2D::Arc(arc) => arc,
         }
     }
     pub fn get_mut(&mut self) -> &mut dyn Segment<VectorType = Vector2<T>> {
@@ -489,6 +584,7 @@ impl<T: Number> Segment2D<T> {
             Segment2D::Linear(linear) => linear,
             Segment2D::Quadratic(quadratic) => quadratic,
             Segment2D::Cubic(cubic) => cubic,
+            Segment2D::Arc(arc) => arc,
         }
     }
     pub fn linear(start: Vector2<T>, end: Vector2<T>) -> Self {
@@ -500,6 +596,9 @@ impl<T: Number> Segment2D<T> {
     pub fn cubic(start: Vector2<T>, control1: Vector2<T>, control2: Vector2<T>, end: Vector2<T>) -> Self {
         Segment2D::Cubic(CubicSegment2D::new(start, control1, control2, end))
     }
+    pub fn arc(center: Vector2<T>, radii: Vector2<T>, rotation: T, start_angle: T, sweep_angle: T) -> Self {
+        Segment2D::Arc(ArcSegment2D::new(center, radii, rotation, start_angle, sweep_angle))
+    }
     fn split_in_thirds_static(&self) -> [Self; 3] 
         where <Vector2<T> as VectorSpace>::Scalar: Real,
         Self: 'static {
@@ -528,6 +627,34 @@ impl<T: Number> Segment2D<T> {
                     Self::Cubic(cubic[2]),
                 ]
             },
+            Segment2D::Arc(arc) => {
+                let arc = arc.split_in_thirds_static();
+                [
+                    Self::Arc(arc[0]),
+                    Self::Arc(arc[1]),
+                    Self::Arc(arc[2]),
+                ]
+            },
+        }
+    }
+    /// The derivative of the segment with respect to t.
+    pub fn derivative(&self, t: f64) -> Vector2<T>
+        where T: Real {
+        match self {
+            Segment2D::Linear(linear) => linear.derivative(t),
+            Segment2D::Quadratic(quadratic) => quadratic.derivative(t),
+            Segment2D::Cubic(cubic) => cubic.derivative(t),
+            Segment2D::Arc(arc) => arc.derivative(t),
+        }
+    }
+    /// The smallest axis aligned [`Rect`] containing the segment.
+    pub fn bounds(&self) -> Rect<T>
+        where T: Real {
+        match self {
+            Segment2D::Linear(linear) => linear.bounds(),
+            Segment2D::Quadratic(quadratic) => quadratic.bounds(),
+            Segment2D::Cubic(cubic) => cubic.bounds(),
+            Segment2D::Arc(arc) => arc.bounds(),
         }
     }
 }
@@ -566,6 +693,7 @@ impl<T: Number> Segment for Segment2D<T> {
             Segment2D::Linear(linear) => linear.split_in_thirds(),
             Segment2D::Quadratic(quadratic) => quadratic.split_in_thirds(),
             Segment2D::Cubic(cubic) => cubic.split_in_thirds(),
+            Segment2D::Arc(arc) => arc.split_in_thirds(),
         }
     }
 }
@@ -577,6 +705,7 @@ impl<T: Number> CalculateCentroid for Segment2D<T> {
             Segment2D::Linear(linear) => linear.centroid(),
             Segment2D::Quadratic(quadratic) => quadratic.centroid(),
             Segment2D::Cubic(cubic) => cubic.centroid(),
+            Segment2D::Arc(arc) => arc.centroid(),
         }
     }
 }
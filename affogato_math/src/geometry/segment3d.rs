@@ -0,0 +1,720 @@
+use core::{fmt::{Debug, Display}, ops::{Deref, Div}};
+
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{Bounds, Number, Zero}, sets::Real};
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{algebra::solve_quadratic, geometry::CalculateCentroid, lerp, vector::Vector3};
+
+use super::{Dimension, Rect3D, Segment};
+#[cfg(feature="alloc")]
+extern crate alloc;
+
+/// The amount of evenly spaced intervals a curve is divided into before
+/// each is integrated or searched on its own.
+const CURVE_INTERVALS: usize = 16;
+/// The most Newton–Raphson steps taken when refining the closest point.
+const MAX_CLOSEST_POINT_ITERATIONS: usize = 8;
+/// The nodes and weights of 5 point Gauss–Legendre quadrature on [-1, 1].
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+
+/// A vector perpendicular to `direction`, preferring the part of `up` that
+/// is perpendicular to it.
+pub(crate) fn perpendicular<T: Real>(direction: Vector3<T>, up: Vector3<T>) -> Vector3<T> {
+    let projected = up - direction*up.dot(&direction);
+    if projected.length_squared() > T::from_f64(1e-12) {
+        return projected.normalize();
+    }
+    // use the axis that is least aligned with the direction
+    let axis = if direction.x().abs() <= direction.y().abs() && direction.x().abs() <= direction.z().abs() {
+        Vector3::right()
+    } else if direction.y().abs() <= direction.z().abs() {
+        Vector3::top()
+    } else {
+        Vector3::forward()
+    };
+    direction.cross(&axis).normalize()
+}
+
+/// An orthonormal frame at a point on a curve, used to orient geometry that
+/// follows the curve, such as the cross sections of a tube.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug)]
+pub struct Frame3D<T: Number> {
+    pub origin: Vector3<T>,
+    pub tangent: Vector3<T>,
+    pub normal: Vector3<T>,
+    pub binormal: Vector3<T>,
+}
+impl<T: Real> Frame3D<T> {
+    /// Creates the frame with the given tangent, and the normal closest to
+    /// `normal` that is perpendicular to it.
+    pub fn new(origin: Vector3<T>, tangent: Vector3<T>, normal: Vector3<T>) -> Self {
+        let tangent = tangent.normalize();
+        let normal = perpendicular(tangent, normal);
+        Self { origin, tangent, normal, binormal: tangent.cross(&normal) }
+    }
+}
+
+/// Implements the differential geometry shared by every 3D segment, using
+/// its `derivative`, `second_derivative` and [`Segment`] implementation.
+macro_rules! impl_curve3d {
+    ($segment:ident) => {
+        impl<T: Real> $segment<T> {
+            /// The unit tangent at t.
+            pub fn tangent(&self, t: f64) -> Vector3<T> {
+                let direction = self.direction_at(t);
+                if direction.is_zero() {
+                    return direction;
+                }
+                direction.normalize()
+            }
+            /// How sharply the curve bends at t, which is the reciprocal of
+            /// the radius of the circle that best fits the curve there.
+            pub fn curvature(&self, t: f64) -> T {
+                let derivative = self.derivative(t);
+                let speed = derivative.length();
+                if speed == T::ZERO {
+                    return T::ZERO;
+                }
+                derivative.cross(&self.second_derivative(t)).length()/(speed*speed*speed)
+            }
+            /// The Frenet–Serret frame at t, whose normal points toward the
+            /// center of curvature. Gives [`None`] where the curve is straight,
+            /// since the normal isn't defined there.
+            pub fn frenet_frame(&self, t: f64) -> Option<Frame3D<T>> {
+                let derivative = self.derivative(t);
+                let binormal = derivative.cross(&self.second_derivative(t));
+                if binormal.length_squared() <= T::from_f64(1e-12)*derivative.length_squared()*derivative.length_squared() {
+                    return None;
+                }
+                let tangent = derivative.normalize();
+                let binormal = binormal.normalize();
+                Some(Frame3D { origin: Segment::get(self, t), tangent, normal: binormal.cross(&tangent), binormal })
+            }
+            /// Frames at `count + 1` evenly spaced values of t that twist as
+            /// little as possible along the curve, found with the double
+            /// reflection method. Unlike [`Self::frenet_frame`] these never flip,
+            /// so they are what tubes and cables should be built from. The normal
+            /// of the first frame is the part of `up` perpendicular to the curve.
+            #[cfg(feature="alloc")]
+            pub fn rotation_minimizing_frames(&self, up: Vector3<T>, count: usize) -> alloc::vec::Vec<Frame3D<T>> {
+                let count = Ord::max(count, 1);
+                let mut frame = Frame3D::new(self.start(), self.tangent(0.0), up);
+                let mut frames = alloc::vec::Vec::with_capacity(count+1);
+                frames.push(frame);
+                let two = T::from_f64(2.0);
+                for i in 1..=count {
+                    let t = i as f64/count as f64;
+                    let (origin, tangent) = (Segment::get(self, t), self.tangent(t));
+                    // reflect the frame onto the next point, then reflect again
+                    // so the tangent lines up with the curve
+                    let v1 = origin - frame.origin;
+                    let c1 = v1.length_squared();
+                    let (normal, reflected_tangent) = if c1 == T::ZERO {
+                        (frame.normal, frame.tangent)
+                    } else {
+                        (frame.normal - v1*(two*v1.dot(&frame.normal)/c1), frame.tangent - v1*(two*v1.dot(&frame.tangent)/c1))
+                    };
+                    let v2 = tangent - reflected_tangent;
+                    let c2 = v2.length_squared();
+                    let normal = if c2 == T::ZERO { normal } else { normal - v2*(two*v2.dot(&normal)/c2) };
+                    frame = Frame3D::new(origin, tangent, normal);
+                    frames.push(frame);
+                }
+                frames
+            }
+            /// The length of the curve, integrated numerically.
+            pub fn arc_length(&self) -> T {
+                let width = 1.0/CURVE_INTERVALS as f64;
+                let mut length = T::ZERO;
+                for interval in 0..CURVE_INTERVALS {
+                    let center = (interval as f64 + 0.5)*width;
+                    for (node, weight) in GAUSS_LEGENDRE {
+                        length += self.derivative(center + node*width/2.0).length()*T::from_f64(weight*width/2.0);
+                    }
+                }
+                length
+            }
+            /// The value of t and the point on the curve that are closest to
+            /// `point`.
+            pub fn closest_point(&self, point: Vector3<T>) -> (f64, Vector3<T>) {
+                let distance = |t: f64| (Segment::get(self, t) - point).length_squared();
+                let mut closest = (0..=CURVE_INTERVALS)
+                    .map(|i| i as f64/CURVE_INTERVALS as f64)
+                    .map(|t| (t, distance(t)))
+                    .fold((0.0, distance(0.0)), |closest, sample| if sample.1 < closest.1 { sample } else { closest });
+                let mut t = closest.0;
+                for _ in 0..MAX_CLOSEST_POINT_ITERATIONS {
+                    let difference = Segment::get(self, t) - point;
+                    let derivative = self.derivative(t);
+                    let numerator = difference.dot(&derivative);
+                    let denominator = derivative.dot(&derivative) + difference.dot(&self.second_derivative(t));
+                    if denominator <= T::ZERO {
+                        break;
+                    }
+                    let next = (t - (numerator/denominator).to_f64()).clamp(0.0, 1.0);
+                    if (next - t).abs() < 1e-12 {
+                        break;
+                    }
+                    t = next;
+                }
+                let refined = distance(t);
+                if refined < closest.1 {
+                    closest = (t, refined);
+                }
+                (closest.0, Segment::get(self, closest.0))
+            }
+        }
+    };
+}
+
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy)]
+pub struct LinearSegment3D<T: Number> {
+    pub start: Vector3<T>,
+    pub end: Vector3<T>,
+}
+impl<T: Debug + Number> Debug for LinearSegment3D<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LinearSegment3D")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+impl<T: Number> LinearSegment3D<T> {
+    pub const fn new(start: Vector3<T>, end: Vector3<T>) -> Self {
+        Self { start, end }
+    }
+    pub fn length(&self) -> T
+        where T: Real {
+        (self.end-self.start).length()
+    }
+    /// The derivative of the segment with respect to t, which is constant
+    /// for a line.
+    pub fn derivative(&self, _: f64) -> Vector3<T> {
+        self.end-self.start
+    }
+    /// The second derivative of the segment with respect to t, which is
+    /// always zero for a line.
+    pub fn second_derivative(&self, _: f64) -> Vector3<T> {
+        Vector3::ZERO
+    }
+    /// Splits the segment at t into two segments.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        let t = T::from_f64(t);
+        let middle = lerp(self.start, self.end, t);
+        (Self::new(self.start, middle), Self::new(middle, self.end))
+    }
+    /// The smallest axis aligned [`Rect3D`] containing the segment.
+    pub fn bounds(&self) -> Rect3D<T> {
+        Rect3D::new(self.start.min(self.end), self.start.max(self.end))
+    }
+}
+impl<T: Number> Segment for LinearSegment3D<T> {
+    type VectorType = Vector3<T>;
+    fn start(&self) -> Vector3<T> {
+        self.start
+    }
+    fn end(&self) -> Vector3<T> {
+        self.end
+    }
+    fn get(&self, t: f64) -> Vector3<T>
+        where <Self::VectorType as VectorSpace>::Scalar: Real {
+        lerp(self.start, self.end, T::from_f64(t))
+    }
+    fn control_point(&self, idx: usize) -> Vector3<T> {
+        self[idx]
+    }
+    fn order(&self) -> usize { 2 }
+    fn direction_at(&self, _: f64) -> Vector3<T> {
+        self.end-self.start
+    }
+    fn adjust_end_point(&mut self, to: Vector3<T>) {
+        self.end = to;
+    }
+    fn adjust_start_point(&mut self, to: Vector3<T>) {
+        self.start = to;
+    }
+    #[cfg(feature="alloc")]
+    fn split_in_thirds(&self) -> [alloc::boxed::Box<dyn Segment<VectorType = Self::VectorType>>; 3]
+        where <Self::VectorType as VectorSpace>::Scalar: Real,
+        Self: 'static {
+        let (first, rest) = self.split(1.0/3.0);
+        let (second, third) = rest.split(0.5);
+        [alloc::boxed::Box::new(first), alloc::boxed::Box::new(second), alloc::boxed::Box::new(third)]
+    }
+}
+impl<T: Number> Deref for LinearSegment3D<T> {
+    type Target = [Vector3<T>; 2];
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::mem::transmute::<&Self, &[Vector3<T>; 2]>(self) }
+    }
+}
+impl<T: Number> CalculateCentroid for LinearSegment3D<T> {
+    type Vector = Vector3<T>;
+    fn centroid(&self) -> Self::Vector {
+        (self.start+self.end).div(T::from_u32(2))
+    }
+}
+impl<T: Number> core::ops::Add<Vector3<T>> for LinearSegment3D<T> {
+    type Output = Self;
+    fn add(self, rhs: Vector3<T>) -> Self::Output {
+        Self::new(self.start+rhs, self.end+rhs)
+    }
+}
+impl_curve3d!(LinearSegment3D);
+
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy)]
+pub struct QuadraticSegment3D<T: Number> {
+    pub start: Vector3<T>,
+    pub control: Vector3<T>,
+    pub end: Vector3<T>,
+}
+impl<T: Debug + Number> Debug for QuadraticSegment3D<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("QuadraticSegment3D")
+            .field("start", &self.start)
+            .field("control", &self.control)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+impl<T: Number> QuadraticSegment3D<T> {
+    pub fn new(start: Vector3<T>, control: Vector3<T>, end: Vector3<T>) -> Self {
+        Self { start, control, end }
+    }
+    /// The derivative of the segment with respect to t.
+    pub fn derivative(&self, t: f64) -> Vector3<T>
+        where T: Real {
+        lerp(self.control-self.start, self.end-self.control, T::from_f64(t))*T::from_f64(2.0)
+    }
+    /// The second derivative of the segment with respect to t, which is
+    /// constant for a quadratic.
+    pub fn second_derivative(&self, _: f64) -> Vector3<T>
+        where T: Real {
+        (self.start-self.control*T::from_f64(2.0)+self.end)*T::from_f64(2.0)
+    }
+    /// Splits the segment at t into two segments using de casteljau's algorithm.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        let t = T::from_f64(t);
+        let a = lerp(self.start, self.control, t);
+        let b = lerp(self.control, self.end, t);
+        let middle = lerp(a, b, t);
+        (Self::new(self.start, a, middle), Self::new(middle, b, self.end))
+    }
+    /// The [`CubicSegment3D`] tracing the exact same curve.
+    pub fn to_cubic(&self) -> CubicSegment3D<T> {
+        let two_thirds = T::from_f64(2.0/3.0);
+        CubicSegment3D::new(
+            self.start,
+            lerp(self.start, self.control, two_thirds),
+            lerp(self.end, self.control, two_thirds),
+            self.end
+        )
+    }
+    /// The smallest axis aligned [`Rect3D`] containing the segment, found
+    /// by including the extremas of the curve along each axis.
+    pub fn bounds(&self) -> Rect3D<T>
+        where T: Real {
+        let mut min = self.start.min(self.end);
+        let mut max = self.start.max(self.end);
+        // the derivative of each axis is linear, so each axis has at most one extrema
+        let denominator = self.start-self.control*T::from_f64(2.0)+self.end;
+        for axis in 0..3 {
+            if denominator[axis] == T::ZERO {
+                continue;
+            }
+            let t = (self.start[axis]-self.control[axis])/denominator[axis];
+            if t > T::ZERO && t < T::ONE {
+                let point = self.get(t.to_f64());
+                min = min.min(point);
+                max = max.max(point);
+            }
+        }
+        Rect3D::new(min, max)
+    }
+}
+impl<T: Number> Segment for QuadraticSegment3D<T> {
+    type VectorType = Vector3<T>;
+    fn start(&self) -> Vector3<T> {
+        self.start
+    }
+    fn end(&self) -> Vector3<T> {
+        self.end
+    }
+    fn get(&self, t: f64) -> Vector3<T>
+        where <Self::VectorType as VectorSpace>::Scalar: Real {
+        let t = T::from_f64(t);
+        lerp(lerp(self.start, self.control, t), lerp(self.control, self.end, t), t)
+    }
+    fn control_point(&self, idx: usize) -> Vector3<T> {
+        self[idx]
+    }
+    fn order(&self) -> usize { 3 }
+    fn direction_at(&self, t: f64) -> Vector3<T>
+        where <Self::VectorType as VectorSpace>::Scalar: Real {
+        let tangent = lerp(self.control-self.start, self.end-self.control, T::from_f64(t));
+        if tangent.is_zero() {
+            return self.end-self.start;
+        }
+        tangent
+    }
+    fn adjust_start_point(&mut self, to: Vector3<T>) {
+        self.control += to-self.start;
+        self.start = to;
+    }
+    fn adjust_end_point(&mut self, to: Vector3<T>) {
+        self.control += to-self.end;
+        self.end = to;
+    }
+    #[cfg(feature="alloc")]
+    fn split_in_thirds(&self) -> [alloc::boxed::Box<dyn Segment<VectorType = Self::VectorType>>; 3]
+        where <Self::VectorType as VectorSpace>::Scalar: Real,
+        Self: 'static {
+        let (first, rest) = self.split(1.0/3.0);
+        let (second, third) = rest.split(0.5);
+        [alloc::boxed::Box::new(first), alloc::boxed::Box::new(second), alloc::boxed::Box::new(third)]
+    }
+}
+impl<T: Number> Deref for QuadraticSegment3D<T> {
+    type Target = [Vector3<T>; 3];
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::mem::transmute::<&Self, &[Vector3<T>; 3]>(self) }
+    }
+}
+impl<T: Number> CalculateCentroid for QuadraticSegment3D<T> {
+    type Vector = Vector3<T>;
+    fn centroid(&self) -> Self::Vector {
+        (self.start+self.control+self.end).div(T::from_u32(3))
+    }
+}
+impl<T: Number> core::ops::Add<Vector3<T>> for QuadraticSegment3D<T> {
+    type Output = Self;
+    fn add(self, rhs: Vector3<T>) -> Self::Output {
+        Self::new(self.start+rhs, self.control+rhs, self.end+rhs)
+    }
+}
+impl_curve3d!(QuadraticSegment3D);
+
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy)]
+pub struct CubicSegment3D<T: Number> {
+    pub start: Vector3<T>,
+    pub control1: Vector3<T>,
+    pub control2: Vector3<T>,
+    pub end: Vector3<T>,
+}
+impl<T: Debug + Number> Debug for CubicSegment3D<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CubicSegment3D")
+            .field("start", &self.start)
+            .field("control1", &self.control1)
+            .field("control2", &self.control2)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+impl<T: Number> CubicSegment3D<T> {
+    pub fn new(start: Vector3<T>, control1: Vector3<T>, control2: Vector3<T>, end: Vector3<T>) -> Self {
+        Self { start, control1, control2, end }
+    }
+    /// The derivative of the segment with respect to t.
+    pub fn derivative(&self, t: f64) -> Vector3<T>
+        where T: Real {
+        let t = T::from_f64(t);
+        let a = lerp(self.control1-self.start, self.control2-self.control1, t);
+        let b = lerp(self.control2-self.control1, self.end-self.control2, t);
+        lerp(a, b, t)*T::from_f64(3.0)
+    }
+    /// The second derivative of the segment with respect to t.
+    pub fn second_derivative(&self, t: f64) -> Vector3<T>
+        where T: Real {
+        let a = self.start-self.control1*T::from_f64(2.0)+self.control2;
+        let b = self.control1-self.control2*T::from_f64(2.0)+self.end;
+        lerp(a, b, T::from_f64(t))*T::from_f64(6.0)
+    }
+    /// Splits the segment at t into two segments using de casteljau's algorithm.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        let t = T::from_f64(t);
+        let a = lerp(self.start, self.control1, t);
+        let b = lerp(self.control1, self.control2, t);
+        let c = lerp(self.control2, self.end, t);
+        let ab = lerp(a, b, t);
+        let bc = lerp(b, c, t);
+        let middle = lerp(ab, bc, t);
+        (Self::new(self.start, a, ab, middle), Self::new(middle, bc, c, self.end))
+    }
+    /// The smallest axis aligned [`Rect3D`] containing the segment, found
+    /// by including the extremas of the curve along each axis.
+    pub fn bounds(&self) -> Rect3D<T>
+        where T: Real {
+        let mut min = self.start.min(self.end);
+        let mut max = self.start.max(self.end);
+        // coefficients of the derivative divided by 3
+        let a = self.end-self.start+(self.control1-self.control2)*T::from_f64(3.0);
+        let b = (self.start-self.control1*T::from_f64(2.0)+self.control2)*T::from_f64(2.0);
+        let c = self.control1-self.start;
+        for axis in 0..3 {
+            let Some(roots) = solve_quadratic(a[axis], b[axis], c[axis]) else {
+                continue;
+            };
+            for t in roots {
+                if t > T::ZERO && t < T::ONE {
+                    let point = self.get(t.to_f64());
+                    min = min.min(point);
+                    max = max.max(point);
+                }
+            }
+        }
+        Rect3D::new(min, max)
+    }
+}
+impl<T: Number> Segment for CubicSegment3D<T> {
+    type VectorType = Vector3<T>;
+    fn start(&self) -> Vector3<T> {
+        self.start
+    }
+    fn end(&self) -> Vector3<T> {
+        self.end
+    }
+    fn get(&self, t: f64) -> Vector3<T>
+        where <Self::VectorType as VectorSpace>::Scalar: Real {
+        let t = T::from_f64(t);
+        let a = lerp(self.start, self.control1, t);
+        let b = lerp(self.control1, self.control2, t);
+        let c = lerp(self.control2, self.end, t);
+        lerp(lerp(a, b, t), lerp(b, c, t), t)
+    }
+    fn control_point(&self, idx: usize) -> Vector3<T> {
+        self[idx]
+    }
+    fn order(&self) -> usize { 4 }
+    fn direction_at(&self, t: f64) -> Vector3<T>
+        where <Self::VectorType as VectorSpace>::Scalar: Real {
+        let t = T::from_f64(t);
+        let tangent = lerp(lerp(self.control1-self.start, self.control2-self.control1, t), lerp(self.control2-self.control1, self.end-self.control2, t), t);
+        if tangent.is_zero() {
+            // a control point on top of an endpoint leaves the derivative zero there
+            let direction = if t == T::ZERO { self.control2-self.start } else if t == T::ONE { self.end-self.control1 } else { tangent };
+            if direction.is_zero() { return self.end-self.start; }
+            return direction;
+        }
+        tangent
+    }
+    fn adjust_start_point(&mut self, to: Vector3<T>) {
+        self.control1 += to-self.start;
+        self.start = to;
+    }
+    fn adjust_end_point(&mut self, to: Vector3<T>) {
+        self.control2 += to-self.end;
+        self.end = to;
+    }
+    #[cfg(feature="alloc")]
+    fn split_in_thirds(&self) -> [alloc::boxed::Box<dyn Segment<VectorType = Self::VectorType>>; 3]
+        where <Self::VectorType as VectorSpace>::Scalar: Real,
+        Self: 'static {
+        let (first, rest) = self.split(1.0/3.0);
+        let (second, third) = rest.split(0.5);
+        [alloc::boxed::Box::new(first), alloc::boxed::Box::new(second), alloc::boxed::Box::new(third)]
+    }
+}
+impl<T: Number> Deref for CubicSegment3D<T> {
+    type Target = [Vector3<T>; 4];
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::mem::transmute::<&Self, &[Vector3<T>; 4]>(self) }
+    }
+}
+impl<T: Number> CalculateCentroid for CubicSegment3D<T> {
+    type Vector = Vector3<T>;
+    fn centroid(&self) -> Self::Vector {
+        (self.start+self.control1+self.control2+self.end).div(T::from_u32(4))
+    }
+}
+impl<T: Number> core::ops::Add<Vector3<T>> for CubicSegment3D<T> {
+    type Output = Self;
+    fn add(self, rhs: Vector3<T>) -> Self::Output {
+        Self::new(self.start+rhs, self.control1+rhs, self.control2+rhs, self.end+rhs)
+    }
+}
+impl_curve3d!(CubicSegment3D);
+
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug)]
+pub enum Segment3D<T: Number> {
+    Linear(LinearSegment3D<T>),
+    Quadratic(QuadraticSegment3D<T>),
+    Cubic(CubicSegment3D<T>),
+}
+impl<T: Debug + Number> Display for Segment3D<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Segment3D::Linear(linear) => f.write_fmt(format_args!("{:?}", linear)),
+            Segment3D::Quadratic(quadratic) => f.write_fmt(format_args!("{:?}", quadratic)),
+            Segment3D::Cubic(cubic) => f.write_fmt(format_args!("{:?}", cubic)),
+        }
+    }
+}
+impl<T: Number> Segment3D<T> {
+    pub fn get(&self) -> &dyn Segment<VectorType = Vector3<T>> {
+        match self {
+            Segment3D::Linear(linear) => linear,
+            Segment3D::Quadratic(quadratic) => quadratic,
+            Segment3D::Cubic(cubic) => cubic,
+        }
+    }
+    pub fn get_mut(&mut self) -> &mut dyn Segment<VectorType = Vector3<T>> {
+        match self {
+            Segment3D::Linear(linear) => linear,
+            Segment3D::Quadratic(quadratic) => quadratic,
+            Segment3D::Cubic(cubic) => cubic,
+        }
+    }
+    pub fn linear(start: Vector3<T>, end: Vector3<T>) -> Self {
+        Segment3D::Linear(LinearSegment3D::new(start, end))
+    }
+    pub fn quadratic(start: Vector3<T>, control: Vector3<T>, end: Vector3<T>) -> Self {
+        Segment3D::Quadratic(QuadraticSegment3D::new(start, control, end))
+    }
+    pub fn cubic(start: Vector3<T>, control1: Vector3<T>, control2: Vector3<T>, end: Vector3<T>) -> Self {
+        Segment3D::Cubic(CubicSegment3D::new(start, control1, control2, end))
+    }
+    /// The derivative of the segment with respect to t.
+    pub fn derivative(&self, t: f64) -> Vector3<T>
+        where T: Real {
+        match self {
+            Segment3D::Linear(linear) => linear.derivative(t),
+            Segment3D::Quadratic(quadratic) => quadratic.derivative(t),
+            Segment3D::Cubic(cubic) => cubic.derivative(t),
+        }
+    }
+    /// The second derivative of the segment with respect to t.
+    pub fn second_derivative(&self, t: f64) -> Vector3<T>
+        where T: Real {
+        match self {
+            Segment3D::Linear(linear) => linear.second_derivative(t),
+            Segment3D::Quadratic(quadratic) => quadratic.second_derivative(t),
+            Segment3D::Cubic(cubic) => cubic.second_derivative(t),
+        }
+    }
+    /// Splits the segment at t into two segments.
+    pub fn split(&self, t: f64) -> (Self, Self) {
+        match self {
+            Segment3D::Linear(linear) => {
+                let (first, second) = linear.split(t);
+                (Segment3D::Linear(first), Segment3D::Linear(second))
+            },
+            Segment3D::Quadratic(quadratic) => {
+                let (first, second) = quadratic.split(t);
+                (Segment3D::Quadratic(first), Segment3D::Quadratic(second))
+            },
+            Segment3D::Cubic(cubic) => {
+                let (first, second) = cubic.split(t);
+                (Segment3D::Cubic(first), Segment3D::Cubic(second))
+            },
+        }
+    }
+    /// The smallest axis aligned [`Rect3D`] containing the segment.
+    pub fn bounds(&self) -> Rect3D<T>
+        where T: Real {
+        match self {
+            Segment3D::Linear(linear) => linear.bounds(),
+            Segment3D::Quadratic(quadratic) => quadratic.bounds(),
+            Segment3D::Cubic(cubic) => cubic.bounds(),
+        }
+    }
+}
+impl<T: Number> Segment for Segment3D<T> {
+    type VectorType = Vector3<T>;
+    fn start(&self) -> Self::VectorType {
+        self.get().start()
+    }
+    fn end(&self) -> Self::VectorType {
+        self.get().end()
+    }
+    fn get(&self, t: f64) -> Self::VectorType
+            where <Self::VectorType as VectorSpace>::Scalar: Real {
+        self.get().get(t)
+    }
+    fn control_point(&self, idx: usize) -> Vector3<T> {
+        self.get().control_point(idx)
+    }
+    fn order(&self) -> usize { self.get().order() }
+    fn direction_at(&self, t: f64) -> Self::VectorType
+        where <Self::VectorType as VectorSpace>::Scalar: Real {
+        self.get().direction_at(t)
+    }
+    fn adjust_start_point(&mut self, to: Self::VectorType) {
+        self.get_mut().adjust_start_point(to)
+    }
+    fn adjust_end_point(&mut self, to: Self::VectorType) {
+        self.get_mut().adjust_end_point(to)
+    }
+    #[cfg(feature="alloc")]
+    fn split_in_thirds(&self) -> [alloc::boxed::Box<dyn Segment<VectorType = Self::VectorType>>; 3]
+            where <Self::VectorType as VectorSpace>::Scalar: Real,
+            Self: 'static {
+        match self {
+            Segment3D::Linear(linear) => linear.split_in_thirds(),
+            Segment3D::Quadratic(quadratic) => quadratic.split_in_thirds(),
+            Segment3D::Cubic(cubic) => cubic.split_in_thirds(),
+        }
+    }
+}
+impl_curve3d!(Segment3D);
+
+impl<T: Number> CalculateCentroid for Segment3D<T> {
+    type Vector = Vector3<T>;
+    fn centroid(&self) -> Self::Vector {
+        match self {
+            Segment3D::Linear(linear) => linear.centroid(),
+            Segment3D::Quadratic(quadratic) => quadratic.centroid(),
+            Segment3D::Cubic(cubic) => cubic.centroid(),
+        }
+    }
+}
+
+impl<T: Number> Dimension for Segment3D<T> {
+    const DIMENSION: usize = 3;
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::groups::vector_spaces::{NormedVectorSpace, VectorSpace};
+
+    use crate::{geometry::{CubicSegment3D, QuadraticSegment3D, Segment, Segment3D}, vector::DVec3};
+
+    #[test]
+    fn curve_properties() {
+        // control points evenly spaced along a line trace the line at constant speed
+        let line = CubicSegment3D::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 1.0, 1.0), DVec3::new(2.0, 2.0, 2.0), DVec3::new(3.0, 3.0, 3.0));
+        assert!((line.arc_length() - 27.0f64.sqrt()).abs() < 1e-9);
+        assert!(line.frenet_frame(0.5).is_none());
+        let (t, point) = line.closest_point(DVec3::new(2.0, 0.0, 1.0));
+        assert!((t - 1.0/3.0).abs() < 1e-6 && (point - DVec3::new(1.0, 1.0, 1.0)).length() < 1e-6);
+
+        // a quadratic in the xy plane bends toward its control point
+        let bend = Segment3D::Quadratic(QuadraticSegment3D::new(DVec3::new(-1.0, 0.0, 0.0), DVec3::new(0.0, 2.0, 0.0), DVec3::new(1.0, 0.0, 0.0)));
+        let frame = bend.frenet_frame(0.5).unwrap();
+        assert!((frame.normal - DVec3::new(0.0, -1.0, 0.0)).length() < 1e-9);
+        assert!((bend.curvature(0.5) - 2.0).abs() < 1e-9);
+
+        let helix = Segment3D::cubic(DVec3::new(1.0, 0.0, 0.0), DVec3::new(1.0, 1.0, 0.5), DVec3::new(-1.0, 1.0, 1.0), DVec3::new(-1.0, 0.0, 1.5));
+        let frames = helix.rotation_minimizing_frames(DVec3::new(0.0, 0.0, 1.0), 32);
+        assert_eq!(frames.len(), 33);
+        assert_eq!(frames[32].origin, helix.end());
+        for frame in frames {
+            assert!(frame.tangent.dot(&frame.normal).abs() < 1e-9);
+            assert!((frame.normal.length() - 1.0).abs() < 1e-9);
+            assert!((frame.tangent.cross(&frame.normal) - frame.binormal).length() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,591 @@
+use core::{fmt::Debug, ops::Deref};
+
+use affogato_core::{groups::vector_spaces::VectorSpace, num::{Bounds, Number, Zero}, sets::Real};
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{algebra::{solve_cubic, solve_quadratic}, lerp, vector::Vector2};
+
+use super::{ArcSegment2D, CubicSegment2D, LinearSegment2D, QuadraticSegment2D, Segment2D};
+
+/// The maximum amount of intersections 2 cubic segments can have.
+pub const MAX_SEGMENT_INTERSECTIONS: usize = 9;
+const MAX_SUBDIVISION_DEPTH: usize = 24;
+const NEWTON_ITERATIONS: usize = 8;
+
+/// A single point where 2 segments cross.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SegmentIntersection<T: Number> {
+    /// The t value, between 0.0 and 1.0, on the segment the intersection was called on.
+    pub t0: T,
+    /// The t value, between 0.0 and 1.0, on the segment passed as an argument.
+    pub t1: T,
+    /// The point where both segments meet.
+    pub point: Vector2<T>,
+}
+
+impl<T: Number> SegmentIntersection<T> {
+    pub const fn new(t0: T, t1: T, point: Vector2<T>) -> Self {
+        Self { t0, t1, point }
+    }
+    /// Swaps the t values, used when the order of the segments is reversed.
+    pub const fn swapped(self) -> Self {
+        Self { t0: self.t1, t1: self.t0, point: self.point }
+    }
+}
+
+/// Contains every intersection found between 2 segments. 2 segments can intersect
+/// at most [`MAX_SEGMENT_INTERSECTIONS`] times unless they overlap, in which case
+/// only the first [`MAX_SEGMENT_INTERSECTIONS`] points found are kept.
+#[derive(Clone, Copy)]
+pub struct SegmentIntersections<T: Number> {
+    intersections: [SegmentIntersection<T>; MAX_SEGMENT_INTERSECTIONS],
+    total: usize,
+}
+
+impl<T: Debug + Number> Debug for SegmentIntersections<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SegmentIntersections")
+            .field("intersections", &self.as_slice())
+            .finish()
+    }
+}
+
+impl<T: Number> Default for SegmentIntersections<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Number> SegmentIntersections<T> {
+    pub const fn new() -> Self {
+        Self { intersections: [SegmentIntersection::new(T::ZERO, T::ZERO, Vector2::ZERO); MAX_SEGMENT_INTERSECTIONS], total: 0 }
+    }
+    pub fn as_slice(&self) -> &[SegmentIntersection<T>] {
+        &self.intersections[..self.total]
+    }
+    /// Adds an intersection, returns false if there is no more space left.
+    pub fn push(&mut self, intersection: SegmentIntersection<T>) -> bool {
+        if self.total == MAX_SEGMENT_INTERSECTIONS {
+            return false;
+        }
+        self.intersections[self.total] = intersection;
+        self.total += 1;
+        true
+    }
+    pub fn is_full(&self) -> bool {
+        self.total == MAX_SEGMENT_INTERSECTIONS
+    }
+    /// Swaps the t values of every intersection.
+    pub fn swapped(mut self) -> Self {
+        for intersection in self.intersections[..self.total].iter_mut() {
+            *intersection = intersection.swapped();
+        }
+        self
+    }
+    /// Sorts the intersections by their t value on the first segment.
+    pub fn sort(&mut self) {
+        self.intersections[..self.total].sort_unstable_by(|a, b| a.t0.partial_cmp(&b.t0).unwrap_or(core::cmp::Ordering::Equal));
+    }
+    fn push_unique(&mut self, intersection: SegmentIntersection<T>, epsilon: T) -> bool
+        where T: Real {
+        for other in self.as_slice() {
+            if (other.t0-intersection.t0).abs() <= epsilon && (other.t1-intersection.t1).abs() <= epsilon {
+                return true;
+            }
+        }
+        self.push(intersection)
+    }
+}
+
+impl<T: Number> Deref for SegmentIntersections<T> {
+    type Target = [SegmentIntersection<T>];
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+/// Finds every point where a segment crosses another segment.
+pub trait SegmentIntersect<Rhs> {
+    type Scalar: Number;
+    fn intersections(&self, other: &Rhs) -> SegmentIntersections<Self::Scalar>;
+}
+
+#[inline]
+fn parameter_epsilon<T: Real>() -> T {
+    T::EPSILON.sqrt()
+}
+
+fn in_unit_range<T: Real>(t: T) -> Option<T> {
+    let epsilon = parameter_epsilon::<T>();
+    if t < -epsilon || t > T::ONE+epsilon {
+        None
+    } else {
+        Some(t.max(T::ZERO).min(T::ONE))
+    }
+}
+
+fn line_parameter<T: Real>(line: &LinearSegment2D<T>, point: Vector2<T>) -> T {
+    let direction = line.end-line.start;
+    (point-line.start).dot(&direction)/direction.length_squared()
+}
+
+fn line_line<T: Real>(a: &LinearSegment2D<T>, b: &LinearSegment2D<T>) -> SegmentIntersections<T> {
+    let mut intersections = SegmentIntersections::new();
+    let da = a.end-a.start;
+    let db = b.end-b.start;
+    let offset = b.start-a.start;
+    let denominator = da.cross(&db);
+    let scale = da.length_squared().max(db.length_squared());
+    if scale == T::ZERO {
+        return intersections;
+    }
+    if denominator.abs() <= T::EPSILON*scale {
+        // Parallel, only collinear overlapping segments intersect.
+        if offset.cross(&da).abs() > T::EPSILON*scale || da.is_zero() {
+            return intersections;
+        }
+        let length = da.length_squared();
+        let s0 = offset.dot(&da)/length;
+        let s1 = (b.end-a.start).dot(&da)/length;
+        let start = s0.min(s1).max(T::ZERO);
+        let end = s0.max(s1).min(T::ONE);
+        if start > end {
+            return intersections;
+        }
+        for t0 in [start, end] {
+            let point = a.start+da*t0;
+            let t1 = line_parameter(b, point).max(T::ZERO).min(T::ONE);
+            intersections.push_unique(SegmentIntersection::new(t0, t1, point), parameter_epsilon());
+        }
+        return intersections;
+    }
+    let t0 = offset.cross(&db)/denominator;
+    let t1 = offset.cross(&da)/denominator;
+    if let (Some(t0), Some(t1)) = (in_unit_range(t0), in_unit_range(t1)) {
+        intersections.push(SegmentIntersection::new(t0, t1, a.start+da*t0));
+    }
+    intersections
+}
+
+/// Intersects a line with a bezier curve by projecting the control points onto the
+/// normal of the line, the roots of the resulting polynomial are the t values of the curve.
+fn line_curve<T: Real, R: Iterator<Item = T>>(line: &LinearSegment2D<T>, roots: Option<R>, evaluate: impl Fn(T) -> Vector2<T>) -> SegmentIntersections<T> {
+    let mut intersections = SegmentIntersections::new();
+    let Some(roots) = roots else {
+        return intersections;
+    };
+    for root in roots {
+        let Some(t1) = in_unit_range(root) else {
+            continue;
+        };
+        let point = evaluate(t1);
+        let Some(t0) = in_unit_range(line_parameter(line, point)) else {
+            continue;
+        };
+        intersections.push_unique(SegmentIntersection::new(t0, t1, point), parameter_epsilon());
+    }
+    intersections.sort();
+    intersections
+}
+
+fn line_normal_distances<T: Real, const N: usize>(line: &LinearSegment2D<T>, points: &[Vector2<T>; N]) -> Option<[T; N]> {
+    let normal = (line.end-line.start).rotate_90();
+    if normal.is_zero() {
+        return None;
+    }
+    Some(points.map(|point| (point-line.start).dot(&normal)))
+}
+
+/// Whether every control point is on the line, where the distances are all
+/// zero and their roots can't say where the curve is.
+fn is_on_line<T: Real, const N: usize>(line: &LinearSegment2D<T>, points: &[Vector2<T>; N], distances: &[T; N]) -> bool {
+    let scale = points.iter().fold((line.end-line.start).length_squared(), |scale, point| scale.max((*point-line.start).length_squared()));
+    distances.iter().all(|distance| distance.abs() <= T::EPSILON*scale)
+}
+
+/// Intersects a line with a curve lying on it, giving where their overlap
+/// starts and ends like collinear lines do: the ends of the line that are on
+/// the curve and the ends of the curve that are on the line. `solve` finds
+/// the t values where the curve reaches each end of the line.
+fn line_curve_overlap<T: Real, const N: usize, R: Iterator<Item = T>>(
+    line: &LinearSegment2D<T>,
+    points: &[Vector2<T>; N],
+    solve: impl Fn([T; N]) -> Option<R>,
+    evaluate: impl Fn(T) -> Vector2<T>,
+) -> SegmentIntersections<T> {
+    let mut intersections = SegmentIntersections::new();
+    let along = points.map(|point| line_parameter(line, point));
+    for t0 in [T::ZERO, T::ONE] {
+        for root in solve(along.map(|along| along - t0)).into_iter().flatten() {
+            if let Some(t1) = in_unit_range(root) {
+                intersections.push_unique(SegmentIntersection::new(t0, t1, evaluate(t1)), parameter_epsilon());
+            }
+        }
+    }
+    for (t1, point) in [(T::ZERO, points[0]), (T::ONE, points[N-1])] {
+        if let Some(t0) = in_unit_range(line_parameter(line, point)) {
+            intersections.push_unique(SegmentIntersection::new(t0, t1, point), parameter_epsilon());
+        }
+    }
+    intersections.sort();
+    intersections
+}
+
+fn quadratic_roots<T: Real>([d0, d1, d2]: [T; 3]) -> Option<impl Iterator<Item = T>> {
+    solve_quadratic(d0 - d1*T::from_f64(2.0) + d2, (d1 - d0)*T::from_f64(2.0), d0)
+}
+
+fn cubic_roots<T: Real>([d0, d1, d2, d3]: [T; 4]) -> Option<impl Iterator<Item = T>> {
+    let three = T::from_f64(3.0);
+    let a = -d0 + d1*three - d2*three + d3;
+    let b = d0*three - d1*T::from_f64(6.0) + d2*three;
+    let c = (d1 - d0)*three;
+    solve_cubic(a, b, c, d0)
+}
+
+fn line_quadratic<T: Real>(line: &LinearSegment2D<T>, curve: &QuadraticSegment2D<T>) -> SegmentIntersections<T> {
+    let Some(distances) = line_normal_distances(line, curve) else {
+        return SegmentIntersections::new();
+    };
+    if is_on_line(line, curve, &distances) {
+        return line_curve_overlap(line, curve, quadratic_roots, |t| quadratic_point(curve, t));
+    }
+    line_curve(line, quadratic_roots(distances), |t| quadratic_point(curve, t))
+}
+
+fn line_cubic<T: Real>(line: &LinearSegment2D<T>, curve: &CubicSegment2D<T>) -> SegmentIntersections<T> {
+    let Some(distances) = line_normal_distances(line, curve) else {
+        return SegmentIntersections::new();
+    };
+    if is_on_line(line, curve, &distances) {
+        return line_curve_overlap(line, curve, cubic_roots, |t| cubic_point(curve, t));
+    }
+    line_curve(line, cubic_roots(distances), |t| cubic_point(curve, t))
+}
+
+fn quadratic_point<T: Real>(curve: &QuadraticSegment2D<T>, t: T) -> Vector2<T> {
+    lerp(lerp(curve.start, curve.control, t), lerp(curve.control, curve.end, t), t)
+}
+
+fn cubic_point<T: Real>(curve: &[Vector2<T>; 4], t: T) -> Vector2<T> {
+    let a = lerp(curve[0], curve[1], t);
+    let b = lerp(curve[1], curve[2], t);
+    let c = lerp(curve[2], curve[3], t);
+    lerp(lerp(a, b, t), lerp(b, c, t), t)
+}
+
+fn cubic_derivative<T: Real>(curve: &[Vector2<T>; 4], t: T) -> Vector2<T> {
+    let a = curve[1]-curve[0];
+    let b = curve[2]-curve[1];
+    let c = curve[3]-curve[2];
+    lerp(lerp(a, b, t), lerp(b, c, t), t)*T::from_f64(3.0)
+}
+
+fn split_cubic<T: Real>(curve: &[Vector2<T>; 4], t: T) -> ([Vector2<T>; 4], [Vector2<T>; 4]) {
+    let a = lerp(curve[0], curve[1], t);
+    let b = lerp(curve[1], curve[2], t);
+    let c = lerp(curve[2], curve[3], t);
+    let d = lerp(a, b, t);
+    let e = lerp(b, c, t);
+    let f = lerp(d, e, t);
+    ([curve[0], a, d, f], [f, e, c, curve[3]])
+}
+
+fn hull_bounds<T: Real>(curve: &[Vector2<T>; 4]) -> (Vector2<T>, Vector2<T>) {
+    let mut min = curve[0];
+    let mut max = curve[0];
+    for point in &curve[1..] {
+        min = min.min(*point);
+        max = max.max(*point);
+    }
+    (min, max)
+}
+
+/// Returns whether the control points are close enough to the chord of the curve
+/// that the curve can be treated as a line.
+fn is_flat<T: Real>(curve: &[Vector2<T>; 4], tolerance: T) -> bool {
+    let u = curve[1]*T::from_f64(3.0) - curve[0]*T::from_f64(2.0) - curve[3];
+    let v = curve[2]*T::from_f64(3.0) - curve[0] - curve[3]*T::from_f64(2.0);
+    let u = u*u;
+    let v = v*v;
+    u.x().max(v.x()) + u.y().max(v.y()) <= tolerance*tolerance*T::from_f64(16.0)
+}
+
+/// Polishes a pair of t values with newton's method on `a(t0) - b(t1) = 0`.
+fn refine<T: Real>(a: &[Vector2<T>; 4], b: &[Vector2<T>; 4], mut t0: T, mut t1: T) -> (T, T) {
+    for _ in 0..NEWTON_ITERATIONS {
+        let f = cubic_point(a, t0) - cubic_point(b, t1);
+        if f.is_zero() {
+            break;
+        }
+        let da = cubic_derivative(a, t0);
+        let db = cubic_derivative(b, t1);
+        let determinant = db.cross(&da);
+        if determinant.abs() <= T::EPSILON {
+            break;
+        }
+        t0 = (t0 + f.cross(&db)/determinant).max(T::ZERO).min(T::ONE);
+        t1 = (t1 + f.cross(&da)/determinant).max(T::ZERO).min(T::ONE);
+    }
+    (t0, t1)
+}
+
+struct CurveCurve<'a, T: Real> {
+    a: &'a [Vector2<T>; 4],
+    b: &'a [Vector2<T>; 4],
+    flatness: T,
+    tolerance: T,
+    intersections: SegmentIntersections<T>,
+}
+
+impl<'a, T: Real> CurveCurve<'a, T> {
+    fn accept(&mut self, t0: T, t1: T) {
+        let (t0, t1) = refine(self.a, self.b, t0, t1);
+        let point = cubic_point(self.a, t0);
+        if (point - cubic_point(self.b, t1)).length_squared() <= self.tolerance*self.tolerance {
+            self.intersections.push_unique(SegmentIntersection::new(t0, t1, point), parameter_epsilon::<T>()*T::from_f64(16.0));
+        }
+    }
+    fn subdivide(&mut self, a: [Vector2<T>; 4], a_range: (T, T), b: [Vector2<T>; 4], b_range: (T, T), depth: usize) {
+        if self.intersections.is_full() {
+            return;
+        }
+        let (a_min, a_max) = hull_bounds(&a);
+        let (b_min, b_max) = hull_bounds(&b);
+        if a_min.x() > b_max.x() + self.tolerance || b_min.x() > a_max.x() + self.tolerance ||
+            a_min.y() > b_max.y() + self.tolerance || b_min.y() > a_max.y() + self.tolerance {
+            return;
+        }
+        let half = T::from_f64(0.5);
+        if depth >= MAX_SUBDIVISION_DEPTH || (is_flat(&a, self.flatness) && is_flat(&b, self.flatness)) {
+            let chord_a = LinearSegment2D::new(a[0], a[3]);
+            let chord_b = LinearSegment2D::new(b[0], b[3]);
+            let chords = line_line(&chord_a, &chord_b);
+            if chords.is_empty() {
+                let a_size = a_max-a_min;
+                let b_size = b_max-b_min;
+                // Tangent curves might not have crossing chords, so accept tiny overlapping hulls.
+                if a_size.x().max(a_size.y()).max(b_size.x().max(b_size.y())) <= self.tolerance {
+                    self.accept(lerp(a_range.0, a_range.1, half), lerp(b_range.0, b_range.1, half));
+                }
+            }
+            for chord in chords.iter() {
+                self.accept(lerp(a_range.0, a_range.1, chord.t0), lerp(b_range.0, b_range.1, chord.t1));
+            }
+            return;
+        }
+        let a_mid = lerp(a_range.0, a_range.1, half);
+        let b_mid = lerp(b_range.0, b_range.1, half);
+        let (a0, a1) = split_cubic(&a, half);
+        let (b0, b1) = split_cubic(&b, half);
+        self.subdivide(a0, (a_range.0, a_mid), b0, (b_range.0, b_mid), depth+1);
+        self.subdivide(a0, (a_range.0, a_mid), b1, (b_mid, b_range.1), depth+1);
+        self.subdivide(a1, (a_mid, a_range.1), b0, (b_range.0, b_mid), depth+1);
+        self.subdivide(a1, (a_mid, a_range.1), b1, (b_mid, b_range.1), depth+1);
+    }
+}
+
+/// Intersects 2 cubic bezier curves by recursively subdividing both curves until
+/// the pieces whose bounds overlap are flat, the chords of those pieces are intersected
+/// and the result is polished using newton's method.
+fn curve_curve<T: Real>(a: &[Vector2<T>; 4], b: &[Vector2<T>; 4]) -> SegmentIntersections<T> {
+    let (a_min, a_max) = hull_bounds(a);
+    let (b_min, b_max) = hull_bounds(b);
+    let size = a_max.max(b_max) - a_min.min(b_min);
+    let scale = size.x().max(size.y());
+    let mut state = CurveCurve {
+        a,
+        b,
+        flatness: scale*T::from_f64(1e-3),
+        tolerance: scale*parameter_epsilon::<T>(),
+        intersections: SegmentIntersections::new(),
+    };
+    state.subdivide(*a, (T::ZERO, T::ONE), *b, (T::ZERO, T::ONE), 0);
+    state.intersections.sort();
+    state.intersections
+}
+
+fn elevate_quadratic<T: Real>(curve: &QuadraticSegment2D<T>) -> [Vector2<T>; 4] {
+    let two_thirds = T::from_f64(2.0/3.0);
+    [
+        curve.start,
+        lerp(curve.start, curve.control, two_thirds),
+        lerp(curve.end, curve.control, two_thirds),
+        curve.end,
+    ]
+}
+
+impl<T: Real> SegmentIntersect<LinearSegment2D<T>> for LinearSegment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &LinearSegment2D<T>) -> SegmentIntersections<T> {
+        line_line(self, other)
+    }
+}
+impl<T: Real> SegmentIntersect<QuadraticSegment2D<T>> for LinearSegment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &QuadraticSegment2D<T>) -> SegmentIntersections<T> {
+        line_quadratic(self, other)
+    }
+}
+impl<T: Real> SegmentIntersect<CubicSegment2D<T>> for LinearSegment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &CubicSegment2D<T>) -> SegmentIntersections<T> {
+        line_cubic(self, other)
+    }
+}
+impl<T: Real> SegmentIntersect<LinearSegment2D<T>> for QuadraticSegment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &LinearSegment2D<T>) -> SegmentIntersections<T> {
+        line_quadratic(other, self).swapped()
+    }
+}
+impl<T: Real> SegmentIntersect<QuadraticSegment2D<T>> for QuadraticSegment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &QuadraticSegment2D<T>) -> SegmentIntersections<T> {
+        curve_curve(&elevate_quadratic(self), &elevate_quadratic(other))
+    }
+}
+impl<T: Real> SegmentIntersect<CubicSegment2D<T>> for QuadraticSegment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &CubicSegment2D<T>) -> SegmentIntersections<T> {
+        curve_curve(&elevate_quadratic(self), other)
+    }
+}
+impl<T: Real> SegmentIntersect<LinearSegment2D<T>> for CubicSegment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &LinearSegment2D<T>) -> SegmentIntersections<T> {
+        line_cubic(other, self).swapped()
+    }
+}
+impl<T: Real> SegmentIntersect<QuadraticSegment2D<T>> for CubicSegment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &QuadraticSegment2D<T>) -> SegmentIntersections<T> {
+        curve_curve(self, &elevate_quadratic(other))
+    }
+}
+impl<T: Real> SegmentIntersect<CubicSegment2D<T>> for CubicSegment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &CubicSegment2D<T>) -> SegmentIntersections<T> {
+        curve_curve(self, other)
+    }
+}
+impl<T: Real> SegmentIntersect<Segment2D<T>> for ArcSegment2D<T> {
+    type Scalar = T;
+    /// Arcs are intersected through their cubic approximation, the t value
+    /// on the arc is recovered from the angle of each intersection.
+    fn intersections(&self, other: &Segment2D<T>) -> SegmentIntersections<T> {
+        let radius = self.radii.x().abs().max(self.radii.y().abs());
+        let count = self.cubic_count(radius*parameter_epsilon::<T>());
+        let mut intersections = SegmentIntersections::new();
+        for index in 0..count {
+            let piece = Segment2D::Cubic(self.cubic_piece(index, count));
+            for hit in piece.intersections(other).iter() {
+                let intersection = SegmentIntersection::new(self.parameter_of(hit.point), hit.t1, hit.point);
+                if !intersections.push_unique(intersection, parameter_epsilon()) {
+                    return intersections;
+                }
+            }
+        }
+        intersections
+    }
+}
+impl<T: Real> SegmentIntersect<Segment2D<T>> for Segment2D<T> {
+    type Scalar = T;
+    fn intersections(&self, other: &Segment2D<T>) -> SegmentIntersections<T> {
+        match (self, other) {
+            (Segment2D::Arc(a), b) => a.intersections(b),
+            (a, Segment2D::Arc(b)) => b.intersections(a).swapped(),
+            (Segment2D::Linear(a), Segment2D::Linear(b)) => a.intersections(b),
+            (Segment2D::Linear(a), Segment2D::Quadratic(b)) => a.intersections(b),
+            (Segment2D::Linear(a), Segment2D::Cubic(b)) => a.intersections(b),
+            (Segment2D::Quadratic(a), Segment2D::Linear(b)) => a.intersections(b),
+            (Segment2D::Quadratic(a), Segment2D::Quadratic(b)) => a.intersections(b),
+            (Segment2D::Quadratic(a), Segment2D::Cubic(b)) => a.intersections(b),
+            (Segment2D::Cubic(a), Segment2D::Linear(b)) => a.intersections(b),
+            (Segment2D::Cubic(a), Segment2D::Quadratic(b)) => a.intersections(b),
+            (Segment2D::Cubic(a), Segment2D::Cubic(b)) => a.intersections(b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::{CubicSegment2D, LinearSegment2D, QuadraticSegment2D, Segment, Segment2D, SegmentIntersect}, vector::DVec2};
+
+    #[test]
+    fn line_intersections() {
+        let a = LinearSegment2D::new(DVec2::new(0.0, 0.0), DVec2::new(2.0, 2.0));
+        let b = LinearSegment2D::new(DVec2::new(0.0, 2.0), DVec2::new(2.0, 0.0));
+        let hits = a.intersections(&b);
+        assert!(hits.len() == 1, "expected a single intersection, got {:?}", hits);
+        assert!(hits[0].point.epsilon_eq(DVec2::new(1.0, 1.0), 1e-9));
+        assert!((hits[0].t0-0.5).abs() < 1e-9 && (hits[0].t1-0.5).abs() < 1e-9);
+
+        let c = LinearSegment2D::new(DVec2::new(3.0, 0.0), DVec2::new(3.0, 2.0));
+        assert!(a.intersections(&c).is_empty());
+    }
+    #[test]
+    fn line_curve_intersections() {
+        let line = LinearSegment2D::new(DVec2::new(0.0, 0.5), DVec2::new(4.0, 0.5));
+        let quadratic = QuadraticSegment2D::new(DVec2::new(0.0, 0.0), DVec2::new(2.0, 2.0), DVec2::new(4.0, 0.0));
+        let hits = line.intersections(&quadratic);
+        assert!(hits.len() == 2, "expected 2 intersections, got {:?}", hits);
+        for hit in hits.iter() {
+            assert!((hit.point.y()-0.5).abs() < 1e-9);
+            assert!(Segment::get(&quadratic, hit.t1).epsilon_eq(hit.point, 1e-9));
+        }
+        let cubic = CubicSegment2D::new(DVec2::new(0.0, 0.0), DVec2::new(1.0, 2.0), DVec2::new(3.0, -2.0), DVec2::new(4.0, 0.0));
+        let line = LinearSegment2D::new(DVec2::new(-1.0, 0.0), DVec2::new(5.0, 0.0));
+        let hits = cubic.intersections(&line);
+        assert!(hits.len() == 3, "expected 3 intersections, got {:?}", hits);
+        for hit in hits.iter() {
+            assert!(cubic.get(hit.t0).epsilon_eq(hit.point, 1e-9));
+            assert!(line.get(hit.t1).epsilon_eq(hit.point, 1e-9));
+        }
+
+        // a curve lying on the line overlaps it from where the ends of one
+        // are on the other
+        let flat = QuadraticSegment2D::new(DVec2::new(1.0, 0.0), DVec2::new(3.0, 0.0), DVec2::new(6.0, 0.0));
+        let line = LinearSegment2D::new(DVec2::new(0.0, 0.0), DVec2::new(4.0, 0.0));
+        let hits = line.intersections(&flat);
+        assert!(hits.len() == 2, "expected 2 intersections, got {:?}", hits);
+        assert!(hits[0].point.epsilon_eq(DVec2::new(1.0, 0.0), 1e-9) && hits[1].point.epsilon_eq(DVec2::new(4.0, 0.0), 1e-9));
+        for hit in hits.iter() {
+            assert!(line.get(hit.t0).epsilon_eq(hit.point, 1e-9) && Segment::get(&flat, hit.t1).epsilon_eq(hit.point, 1e-9));
+        }
+        let flat = CubicSegment2D::new(DVec2::new(1.0, 1.0), DVec2::new(2.0, 2.0), DVec2::new(2.5, 2.5), DVec2::new(3.0, 3.0));
+        let hits = flat.intersections(&LinearSegment2D::new(DVec2::new(0.0, 0.0), DVec2::new(4.0, 4.0)));
+        assert!(hits.len() == 2 && hits[0].t0 == 0.0 && hits[1].t0 == 1.0, "expected the ends of the cubic, got {:?}", hits);
+    }
+    #[test]
+    fn curve_curve_intersections() {
+        let a = Segment2D::cubic(DVec2::new(0.0, 0.0), DVec2::new(1.0, 3.0), DVec2::new(2.0, -3.0), DVec2::new(3.0, 0.0));
+        let b = Segment2D::cubic(DVec2::new(0.0, 0.5), DVec2::new(1.0, -2.0), DVec2::new(2.0, 2.0), DVec2::new(3.0, -0.5));
+        let hits = a.intersections(&b);
+        assert!(hits.len() == 3, "expected 3 intersections, got {:?}", hits);
+        for hit in hits.iter() {
+            assert!(Segment::get(&a, hit.t0).epsilon_eq(hit.point, 1e-6));
+            assert!(Segment::get(&b, hit.t1).epsilon_eq(hit.point, 1e-6));
+        }
+        let quadratic = Segment2D::quadratic(DVec2::new(0.0, -1.0), DVec2::new(1.5, 4.0), DVec2::new(3.0, -1.0));
+        let hits = quadratic.intersections(&a);
+        assert!(!hits.is_empty());
+        for hit in hits.iter() {
+            assert!(Segment::get(&quadratic, hit.t0).epsilon_eq(hit.point, 1e-6));
+            assert!(Segment::get(&a, hit.t1).epsilon_eq(hit.point, 1e-6));
+        }
+        // a quarter of a unit circle against a line and another arc
+        let arc = Segment2D::arc(DVec2::new(0.0, 0.0), DVec2::new(1.0, 1.0), 0.0, 0.0, core::f64::consts::FRAC_PI_2);
+        let line = Segment2D::linear(DVec2::new(0.0, 0.0), DVec2::new(2.0, 2.0));
+        let hits = line.intersections(&arc);
+        assert!(hits.len() == 1 && (hits[0].t1 - 0.5).abs() < 1e-6);
+        assert!(hits[0].point.epsilon_eq(DVec2::new(0.5f64.sqrt(), 0.5f64.sqrt()), 1e-6));
+        let other = Segment2D::arc(DVec2::new(1.0, 0.0), DVec2::new(1.0, 1.0), 0.0, core::f64::consts::FRAC_PI_2, core::f64::consts::FRAC_PI_2);
+        let hits = arc.intersections(&other);
+        assert!(hits.len() == 1, "expected 1 intersection, got {:?}", hits);
+        assert!(hits[0].point.epsilon_eq(DVec2::new(0.5, 0.75f64.sqrt()), 1e-6));
+        assert!(Segment::get(&other, hits[0].t1).epsilon_eq(hits[0].point, 1e-6));
+    }
+}
@@ -0,0 +1,405 @@
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{Bounds, Number, Zero}, sets::Real};
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{sdf::SignedDistance, vector::Vector2};
+
+use super::{CalculateCentroid, Rect};
+
+/// `vector` turned counterclockwise by `angle`.
+fn rotate<T: Real>(vector: Vector2<T>, angle: T) -> Vector2<T> {
+    let (sin, cos) = angle.sin_cos();
+    Vector2::new(vector.x()*cos - vector.y()*sin, vector.x()*sin + vector.y()*cos)
+}
+
+/// The rect reaching `half_size` out from `center` in both directions.
+fn centered_rect<T: Real>(center: Vector2<T>, half_size: Vector2<T>) -> Rect<T> {
+    Rect::new(center - half_size, center + half_size)
+}
+
+/// How far the turned box with `half_extents` reaches along x and y.
+fn turned_half_size<T: Real>(half_extents: Vector2<T>, rotation: T) -> Vector2<T> {
+    let (sin, cos) = rotation.sin_cos();
+    let (sin, cos) = (sin.abs(), cos.abs());
+    Vector2::new(
+        cos*half_extents.x() + sin*half_extents.y(),
+        sin*half_extents.x() + cos*half_extents.y(),
+    )
+}
+
+/// The signed distance to a box with `half_extents` centered on the origin,
+/// with its corners rounded off by `radius`.
+fn round_box_sdf<T: Real>(point: Vector2<T>, half_extents: Vector2<T>, radius: T) -> T {
+    let q = point.abs() - half_extents + Vector2::from(radius);
+    q.max(Vector2::ZERO).length() + q.x().max(q.y()).min(T::ZERO) - radius
+}
+
+/// The ellipse with `radii` along its own axes, turned counterclockwise by
+/// `rotation` radians around `center`.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipse<T: Number> {
+    pub center: Vector2<T>,
+    pub radii: Vector2<T>,
+    pub rotation: T,
+}
+
+impl<T: Number> Ellipse<T> {
+    pub fn new(center: Vector2<T>, radii: Vector2<T>, rotation: T) -> Self {
+        Self { center, radii, rotation }
+    }
+}
+
+impl<T: Real> Ellipse<T> {
+    pub fn area(&self) -> T {
+        T::PI*self.radii.x()*self.radii.y()
+    }
+    /// `point` in the frame of the ellipse, where its axes are x and y.
+    pub fn to_local(&self, point: Vector2<T>) -> Vector2<T> {
+        rotate(point - self.center, -self.rotation)
+    }
+    /// `point` from the frame of the ellipse back into the world.
+    pub fn to_world(&self, point: Vector2<T>) -> Vector2<T> {
+        self.center + rotate(point, self.rotation)
+    }
+    /// The point on the ellipse at `angle` radians around its own axes.
+    pub fn point_at(&self, angle: T) -> Vector2<T> {
+        let (sin, cos) = angle.sin_cos();
+        self.to_world(Vector2::new(self.radii.x()*cos, self.radii.y()*sin))
+    }
+    pub fn contains(&self, point: Vector2<T>) -> bool {
+        let local = self.to_local(point);
+        let (x, y) = (local.x()/self.radii.x(), local.y()/self.radii.y());
+        x*x + y*y <= T::ONE
+    }
+    /// The smallest [`Rect`] holding the ellipse.
+    pub fn bounds(&self) -> Rect<T> {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (a, b) = (self.radii.x(), self.radii.y());
+        centered_rect(self.center, Vector2::new(
+            (a*a*cos*cos + b*b*sin*sin).sqrt(),
+            (a*a*sin*sin + b*b*cos*cos).sqrt(),
+        ))
+    }
+    /// The point on the ellipse closest to `point`, found in its own frame
+    /// by bisecting for the root of the distance function.
+    ///
+    /// from: https://www.geometrictools.com/Documentation/DistancePointEllipseEllipsoid.pdf
+    pub fn closest_point(&self, point: Vector2<T>) -> Vector2<T> {
+        let local = self.to_local(point);
+        let (a, b) = (self.radii.x().abs(), self.radii.y().abs());
+        // the method wants the major axis first and the point in the first quadrant
+        let swapped = a < b;
+        let (e0, e1, y0, y1) = if swapped {
+            (b, a, local.y().abs(), local.x().abs())
+        } else {
+            (a, b, local.x().abs(), local.y().abs())
+        };
+        let (x0, x1) = closest_quadrant_point(e0, e1, y0, y1);
+        let (x, y) = if swapped { (x1, x0) } else { (x0, x1) };
+        self.to_world(Vector2::new(
+            if local.x() < T::ZERO { -x } else { x },
+            if local.y() < T::ZERO { -y } else { y },
+        ))
+    }
+}
+
+/// The closest point on the quarter of the ellipse with radii `e0 >= e1` to
+/// the point `(y0, y1)` in the first quadrant.
+fn closest_quadrant_point<T: Real>(e0: T, e1: T, y0: T, y1: T) -> (T, T) {
+    if e1 == T::ZERO {
+        return (y0.min(e0), T::ZERO);
+    }
+    if y1 > T::ZERO {
+        if y0 == T::ZERO {
+            return (T::ZERO, e1);
+        }
+        let (z0, z1) = (y0/e0, y1/e1);
+        let g = z0*z0 + z1*z1 - T::ONE;
+        if g == T::ZERO {
+            return (y0, y1);
+        }
+        let r0 = (e0/e1)*(e0/e1);
+        let s = quadrant_root(r0, z0, z1, g);
+        return (r0*y0/(s + r0), y1/(s + T::ONE));
+    }
+    // on the major axis the closest point is off it while the point is
+    // close enough to the center
+    let (numerator, denominator) = (e0*y0, e0*e0 - e1*e1);
+    if numerator < denominator {
+        let x = numerator/denominator;
+        (e0*x, e1*(T::ONE - x*x).max(T::ZERO).sqrt())
+    } else {
+        (e0, T::ZERO)
+    }
+}
+
+/// Bisects for the root of `(r0*z0/(s+r0))^2 + (z1/(s+1))^2 - 1`, which only
+/// stops when the bracket can't be split any more.
+fn quadrant_root<T: Real>(r0: T, z0: T, z1: T, g: T) -> T {
+    let n0 = r0*z0;
+    let mut s0 = z1 - T::ONE;
+    let mut s1 = if g < T::ZERO { T::ZERO } else { (n0*n0 + z1*z1).sqrt() - T::ONE };
+    let mut s = s0;
+    for _ in 0..2048 {
+        s = (s0 + s1)*T::from_f64(0.5);
+        if s == s0 || s == s1 {
+            break;
+        }
+        let (ratio0, ratio1) = (n0/(s + r0), z1/(s + T::ONE));
+        let g = ratio0*ratio0 + ratio1*ratio1 - T::ONE;
+        if g > T::ZERO {
+            s0 = s;
+        } else if g < T::ZERO {
+            s1 = s;
+        } else {
+            break;
+        }
+    }
+    s
+}
+
+impl<T: Real> CalculateCentroid for Ellipse<T> {
+    type Vector = Vector2<T>;
+    fn centroid(&self) -> Vector2<T> {
+        self.center
+    }
+}
+
+impl<T: Real> SignedDistance<Vector2<T>> for Ellipse<T> {
+    type Distance = T;
+    fn sdf(&self, object: &Vector2<T>) -> T {
+        let distance = (*object - self.closest_point(*object)).length();
+        if self.contains(*object) { -distance } else { distance }
+    }
+}
+
+/// The points within `radius` of the segment from `start` to `end`, a
+/// rectangle with a half circle on each end.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capsule2D<T: Number> {
+    pub start: Vector2<T>,
+    pub end: Vector2<T>,
+    pub radius: T,
+}
+
+impl<T: Number> Capsule2D<T> {
+    pub fn new(start: Vector2<T>, end: Vector2<T>, radius: T) -> Self {
+        Self { start, end, radius }
+    }
+}
+
+impl<T: Real> Capsule2D<T> {
+    /// The distance between the centers of the two ends.
+    pub fn length(&self) -> T {
+        (self.end-self.start).length()
+    }
+    pub fn area(&self) -> T {
+        self.radius*(T::PI*self.radius + T::from_f64(2.0)*self.length())
+    }
+    /// The point on the segment through the middle of the capsule closest to
+    /// `point`.
+    pub fn closest_axis_point(&self, point: Vector2<T>) -> Vector2<T> {
+        let axis = self.end-self.start;
+        let length = axis.length_squared();
+        if length == T::ZERO {
+            return self.start;
+        }
+        self.start + axis*((point-self.start).dot(&axis)/length).max(T::ZERO).min(T::ONE)
+    }
+    pub fn contains(&self, point: Vector2<T>) -> bool {
+        (point - self.closest_axis_point(point)).length_squared() <= self.radius*self.radius
+    }
+    /// The smallest [`Rect`] holding the capsule.
+    pub fn bounds(&self) -> Rect<T> {
+        let radius = Vector2::from(self.radius);
+        Rect::new(self.start.min(self.end) - radius, self.start.max(self.end) + radius)
+    }
+}
+
+impl<T: Real> CalculateCentroid for Capsule2D<T> {
+    type Vector = Vector2<T>;
+    fn centroid(&self) -> Vector2<T> {
+        (self.start+self.end)*T::from_f64(0.5)
+    }
+}
+
+impl<T: Real> SignedDistance<Vector2<T>> for Capsule2D<T> {
+    type Distance = T;
+    fn sdf(&self, object: &Vector2<T>) -> T {
+        (*object-self.closest_axis_point(*object)).length() - self.radius
+    }
+}
+
+/// A rectangle reaching `half_extents` out from `center` along its own axes,
+/// turned counterclockwise by `rotation` radians.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrientedRect<T: Number> {
+    pub center: Vector2<T>,
+    pub half_extents: Vector2<T>,
+    pub rotation: T,
+}
+
+impl<T: Number> OrientedRect<T> {
+    pub fn new(center: Vector2<T>, half_extents: Vector2<T>, rotation: T) -> Self {
+        Self { center, half_extents, rotation }
+    }
+}
+
+impl<T: Real> OrientedRect<T> {
+    /// The same area as `rect` before it is turned.
+    pub fn from_rect(rect: &Rect<T>, rotation: T) -> Self {
+        Self::new(rect.centroid(), rect.size()*T::from_f64(0.5), rotation)
+    }
+    pub fn area(&self) -> T {
+        T::from_f64(4.0)*self.half_extents.x()*self.half_extents.y()
+    }
+    /// The unit x and y axes of the rect in the world.
+    pub fn axes(&self) -> [Vector2<T>; 2] {
+        [rotate(Vector2::right(), self.rotation), rotate(Vector2::top(), self.rotation)]
+    }
+    /// `point` in the frame of the rect, where its sides line up with x and y.
+    pub fn to_local(&self, point: Vector2<T>) -> Vector2<T> {
+        rotate(point - self.center, -self.rotation)
+    }
+    /// `point` from the frame of the rect back into the world.
+    pub fn to_world(&self, point: Vector2<T>) -> Vector2<T> {
+        self.center + rotate(point, self.rotation)
+    }
+    /// The corners counterclockwise in the order of [`Rect::get_vertices`].
+    pub fn corners(&self) -> [Vector2<T>; 4] {
+        let h = self.half_extents;
+        [
+            Vector2::new(-h.x(), -h.y()),
+            Vector2::new(h.x(), -h.y()),
+            Vector2::new(h.x(), h.y()),
+            Vector2::new(-h.x(), h.y()),
+        ].map(|corner| self.to_world(corner))
+    }
+    pub fn contains(&self, point: Vector2<T>) -> bool {
+        let local = self.to_local(point).abs();
+        local.x() <= self.half_extents.x() && local.y() <= self.half_extents.y()
+    }
+    /// The smallest [`Rect`] holding the rect.
+    pub fn bounds(&self) -> Rect<T> {
+        centered_rect(self.center, turned_half_size(self.half_extents, self.rotation))
+    }
+}
+
+impl<T: Real> CalculateCentroid for OrientedRect<T> {
+    type Vector = Vector2<T>;
+    fn centroid(&self) -> Vector2<T> {
+        self.center
+    }
+}
+
+impl<T: Real> SignedDistance<Vector2<T>> for OrientedRect<T> {
+    type Distance = T;
+    fn sdf(&self, object: &Vector2<T>) -> T {
+        round_box_sdf(self.to_local(*object), self.half_extents, T::ZERO)
+    }
+}
+
+/// The area of `rect` with each of its corners rounded off by a quarter
+/// circle of `radius`, which should be at most half of its shorter side.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundedRect<T: Number> {
+    pub rect: Rect<T>,
+    pub radius: T,
+}
+
+impl<T: Number> RoundedRect<T> {
+    pub fn new(rect: Rect<T>, radius: T) -> Self {
+        Self { rect, radius }
+    }
+}
+
+impl<T: Real> RoundedRect<T> {
+    pub fn area(&self) -> T {
+        self.rect.area() - (T::from_f64(4.0) - T::PI)*self.radius*self.radius
+    }
+    /// The center of the quarter circle rounding each corner, in the order of
+    /// [`Rect::get_vertices`].
+    pub fn corner_centers(&self) -> [Vector2<T>; 4] {
+        let (min, max) = (self.rect.min + Vector2::from(self.radius), self.rect.max - Vector2::from(self.radius));
+        [min, Vector2::new(max.x(), min.y()), max, Vector2::new(min.x(), max.y())]
+    }
+    pub fn contains(&self, point: Vector2<T>) -> bool {
+        self.sdf(&point) <= T::ZERO
+    }
+    /// The smallest [`Rect`] holding the rect, which is the rect before
+    /// rounding it off.
+    pub fn bounds(&self) -> Rect<T> {
+        self.rect
+    }
+}
+
+impl<T: Real> CalculateCentroid for RoundedRect<T> {
+    type Vector = Vector2<T>;
+    fn centroid(&self) -> Vector2<T> {
+        self.rect.centroid()
+    }
+}
+
+/// from: https://iquilezles.org/articles/distfunctions2d/
+impl<T: Real> SignedDistance<Vector2<T>> for RoundedRect<T> {
+    type Distance = T;
+    fn sdf(&self, object: &Vector2<T>) -> T {
+        round_box_sdf(*object - self.rect.centroid(), self.rect.size()*T::from_f64(0.5), self.radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use affogato_core::groups::vector_spaces::NormedVectorSpace;
+
+    use crate::{geometry::{Capsule2D, Ellipse, OrientedRect, Rect, RoundedRect}, sdf::SignedDistance, vector::DVec2};
+
+    #[test]
+    fn shapes_2d() {
+        let ellipse = Ellipse::new(DVec2::new(1.0, 1.0), DVec2::new(3.0, 1.0), PI/2.0);
+        assert!((ellipse.area() - 3.0*PI).abs() < 1e-12);
+        assert!((ellipse.sdf(&DVec2::new(1.0, 6.0)) - 2.0).abs() < 1e-12);
+        assert!((ellipse.sdf(&DVec2::new(4.0, 1.0)) - 2.0).abs() < 1e-12);
+        assert!((ellipse.sdf(&DVec2::new(1.0, 1.0)) + 1.0).abs() < 1e-12);
+        assert!(ellipse.contains(DVec2::new(1.0, 3.5)) && !ellipse.contains(DVec2::new(2.5, 1.0)));
+        let bounds = ellipse.bounds();
+        assert!((bounds.min - DVec2::new(0.0, -2.0)).length() < 1e-12 && (bounds.max - DVec2::new(2.0, 4.0)).length() < 1e-12);
+        // off the axes the distance is along the normal at the closest point
+        let point = DVec2::new(3.0, 2.5);
+        let closest = ellipse.closest_point(point);
+        let local = ellipse.to_local(closest);
+        assert!(((local.x()/3.0).powi(2) + local.y().powi(2) - 1.0).abs() < 1e-12);
+        assert!((ellipse.sdf(&point) - (point - closest).length()).abs() < 1e-12);
+        for angle in 0..64 {
+            let angle = angle as f64*PI/32.0;
+            assert!((ellipse.point_at(angle) - point).length() >= ellipse.sdf(&point) - 1e-12);
+        }
+
+        let capsule = Capsule2D::new(DVec2::new(0.0, 0.0), DVec2::new(4.0, 0.0), 1.0);
+        assert!((capsule.area() - (PI + 8.0)).abs() < 1e-12);
+        assert!((capsule.sdf(&DVec2::new(2.0, 3.0)) - 2.0).abs() < 1e-12);
+        assert!((capsule.sdf(&DVec2::new(7.0, 4.0)) - 4.0).abs() < 1e-12);
+        assert!(capsule.contains(DVec2::new(-0.5, 0.5)) && !capsule.contains(DVec2::new(-0.9, 0.9)));
+        assert_eq!(capsule.bounds(), Rect::new(DVec2::new(-1.0, -1.0), DVec2::new(5.0, 1.0)));
+
+        let turned = OrientedRect::new(DVec2::new(0.0, 0.0), DVec2::new(1.0, 1.0), PI/4.0);
+        assert!((turned.area() - 4.0).abs() < 1e-12);
+        assert!((turned.sdf(&DVec2::new(3.0, 0.0)) - (3.0 - 2f64.sqrt())).abs() < 1e-12);
+        assert!((turned.sdf(&DVec2::new(0.0, 0.0)) + 1.0).abs() < 1e-12);
+        assert!(turned.contains(DVec2::new(1.3, 0.0)) && !turned.contains(DVec2::new(1.0, 1.0)));
+        assert!((turned.bounds().max - DVec2::new(2f64.sqrt(), 2f64.sqrt())).length() < 1e-12);
+
+        let rounded = RoundedRect::new(Rect::new(DVec2::new(0.0, 0.0), DVec2::new(4.0, 2.0)), 1.0);
+        assert!((rounded.area() - (4.0 + PI)).abs() < 1e-12);
+        assert!((rounded.sdf(&DVec2::new(2.0, 5.0)) - 3.0).abs() < 1e-12);
+        assert!((rounded.sdf(&DVec2::new(-2.0, -3.0)) - 4.0).abs() < 1e-12);
+        assert!((rounded.sdf(&DVec2::new(2.0, 1.0)) + 1.0).abs() < 1e-12);
+        assert!(rounded.contains(DVec2::new(0.5, 1.0)) && !rounded.contains(DVec2::new(0.1, 0.1)));
+    }
+}
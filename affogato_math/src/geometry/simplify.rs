@@ -0,0 +1,142 @@
+use core::cmp::Ordering;
+
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, sets::Real};
+
+use crate::vector::Vector2;
+
+use super::Triangle2D;
+extern crate alloc;
+use alloc::{collections::BinaryHeap, vec::Vec};
+
+/// The distance from `point` to the line segment between `start` and `end`.
+fn distance_to_line<T: Real>(point: Vector2<T>, start: Vector2<T>, end: Vector2<T>) -> T {
+    let line = end - start;
+    let length_squared = line.length_squared();
+    if length_squared == T::ZERO {
+        return (point - start).length();
+    }
+    let t = affogato_core::clamp((point - start).dot(&line)/length_squared, T::ZERO, T::ONE);
+    (point - (start + line*t)).length()
+}
+
+/// Simplifies a polyline with the Ramer–Douglas–Peucker algorithm, removing
+/// points until no removed point is further than `tolerance` from the
+/// simplified line. The first and last points are always kept.
+pub fn simplify_rdp<T: Real>(points: &[Vector2<T>], tolerance: T) -> Vec<Vector2<T>> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let mut keep = alloc::vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len()-1] = true;
+    let mut ranges = alloc::vec![(0, points.len()-1)];
+    while let Some((first, last)) = ranges.pop() {
+        let mut furthest = (T::ZERO, first);
+        for (i, &point) in points.iter().enumerate().take(last).skip(first+1) {
+            let distance = distance_to_line(point, points[first], points[last]);
+            if distance > furthest.0 {
+                furthest = (distance, i);
+            }
+        }
+        let (distance, split) = furthest;
+        if distance > tolerance {
+            keep[split] = true;
+            ranges.push((first, split));
+            ranges.push((split, last));
+        }
+    }
+    points.iter().zip(keep).filter_map(|(&point, keep)| keep.then_some(point)).collect()
+}
+
+/// A point that could be removed by [`simplify_visvalingam`], along with the
+/// neighbours its area was calculated from so stale entries can be skipped.
+struct Candidate<T> {
+    area: T,
+    index: usize,
+    previous: usize,
+    next: usize,
+}
+impl<T: Real> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<T: Real> Eq for Candidate<T> {}
+impl<T: Real> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T: Real> Ord for Candidate<T> {
+    /// Reversed so that the [`BinaryHeap`] gives the smallest area first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.area.partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+/// Simplifies a polyline with the Visvalingam–Whyatt algorithm, repeatedly
+/// removing the point that forms the smallest triangle with its neighbours
+/// until every remaining triangle has an area of at least `min_area`. The
+/// first and last points are always kept.
+pub fn simplify_visvalingam<T: Real>(points: &[Vector2<T>], min_area: T) -> Vec<Vector2<T>> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let area = |previous: usize, index: usize, next: usize| {
+        Triangle2D::new(points[previous], points[index], points[next]).area()
+    };
+    let mut previous: Vec<usize> = (0..points.len()).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=points.len()).collect();
+    let mut removed = alloc::vec![false; points.len()];
+    let mut candidates: BinaryHeap<Candidate<T>> = (1..points.len()-1)
+        .map(|index| Candidate { area: area(index-1, index, index+1), index, previous: index-1, next: index+1 })
+        .collect();
+    // areas never drop below the last removed area, so a point isn't kept
+    // just because removing its neighbour made its triangle smaller
+    let mut last_area = T::ZERO;
+    while let Some(candidate) = candidates.pop() {
+        let index = candidate.index;
+        if removed[index] || previous[index] != candidate.previous || next[index] != candidate.next {
+            continue;
+        }
+        if candidate.area >= min_area {
+            break;
+        }
+        last_area = last_area.max(candidate.area);
+        removed[index] = true;
+        let (before, after) = (previous[index], next[index]);
+        next[before] = after;
+        previous[after] = before;
+        for neighbour in [before, after] {
+            if neighbour != 0 && neighbour != points.len()-1 {
+                let (previous, next) = (previous[neighbour], next[neighbour]);
+                let area = area(previous, neighbour, next).max(last_area);
+                candidates.push(Candidate { area, index: neighbour, previous, next });
+            }
+        }
+    }
+    points.iter().zip(removed).filter_map(|(&point, removed)| (!removed).then_some(point)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vector::DVec2;
+
+    use super::{simplify_rdp, simplify_visvalingam};
+
+    #[test]
+    fn simplify_polylines() {
+        let line: super::alloc::vec::Vec<DVec2> = (0..=10)
+            .map(|i| DVec2::new(i as f64, if i % 2 == 0 { 0.0 } else { 0.01 }))
+            .collect();
+        assert_eq!(simplify_rdp(&line, 0.1), [line[0], line[10]]);
+        assert_eq!(simplify_visvalingam(&line, 0.1), [line[0], line[10]]);
+
+        let corner = [DVec2::new(0.0, 0.0), DVec2::new(1.0, 0.01), DVec2::new(2.0, 0.0), DVec2::new(2.0, 2.0)];
+        let expected = [corner[0], corner[2], corner[3]];
+        assert_eq!(simplify_rdp(&corner, 0.1), expected);
+        assert_eq!(simplify_visvalingam(&corner, 0.1), expected);
+    }
+}
@@ -0,0 +1,421 @@
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{Bounds, Number, Signed, Zero}, sets::Real};
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{algebra::Quaternion, sdf::SignedDistance, vector::Vector3};
+
+use super::{segment3d::perpendicular, CalculateCentroid, Rect3D, Support};
+
+/// The part of `vector` at right angles to the unit `axis`.
+pub(crate) fn radial<T: Real>(vector: Vector3<T>, axis: Vector3<T>) -> Vector3<T> {
+    vector - axis*vector.dot(&axis)
+}
+
+/// `vector` scaled to `length`, or zero when it has no direction.
+fn with_length<T: Real>(vector: Vector3<T>, length: T) -> Vector3<T> {
+    let magnitude = vector.length();
+    if magnitude == T::ZERO { Vector3::ZERO } else { vector*(length/magnitude) }
+}
+
+/// The points within `radius` of the segment from `start` to `end`, a
+/// cylinder with a half sphere on each end.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capsule<T: Number> {
+    pub start: Vector3<T>,
+    pub end: Vector3<T>,
+    pub radius: T,
+}
+
+impl<T: Number> Capsule<T> {
+    pub fn new(start: Vector3<T>, end: Vector3<T>, radius: T) -> Self {
+        Self { start, end, radius }
+    }
+}
+
+impl<T: Real> Capsule<T> {
+    /// The distance between the centers of the two ends.
+    pub fn height(&self) -> T {
+        (self.end-self.start).length()
+    }
+    pub fn volume(&self) -> T {
+        T::PI*self.radius*self.radius*(self.height() + T::from_f64(4.0/3.0)*self.radius)
+    }
+    pub fn surface_area(&self) -> T {
+        T::from_f64(2.0)*T::PI*self.radius*(self.height() + T::from_f64(2.0)*self.radius)
+    }
+    /// The point on the segment through the middle of the capsule closest to
+    /// `point`.
+    pub fn closest_axis_point(&self, point: Vector3<T>) -> Vector3<T> {
+        let axis = self.end-self.start;
+        let length = axis.length_squared();
+        if length == T::ZERO {
+            return self.start;
+        }
+        self.start + axis*((point-self.start).dot(&axis)/length).max(T::ZERO).min(T::ONE)
+    }
+}
+
+impl<T: Real> CalculateCentroid for Capsule<T> {
+    type Vector = Vector3<T>;
+    fn centroid(&self) -> Vector3<T> {
+        (self.start+self.end)*T::from_f64(0.5)
+    }
+}
+
+impl<T: Real> SignedDistance<Vector3<T>> for Capsule<T> {
+    type Distance = T;
+    fn sdf(&self, object: &Vector3<T>) -> T {
+        (*object-self.closest_axis_point(*object)).length() - self.radius
+    }
+}
+
+impl<T: Real> Support for Capsule<T> {
+    type Vector = Vector3<T>;
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        let end = if (self.end-self.start).dot(&direction) >= T::ZERO { self.end } else { self.start };
+        end + with_length(direction, self.radius)
+    }
+}
+
+/// A cylinder of `radius` around the segment from `start` to `end`, with
+/// flat caps on both ends.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cylinder<T: Number> {
+    pub start: Vector3<T>,
+    pub end: Vector3<T>,
+    pub radius: T,
+}
+
+impl<T: Number> Cylinder<T> {
+    pub fn new(start: Vector3<T>, end: Vector3<T>, radius: T) -> Self {
+        Self { start, end, radius }
+    }
+}
+
+impl<T: Real> Cylinder<T> {
+    pub fn height(&self) -> T {
+        (self.end-self.start).length()
+    }
+    /// The unit direction from `start` to `end`.
+    pub fn axis(&self) -> Vector3<T> {
+        (self.end-self.start).normalize()
+    }
+    pub fn volume(&self) -> T {
+        T::PI*self.radius*self.radius*self.height()
+    }
+    pub fn surface_area(&self) -> T {
+        T::from_f64(2.0)*T::PI*self.radius*(self.height() + self.radius)
+    }
+}
+
+impl<T: Real> CalculateCentroid for Cylinder<T> {
+    type Vector = Vector3<T>;
+    fn centroid(&self) -> Vector3<T> {
+        (self.start+self.end)*T::from_f64(0.5)
+    }
+}
+
+/// from: https://iquilezles.org/articles/distfunctions/
+impl<T: Real> SignedDistance<Vector3<T>> for Cylinder<T> {
+    type Distance = T;
+    fn sdf(&self, object: &Vector3<T>) -> T {
+        let axis = self.end-self.start;
+        let offset = *object-self.start;
+        let length = axis.length_squared();
+        let along = offset.dot(&axis);
+        let x = (offset*length - axis*along).length() - self.radius*length;
+        let y = (along - length*T::from_f64(0.5)).abs() - length*T::from_f64(0.5);
+        let (x2, y2) = (x*x, y*y*length);
+        let distance = if x.max(y) < T::ZERO {
+            -x2.min(y2)
+        } else {
+            (if x > T::ZERO { x2 } else { T::ZERO }) + (if y > T::ZERO { y2 } else { T::ZERO })
+        };
+        distance.signum()*distance.abs().sqrt()/length
+    }
+}
+
+impl<T: Real> Support for Cylinder<T> {
+    type Vector = Vector3<T>;
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        let axis = self.axis();
+        let end = if axis.dot(&direction) >= T::ZERO { self.end } else { self.start };
+        end + with_length(radial(direction, axis), self.radius)
+    }
+}
+
+/// A cone with a round base of `radius` around `base`, narrowing to a point
+/// at `apex`.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cone<T: Number> {
+    pub base: Vector3<T>,
+    pub apex: Vector3<T>,
+    pub radius: T,
+}
+
+impl<T: Number> Cone<T> {
+    pub fn new(base: Vector3<T>, apex: Vector3<T>, radius: T) -> Self {
+        Self { base, apex, radius }
+    }
+}
+
+impl<T: Real> Cone<T> {
+    pub fn height(&self) -> T {
+        (self.apex-self.base).length()
+    }
+    /// The unit direction from the base to the apex.
+    pub fn axis(&self) -> Vector3<T> {
+        (self.apex-self.base).normalize()
+    }
+    /// The distance from the edge of the base to the apex.
+    pub fn slant_height(&self) -> T {
+        self.height().hypot(self.radius)
+    }
+    pub fn volume(&self) -> T {
+        T::PI*self.radius*self.radius*self.height()/T::from_f64(3.0)
+    }
+    pub fn surface_area(&self) -> T {
+        T::PI*self.radius*(self.radius + self.slant_height())
+    }
+}
+
+impl<T: Real> CalculateCentroid for Cone<T> {
+    type Vector = Vector3<T>;
+    fn centroid(&self) -> Vector3<T> {
+        self.base + (self.apex-self.base)*T::from_f64(0.25)
+    }
+}
+
+/// from: https://iquilezles.org/articles/distfunctions/
+impl<T: Real> SignedDistance<Vector3<T>> for Cone<T> {
+    type Distance = T;
+    fn sdf(&self, object: &Vector3<T>) -> T {
+        let half = T::from_f64(0.5);
+        let axis = self.apex-self.base;
+        let offset = *object-self.base;
+        let length = axis.length_squared();
+        let along = offset.dot(&axis)/length;
+        let x = (offset.length_squared() - along*along*length).max(T::ZERO).sqrt();
+        let cap_x = (x - if along < half { self.radius } else { T::ZERO }).max(T::ZERO);
+        let cap_y = (along - half).abs() - half;
+        let k = self.radius*self.radius + length;
+        let f = ((x - self.radius)*(-self.radius) + along*length).max(T::ZERO)/k;
+        let f = f.min(T::ONE);
+        let side_x = x - self.radius + f*self.radius;
+        let side_y = along - f;
+        let sign = if side_x < T::ZERO && cap_y < T::ZERO { -T::ONE } else { T::ONE };
+        sign*(cap_x*cap_x + cap_y*cap_y*length).min(side_x*side_x + side_y*side_y*length).sqrt()
+    }
+}
+
+impl<T: Real> Support for Cone<T> {
+    type Vector = Vector3<T>;
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        let rim = self.base + with_length(radial(direction, self.axis()), self.radius);
+        if self.apex.dot(&direction) >= rim.dot(&direction) { self.apex } else { rim }
+    }
+}
+
+/// A ring around `axis` through `center`, the points within `minor_radius`
+/// of the circle of `major_radius`.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Torus<T: Number> {
+    pub center: Vector3<T>,
+    /// The unit direction the hole goes through.
+    pub axis: Vector3<T>,
+    pub major_radius: T,
+    pub minor_radius: T,
+}
+
+impl<T: Real> Torus<T> {
+    /// Creates a [`Torus`], normalizing `axis`.
+    pub fn new(center: Vector3<T>, axis: Vector3<T>, major_radius: T, minor_radius: T) -> Self {
+        Self { center, axis: axis.normalize(), major_radius, minor_radius }
+    }
+    pub fn volume(&self) -> T {
+        T::from_f64(2.0)*T::PI*T::PI*self.major_radius*self.minor_radius*self.minor_radius
+    }
+    pub fn surface_area(&self) -> T {
+        T::from_f64(4.0)*T::PI*T::PI*self.major_radius*self.minor_radius
+    }
+    /// The point on the circle through the middle of the ring closest to
+    /// `point`, which is any of them for points on the axis.
+    pub fn closest_ring_point(&self, point: Vector3<T>) -> Vector3<T> {
+        let outward = radial(point-self.center, self.axis);
+        let outward = if outward.length_squared() == T::ZERO { perpendicular(self.axis, self.axis) } else { outward };
+        self.center + with_length(outward, self.major_radius)
+    }
+}
+
+impl<T: Number> CalculateCentroid for Torus<T> {
+    type Vector = Vector3<T>;
+    fn centroid(&self) -> Vector3<T> {
+        self.center
+    }
+}
+
+impl<T: Real> SignedDistance<Vector3<T>> for Torus<T> {
+    type Distance = T;
+    fn sdf(&self, object: &Vector3<T>) -> T {
+        (*object-self.closest_ring_point(*object)).length() - self.minor_radius
+    }
+}
+
+impl<T: Real> Support for Torus<T> {
+    type Vector = Vector3<T>;
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        self.closest_ring_point(self.center + direction) + with_length(direction, self.minor_radius)
+    }
+}
+
+/// A box turned by `rotation` around its `center`, reaching out
+/// `half_extents` along each of its own axes.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrientedBox3D<T: Number> {
+    pub center: Vector3<T>,
+    pub half_extents: Vector3<T>,
+    /// Has to be a unit quaternion.
+    pub rotation: Quaternion<T>,
+}
+
+impl<T: Real> OrientedBox3D<T> {
+    pub fn new(center: Vector3<T>, half_extents: Vector3<T>, rotation: Quaternion<T>) -> Self {
+        Self { center, half_extents, rotation }
+    }
+    /// The box covering the same space as `rect`, without any rotation.
+    pub fn from_rect(rect: &Rect3D<T>) -> Self {
+        Self::new((rect.min+rect.max)*T::from_f64(0.5), rect.size()*T::from_f64(0.5), Quaternion::identity())
+    }
+    /// The unit directions of the box's own x, y and z axes.
+    pub fn axes(&self) -> [Vector3<T>; 3] {
+        [self.rotation.right(), self.rotation.up(), self.rotation.forward()]
+    }
+    /// `point` in the frame of the box, where it is centered at the origin
+    /// and lined up with the axes.
+    pub fn to_local(&self, point: Vector3<T>) -> Vector3<T> {
+        self.rotation.conjugate()*(point-self.center)
+    }
+    /// `point` from the frame of the box back into the world.
+    pub fn to_world(&self, point: Vector3<T>) -> Vector3<T> {
+        self.center + self.rotation*point
+    }
+    /// The corners in the order of [`Rect3D::get_vertices`], so they can be
+    /// drawn with [`Rect3D::edge_indices`] and [`Rect3D::tri_indices`].
+    pub fn corners(&self) -> [Vector3<T>; 8] {
+        let h = self.half_extents;
+        [
+            Vector3::new(-h.x(), -h.y(), -h.z()),
+            Vector3::new(h.x(), h.y(), -h.z()),
+            Vector3::new(h.x(), -h.y(), -h.z()),
+            Vector3::new(-h.x(), h.y(), -h.z()),
+            Vector3::new(-h.x(), -h.y(), h.z()),
+            Vector3::new(h.x(), h.y(), h.z()),
+            Vector3::new(h.x(), -h.y(), h.z()),
+            Vector3::new(-h.x(), h.y(), h.z()),
+        ].map(|corner| self.to_world(corner))
+    }
+    pub fn volume(&self) -> T {
+        let h = self.half_extents;
+        T::from_f64(8.0)*h.x()*h.y()*h.z()
+    }
+    pub fn surface_area(&self) -> T {
+        let h = self.half_extents;
+        T::from_f64(8.0)*(h.x()*h.y() + h.y()*h.z() + h.z()*h.x())
+    }
+}
+
+impl<T: Real> CalculateCentroid for OrientedBox3D<T> {
+    type Vector = Vector3<T>;
+    fn centroid(&self) -> Vector3<T> {
+        self.center
+    }
+}
+
+impl<T: Real> SignedDistance<Vector3<T>> for OrientedBox3D<T> {
+    type Distance = T;
+    fn sdf(&self, object: &Vector3<T>) -> T {
+        let q = self.to_local(*object).abs() - self.half_extents;
+        q.max(Vector3::ZERO).length() + q.x().max(q.y()).max(q.z()).min(T::ZERO)
+    }
+}
+
+impl<T: Real> Support for OrientedBox3D<T> {
+    type Vector = Vector3<T>;
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        let local = self.rotation.conjugate()*direction;
+        let h = self.half_extents;
+        let corner = Vector3::new(
+            if local.x() >= T::ZERO { h.x() } else { -h.x() },
+            if local.y() >= T::ZERO { h.y() } else { -h.y() },
+            if local.z() >= T::ZERO { h.z() } else { -h.z() },
+        );
+        self.to_world(corner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use affogato_core::groups::vector_spaces::NormedVectorSpace;
+
+    use crate::{algebra::Quaternion, geometry::{Capsule, Cone, Cylinder, OrientedBox3D, Ray3D, Support, Torus}, sdf::SignedDistance, vector::DVec3};
+
+    #[test]
+    fn solids() {
+        let capsule = Capsule::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(0.0, 2.0, 0.0), 1.0);
+        assert!((capsule.volume() - PI*(2.0 + 4.0/3.0)).abs() < 1e-12);
+        assert!((capsule.sdf(&DVec3::new(3.0, 1.0, 0.0)) - 2.0).abs() < 1e-12);
+        assert!((capsule.support(DVec3::new(0.0, 1.0, 0.0)) - DVec3::new(0.0, 3.0, 0.0)).length() < 1e-12);
+        let hit = Ray3D::new(DVec3::new(0.0, 10.0, 0.0), DVec3::new(0.0, 0.0, 0.0)).intersect_capsule(&capsule).unwrap();
+        assert!((hit.distance - 7.0).abs() < 1e-12);
+        let hit = Ray3D::new(DVec3::new(-5.0, 1.0, 0.0), DVec3::new(0.0, 1.0, 0.0)).intersect_capsule(&capsule).unwrap();
+        assert!((hit.distance - 4.0).abs() < 1e-12 && (hit.normal - DVec3::new(-1.0, 0.0, 0.0)).length() < 1e-12);
+
+        let cylinder = Cylinder::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(0.0, 0.0, 2.0), 1.0);
+        assert!((cylinder.surface_area() - 6.0*PI).abs() < 1e-12);
+        assert!((cylinder.sdf(&DVec3::new(0.5, 0.0, 1.0)) + 0.5).abs() < 1e-12);
+        assert!((cylinder.sdf(&DVec3::new(4.0, 0.0, 6.0)) - 5.0).abs() < 1e-12);
+        let hit = Ray3D::new(DVec3::new(0.5, 0.0, 5.0), DVec3::new(0.5, 0.0, 0.0)).intersect_cylinder(&cylinder).unwrap();
+        assert!((hit.distance - 3.0).abs() < 1e-12 && (hit.normal - DVec3::new(0.0, 0.0, 1.0)).length() < 1e-12);
+        assert!(Ray3D::new(DVec3::new(2.0, 0.0, 5.0), DVec3::new(2.0, 0.0, 0.0)).intersect_cylinder(&cylinder).is_none());
+
+        let cone = Cone::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(0.0, 3.0, 0.0), 1.0);
+        assert!((cone.volume() - PI).abs() < 1e-12);
+        assert!((cone.sdf(&DVec3::new(0.0, 5.0, 0.0)) - 2.0).abs() < 1e-12);
+        assert!(cone.sdf(&DVec3::new(0.0, 1.0, 0.0)) < 0.0);
+        assert!((cone.support(DVec3::new(1.0, -1.0, 0.0)) - DVec3::new(1.0, 0.0, 0.0)).length() < 1e-12);
+        let hit = Ray3D::new(DVec3::new(-5.0, 1.5, 0.0), DVec3::new(0.0, 1.5, 0.0)).intersect_cone(&cone).unwrap();
+        assert!((hit.distance - 4.5).abs() < 1e-12);
+        let hit = Ray3D::new(DVec3::new(0.2, -4.0, 0.0), DVec3::new(0.2, 0.0, 0.0)).intersect_cone(&cone).unwrap();
+        assert!((hit.distance - 4.0).abs() < 1e-12 && (hit.normal - DVec3::new(0.0, -1.0, 0.0)).length() < 1e-12);
+
+        let torus = Torus::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(0.0, 0.0, 1.0), 2.0, 0.5);
+        assert!((torus.volume() - 2.0*PI*PI*2.0*0.25).abs() < 1e-12);
+        assert!((torus.sdf(&DVec3::new(0.0, 0.0, 0.0)) - 1.5).abs() < 1e-12);
+        assert!((torus.support(DVec3::new(1.0, 0.0, 0.0)) - DVec3::new(2.5, 0.0, 0.0)).length() < 1e-12);
+        let hit = Ray3D::new(DVec3::new(-5.0, 0.0, 0.0), DVec3::new(0.0, 0.0, 0.0)).intersect_torus(&torus).unwrap();
+        assert!((hit.distance - 2.5).abs() < 1e-9 && (hit.normal - DVec3::new(-1.0, 0.0, 0.0)).length() < 1e-9);
+        let hit = Ray3D::new(DVec3::new(2.0, 0.0, -5.0), DVec3::new(2.0, 0.0, 0.0)).intersect_torus(&torus).unwrap();
+        assert!((hit.distance - 4.5).abs() < 1e-9);
+        // straight through the hole
+        assert!(Ray3D::new(DVec3::new(0.0, 0.0, -5.0), DVec3::new(0.0, 0.0, 0.0)).intersect_torus(&torus).is_none());
+        // from inside the hole out through the inner side of the ring
+        let hit = Ray3D::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 0.0, 0.0)).intersect_torus(&torus).unwrap();
+        assert!((hit.distance - 1.5).abs() < 1e-9);
+
+        let turned = OrientedBox3D::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 1.0, 1.0), Quaternion::angle_axis(PI/4.0, DVec3::new(0.0, 0.0, 1.0)));
+        assert!((turned.sdf(&DVec3::new(3.0, 0.0, 0.0)) - (3.0 - 2f64.sqrt())).abs() < 1e-12);
+        assert!((turned.support(DVec3::new(1.0, 0.01, 0.0)).x() - 2f64.sqrt()).abs() < 1e-12);
+        let hit = Ray3D::new(DVec3::new(-5.0, 0.0, 0.0), DVec3::new(0.0, 0.0, 0.0)).intersect_oriented_box(&turned).unwrap();
+        assert!((hit.distance - (5.0 - 2f64.sqrt())).abs() < 1e-12);
+        assert!((hit.normal.x() + 0.5f64.sqrt()).abs() < 1e-12);
+        assert_eq!(turned, OrientedBox3D::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 1.0, 1.0), Quaternion::angle_axis(PI/4.0, DVec3::new(0.0, 0.0, 1.0))));
+        assert_ne!(turned, OrientedBox3D::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 1.0, 1.0), Quaternion::identity()));
+    }
+}
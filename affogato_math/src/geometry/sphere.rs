@@ -1,4 +1,4 @@
-use crate::{geometry::CalculateCentroid, sdf::SignedDistance, vector::{Vector2, Vector3}};
+use crate::{geometry::{CalculateCentroid, Support}, sdf::SignedDistance, vector::{Vector2, Vector3}};
 
 use affogato_core::{groups::vector_spaces::{MetricSpace, VectorSpace, NormedVectorSpace}, num::Number, sets::Real};
 #[cfg(feature="serde")]
@@ -116,4 +116,12 @@ impl<T: Real> SignedDistance<Self> for Circle<T> {
     }
 }
 
+impl<T: Real> Support for Sphere<T> {
+    type Vector = Vector3<T>;
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        let length = direction.length();
+        if length == T::ZERO { self.center } else { self.center + direction*(self.radius/length) }
+    }
+}
+
 impl_ops_hsphere!(Sphere, Vector3);
\ No newline at end of file
@@ -0,0 +1,389 @@
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{FloatConsts, Number, Zero}, sets::Real};
+
+use crate::vector::Vector2;
+
+use super::{Path2D, Segment2D, Triangle2D};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// How two connected lines of a stroke are joined on the outside of the turn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    /// Extends the outer edges of both lines until they meet, falling back
+    /// to [`LineJoin::Bevel`] past the miter limit.
+    #[default]
+    Miter,
+    Round,
+    /// Cuts the corner with a straight line.
+    Bevel,
+}
+
+/// How the ends of an open stroke are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// The stroke ends exactly at the end of the line.
+    #[default]
+    Butt,
+    /// A half circle with a diameter of the width of the stroke.
+    Round,
+    /// The stroke extends past the end of the line by half its width.
+    Square,
+}
+
+/// Describes how a line is turned into a filled shape, following the same
+/// rules as SVG and the canvas API.
+#[derive(Clone, Debug)]
+pub struct StrokeStyle<T: Number> {
+    pub width: T,
+    pub join: LineJoin,
+    pub cap: LineCap,
+    /// The longest a miter join can be as a ratio of the width of the stroke.
+    pub miter_limit: T,
+    /// Alternating lengths of dashes and gaps, when empty the stroke is solid.
+    /// A pattern with an odd amount of lengths is repeated to make it even.
+    pub dashes: Vec<T>,
+    /// How far into the dash pattern the stroke starts.
+    pub dash_offset: T,
+    /// The most curves and round joins and caps can deviate from the exact shape.
+    pub tolerance: T,
+}
+impl<T: Number> StrokeStyle<T> {
+    /// A solid stroke with miter joins and butt caps. The tolerance starts
+    /// at a hundredth of the width.
+    pub fn new(width: T) -> Self {
+        Self {
+            width,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            miter_limit: T::from_f64(4.0),
+            dashes: Vec::new(),
+            dash_offset: T::ZERO,
+            tolerance: width/T::from_f64(100.0),
+        }
+    }
+    pub fn with_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+    pub fn with_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+    pub fn with_miter_limit(mut self, miter_limit: T) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+    pub fn with_dashes(mut self, dashes: Vec<T>, offset: T) -> Self {
+        self.dashes = dashes;
+        self.dash_offset = offset;
+        self
+    }
+    pub fn with_tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// A list of vertices and the indices of the triangles formed from them,
+/// three indices per triangle.
+#[derive(Clone, Debug)]
+pub struct TriangleMesh2D<T: Number> {
+    pub vertices: Vec<Vector2<T>>,
+    pub indices: Vec<u32>,
+}
+impl<T: Number> Default for TriangleMesh2D<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Number> TriangleMesh2D<T> {
+    pub const fn new() -> Self {
+        Self { vertices: Vec::new(), indices: Vec::new() }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+    /// Adds a vertex, giving back its index.
+    pub fn push_vertex(&mut self, vertex: Vector2<T>) -> u32 {
+        self.vertices.push(vertex);
+        (self.vertices.len()-1) as u32
+    }
+    pub fn push_triangle(&mut self, a: Vector2<T>, b: Vector2<T>, c: Vector2<T>) {
+        let a = self.push_vertex(a);
+        let b = self.push_vertex(b);
+        let c = self.push_vertex(c);
+        self.indices.extend_from_slice(&[a, b, c]);
+    }
+    /// Adds the quad `a b c d` given in order around its edge as two triangles.
+    pub fn push_quad(&mut self, a: Vector2<T>, b: Vector2<T>, c: Vector2<T>, d: Vector2<T>) {
+        let a = self.push_vertex(a);
+        let b = self.push_vertex(b);
+        let c = self.push_vertex(c);
+        let d = self.push_vertex(d);
+        self.indices.extend_from_slice(&[a, b, c, a, c, d]);
+    }
+    /// Adds every vertex and triangle of `other` to the mesh.
+    pub fn append(&mut self, other: &Self) {
+        let offset = self.vertices.len() as u32;
+        self.vertices.extend_from_slice(&other.vertices);
+        self.indices.extend(other.indices.iter().map(|index| index+offset));
+    }
+    pub fn triangles(&self) -> impl Iterator<Item = Triangle2D<T>> + '_ {
+        self.indices.chunks_exact(3).map(|triangle| {
+            Triangle2D::new(
+                self.vertices[triangle[0] as usize],
+                self.vertices[triangle[1] as usize],
+                self.vertices[triangle[2] as usize]
+            )
+        })
+    }
+}
+
+/// Builds the triangles of a stroke one polyline at a time.
+struct Stroker<'a, T: Number> {
+    style: &'a StrokeStyle<T>,
+    half_width: T,
+    mesh: TriangleMesh2D<T>,
+}
+impl<'a, T: Real> Stroker<'a, T> {
+    fn new(style: &'a StrokeStyle<T>) -> Self {
+        Self { style, half_width: style.width.abs()/T::from_f64(2.0), mesh: TriangleMesh2D::new() }
+    }
+    /// Adds a fan of triangles around `center`, starting at `center+from` and
+    /// rotating by `angle` radians.
+    fn fan(&mut self, center: Vector2<T>, from: Vector2<T>, angle: T) {
+        let step = if self.style.tolerance >= self.half_width || self.style.tolerance <= T::ZERO {
+            T::FRAC_PI_2
+        } else {
+            (T::ONE - self.style.tolerance/self.half_width).acos()*T::from_f64(2.0)
+        };
+        let count = (angle.abs()/step).ceil().max(T::ONE).to_usize();
+        let mut previous = from;
+        for i in 1..=count {
+            let (sin, cos) = (angle*T::from_usize(i)/T::from_usize(count)).sin_cos();
+            let next = Vector2::new(from.x()*cos - from.y()*sin, from.x()*sin + from.y()*cos);
+            self.mesh.push_triangle(center, center+previous, center+next);
+            previous = next;
+        }
+    }
+    /// Adds the cap at the end of a line going in `direction`.
+    fn cap(&mut self, point: Vector2<T>, direction: Vector2<T>) {
+        let normal = direction.rotate_90()*self.half_width;
+        let extension = direction*self.half_width;
+        match self.style.cap {
+            LineCap::Butt => {},
+            LineCap::Square => self.mesh.push_quad(point+normal, point+normal+extension, point-normal+extension, point-normal),
+            LineCap::Round => self.fan(point, normal, -T::PI),
+        }
+    }
+    /// Adds the join on the outside of the turn from `incoming` to `outgoing`
+    /// at `point`, the inside is already covered by the overlapping lines.
+    fn join(&mut self, point: Vector2<T>, incoming: Vector2<T>, outgoing: Vector2<T>) {
+        let turn = incoming.cross(&outgoing);
+        let dot = incoming.dot(&outgoing);
+        if turn == T::ZERO && dot > T::ZERO {
+            return;
+        }
+        // the outer side of a left turn is on the right
+        let side = if turn > T::ZERO { -self.half_width } else { self.half_width };
+        let from = incoming.rotate_90()*side;
+        let to = outgoing.rotate_90()*side;
+        match self.style.join {
+            LineJoin::Round => self.fan(point, from, from.cross(&to).atan2(from.dot(&to))),
+            // the miter length relative to the width is 1/sin(θ/2) where θ is the angle between both lines
+            LineJoin::Miter if dot > -T::ONE && T::from_f64(2.0) <= self.style.miter_limit*self.style.miter_limit*(T::ONE+dot) => {
+                let miter = point + (from+to)/(T::ONE+dot);
+                self.mesh.push_quad(point, point+from, miter, point+to);
+            },
+            LineJoin::Miter | LineJoin::Bevel => self.mesh.push_triangle(point, point+from, point+to),
+        }
+    }
+    fn polyline(&mut self, points: &[Vector2<T>], closed: bool) {
+        let mut points: Vec<Vector2<T>> = points.to_vec();
+        points.dedup();
+        if closed && points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        let Some(&first) = points.first() else {
+            return;
+        };
+        if points.len() == 1 {
+            // a line with no length only draws its caps
+            if !closed {
+                self.cap(first, Vector2::right());
+                self.cap(first, -Vector2::right());
+            }
+            return;
+        }
+        let closed = closed && points.len() > 2;
+        let lines = if closed { points.len() } else { points.len()-1 };
+        let direction = |i: usize| (points[(i+1)%points.len()]-points[i]).normalize();
+        for i in 0..lines {
+            let (start, end) = (points[i], points[(i+1)%points.len()]);
+            let normal = direction(i).rotate_90()*self.half_width;
+            self.mesh.push_quad(start+normal, end+normal, end-normal, start-normal);
+        }
+        for (i, &point) in points.iter().enumerate().take(lines).skip(1) {
+            self.join(point, direction(i-1), direction(i));
+        }
+        if closed {
+            self.join(first, direction(lines-1), direction(0));
+        } else {
+            self.cap(first, -direction(0));
+            self.cap(points[lines], direction(lines-1));
+        }
+    }
+    /// Strokes the polyline, splitting it into dashes first when the style
+    /// has a dash pattern.
+    fn add(&mut self, points: &[Vector2<T>], closed: bool) {
+        match dash(points, closed, &self.style.dashes, self.style.dash_offset) {
+            Some(dashes) => dashes.iter().for_each(|dash| self.polyline(dash, false)),
+            None => self.polyline(points, closed),
+        }
+    }
+}
+
+/// Splits a polyline into the parts covered by the dashes of `pattern`. Gives
+/// [`None`] when the pattern can't be used, in which case the line is solid.
+fn dash<T: Real>(points: &[Vector2<T>], closed: bool, pattern: &[T], offset: T) -> Option<Vec<Vec<Vector2<T>>>> {
+    let repeat = if pattern.len() % 2 == 1 { 2 } else { 1 };
+    let pattern: Vec<T> = pattern.iter().copied().cycle().take(pattern.len()*repeat).collect();
+    let total = pattern.iter().fold(T::ZERO, |total, &length| total + length);
+    if pattern.is_empty() || total <= T::ZERO || pattern.iter().any(|&length| length < T::ZERO) {
+        return None;
+    }
+    let mut position = offset - total*(offset/total).floor();
+    let mut index = 0;
+    while position >= pattern[index] {
+        position -= pattern[index];
+        index = (index+1)%pattern.len();
+    }
+    let mut remaining = pattern[index]-position;
+
+    let mut dashes = Vec::new();
+    let mut current = Vec::new();
+    let closing = closed.then(|| points.first()).flatten();
+    let mut points = points.iter().chain(closing).copied();
+    let Some(mut start) = points.next() else {
+        return Some(dashes);
+    };
+    if index % 2 == 0 {
+        current.push(start);
+    }
+    for end in points {
+        let length = (end-start).length();
+        let mut travelled = T::ZERO;
+        while length-travelled > remaining {
+            travelled += remaining;
+            let point = start + (end-start)*(travelled/length);
+            if index % 2 == 0 {
+                current.push(point);
+                dashes.push(core::mem::take(&mut current));
+            } else {
+                current = alloc::vec![point];
+            }
+            index = (index+1)%pattern.len();
+            remaining = pattern[index];
+        }
+        remaining -= length-travelled;
+        if index % 2 == 0 {
+            current.push(end);
+        }
+        start = end;
+    }
+    if index % 2 == 0 && !current.is_empty() {
+        dashes.push(current);
+    }
+    Some(dashes)
+}
+
+/// Turns a polyline into the triangles covered by stroking it with `style`.
+pub fn stroke_polyline<T: Real>(points: &[Vector2<T>], closed: bool, style: &StrokeStyle<T>) -> TriangleMesh2D<T> {
+    let mut stroker = Stroker::new(style);
+    stroker.add(points, closed);
+    stroker.mesh
+}
+
+impl<T: Real> Segment2D<T> {
+    /// The triangles covered by stroking the segment with `style`.
+    pub fn stroke(&self, style: &StrokeStyle<T>) -> TriangleMesh2D<T> {
+        stroke_polyline(&self.flatten(style.tolerance), false, style)
+    }
+}
+impl<T: Real> Path2D<T> {
+    /// The triangles covered by stroking every subpath with `style`.
+    pub fn stroke(&self, style: &StrokeStyle<T>) -> TriangleMesh2D<T> {
+        let mut stroker = Stroker::new(style);
+        for subpath in self.subpaths() {
+            if !subpath.is_empty() || subpath.closed {
+                stroker.add(&subpath.flatten(style.tolerance), subpath.closed);
+            }
+        }
+        stroker.mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::FloatConsts};
+
+    use crate::{geometry::{LineCap, LineJoin, Path2D, Segment2D, StrokeStyle, TriangleMesh2D}, vector::DVec2};
+
+    fn area(mesh: &TriangleMesh2D<f64>) -> f64 {
+        mesh.triangles().map(|triangle| triangle.area()).sum()
+    }
+    #[test]
+    fn flatten_within_tolerance() {
+        let cubic = Segment2D::cubic(DVec2::new(0.0, 0.0), DVec2::new(1.0, 3.0), DVec2::new(2.0, -3.0), DVec2::new(3.0, 0.0));
+        let coarse = cubic.flatten(0.1);
+        let fine = cubic.flatten(0.001);
+        assert!(coarse.len() < fine.len());
+        assert!(coarse.first() == Some(&DVec2::new(0.0, 0.0)) && coarse.last() == Some(&DVec2::new(3.0, 0.0)));
+        // every point on the curve is close to some point of the fine polyline
+        for i in 0..=100 {
+            let point = crate::geometry::Segment::get(&cubic, i as f64/100.0);
+            let closest = fine.windows(2).map(|line| {
+                let direction = line[1]-line[0];
+                let t = ((point-line[0]).dot(&direction)/direction.dot(&direction)).clamp(0.0, 1.0);
+                (line[0] + direction*t - point).length()
+            }).fold(f64::INFINITY, f64::min);
+            assert!(closest <= 0.001);
+        }
+        let line = Segment2D::linear(DVec2::new(0.0, 0.0), DVec2::new(3.0, 0.0));
+        assert_eq!(line.flatten(0.001).len(), 2);
+
+        // a zero tolerance is capped instead of flattening forever
+        let path: Path2D<f64> = "M0 0 A1 1 0 0 1 2 0".parse().unwrap();
+        let Some(Segment2D::Arc(arc)) = path.subpaths()[0].segments.first() else { panic!() };
+        assert_eq!(arc.flatten(0.0).len(), (1 << 16) + 1);
+        assert!(!path.stroke(&StrokeStyle::new(0.0)).indices.is_empty());
+    }
+    #[test]
+    fn stroke_shapes() {
+        let line = Segment2D::linear(DVec2::new(0.0, 0.0), DVec2::new(4.0, 0.0));
+        let butt = line.stroke(&StrokeStyle::new(2.0));
+        assert!((area(&butt) - 8.0).abs() < 1e-9);
+        let square = line.stroke(&StrokeStyle::new(2.0).with_cap(LineCap::Square));
+        assert!((area(&square) - 12.0).abs() < 1e-9);
+        let round = line.stroke(&StrokeStyle::new(2.0).with_cap(LineCap::Round).with_tolerance(1e-4));
+        assert!((area(&round) - (8.0 + f64::PI)).abs() < 1e-2);
+
+        // a closed square stroked with miters covers the ring between two squares
+        let path: Path2D<f64> = "M0 0 H4 V4 H0 Z".parse().unwrap();
+        let mitered = path.stroke(&StrokeStyle::new(2.0));
+        // the quads of the sides overlap in the corners, the miters fill the outer corners
+        assert!((area(&mitered) - (4.0*(4.0*2.0) + 4.0*1.0)).abs() < 1e-9);
+        let bevelled = path.stroke(&StrokeStyle::new(2.0).with_join(LineJoin::Bevel));
+        assert!((area(&bevelled) - (4.0*(4.0*2.0) + 4.0*0.5)).abs() < 1e-9);
+        // a miter limit below √2 turns right angles into bevels
+        let limited = path.stroke(&StrokeStyle::new(2.0).with_miter_limit(1.2));
+        assert!((area(&limited) - area(&bevelled)).abs() < 1e-9);
+
+        // 10 long line with dashes of 2 and gaps of 1, starting 1 into the pattern
+        let long = Segment2D::linear(DVec2::new(0.0, 0.0), DVec2::new(10.0, 0.0));
+        let dashed = long.stroke(&StrokeStyle::new(1.0).with_dashes(super::alloc::vec![2.0, 1.0], 1.0));
+        // dashes cover 0-1, 2-4, 5-7 and 8-10
+        assert_eq!(dashed.indices.len(), 4*6);
+        assert!((area(&dashed) - 7.0).abs() < 1e-9);
+    }
+}
@@ -141,6 +141,14 @@ impl<T: Number> Triangle2D<T> {
     pub fn new(v0: Vector2<T>, v1: Vector2<T>, v2: Vector2<T>) -> Self {
         Self { v: [v0,v1,v2] }
     }
+    /// The area of the triangle, positive when its vertices go counter clockwise.
+    pub fn signed_area(&self) -> T {
+        (self.v[1]-self.v[0]).cross(&(self.v[2]-self.v[0]))/T::from_f64(2.0)
+    }
+    pub fn area(&self) -> T
+        where T: Real {
+        self.signed_area().abs()
+    }
 }
 impl<T: Real> CalculateCentroid for Triangle2D<T> {
     type Vector = Vector2<T>;
@@ -0,0 +1,557 @@
+use core::cmp::Ordering;
+
+use affogato_core::{groups::vector_spaces::VectorSpace, sets::Real};
+
+use crate::vector::Vector2;
+
+use super::{orient2d, Polygon2D};
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// The vertices of the outline followed by the vertices of each hole, which
+/// is what the triangulation indices refer to, along with the rings as
+/// indices into it, which is empty without an outline. The outline winds counterclockwise and the holes
+/// clockwise, so the inside is always on the left.
+fn rings<T: Real>(outline: &[Vector2<T>], holes: &[&[Vector2<T>]]) -> (Vec<Vector2<T>>, Vec<Vec<u32>>) {
+    let mut points = Vec::with_capacity(outline.len() + holes.iter().map(|hole| hole.len()).sum::<usize>());
+    let mut rings = Vec::with_capacity(holes.len() + 1);
+    for (ring_index, ring) in core::iter::once(outline).chain(holes.iter().copied()).enumerate() {
+        let start = points.len() as u32;
+        points.extend_from_slice(ring);
+        let mut indices: Vec<u32> = (start..points.len() as u32).collect();
+        let area = indices.iter().zip(indices.iter().cycle().skip(1))
+            .fold(T::ZERO, |area, (&a, &b)| area + points[a as usize].cross(&points[b as usize]));
+        if (ring_index == 0) == (area < T::ZERO) {
+            indices.reverse();
+        }
+        if indices.len() >= 3 {
+            rings.push(indices);
+        } else if ring_index == 0 {
+            return (points, Vec::new());
+        }
+    }
+    (points, rings)
+}
+
+/// A vertex in the doubly linked list of the polygon being clipped.
+#[derive(Clone, Copy)]
+struct EarNode {
+    index: u32,
+    previous: usize,
+    next: usize,
+}
+
+struct EarClipper<'a, T: Real> {
+    points: &'a [Vector2<T>],
+    nodes: Vec<EarNode>,
+}
+impl<T: Real> EarClipper<'_, T> {
+    fn point(&self, node: usize) -> Vector2<T> {
+        self.points[self.nodes[node].index as usize]
+    }
+    /// Adds a ring as a circular list, giving the first node.
+    fn add_ring(&mut self, ring: &[u32]) -> usize {
+        let start = self.nodes.len();
+        for (i, &index) in ring.iter().enumerate() {
+            self.nodes.push(EarNode {
+                index,
+                previous: start + (i + ring.len() - 1)%ring.len(),
+                next: start + (i + 1)%ring.len(),
+            });
+        }
+        start
+    }
+    fn remove(&mut self, node: usize) {
+        let EarNode { previous, next, .. } = self.nodes[node];
+        self.nodes[previous].next = next;
+        self.nodes[next].previous = previous;
+    }
+    /// Connects a hole to the outline through a pair of coincident edges,
+    /// turning both into a single ring. The bridge goes from the rightmost
+    /// vertex of the hole to a vertex of the outline it can see, found as in
+    /// "Triangulation by Ear Clipping" by David Eberly.
+    fn bridge(&mut self, outline: usize, hole: usize) {
+        let mut rightmost = hole;
+        let mut node = self.nodes[hole].next;
+        while node != hole {
+            if self.point(node).x() > self.point(rightmost).x() {
+                rightmost = node;
+            }
+            node = self.nodes[node].next;
+        }
+        let m = self.point(rightmost);
+        // cast a ray to the right from the hole, the closest edge it hits
+        // has a vertex that can almost always be seen
+        let mut closest = T::INFINITY;
+        let mut visible = None;
+        let mut node = outline;
+        loop {
+            let next = self.nodes[node].next;
+            let (a, b) = (self.point(node), self.point(next));
+            if (a.y() <= m.y() && m.y() <= b.y()) || (b.y() <= m.y() && m.y() <= a.y()) {
+                let x = if a.y() == b.y() {
+                    a.x().min(b.x())
+                } else {
+                    a.x() + (m.y() - a.y())*(b.x() - a.x())/(b.y() - a.y())
+                };
+                if x >= m.x() && x < closest {
+                    closest = x;
+                    visible = Some(if a.x() > b.x() { node } else { next });
+                }
+            }
+            node = next;
+            if node == outline {
+                break;
+            }
+        }
+        let Some(mut visible) = visible else {
+            return;
+        };
+        // a reflex vertex inside the triangle between the hole, the hit and the
+        // vertex can block the view, the one closest in angle to the ray can't be
+        let hit = Vector2::new(closest, m.y());
+        let p = self.point(visible);
+        let (a, b, c) = if m.y() < p.y() { (m, hit, p) } else { (m, p, hit) };
+        let mut best_tangent = T::INFINITY;
+        let mut node = outline;
+        loop {
+            let point = self.point(node);
+            let reflex = orient2d(self.point(self.nodes[node].previous), point, self.point(self.nodes[node].next)) < T::ZERO;
+            if node != visible && point != m && reflex && point_in_triangle(a, b, c, point) {
+                let tangent = (m.y() - point.y()).abs()/(point.x() - m.x());
+                if tangent < best_tangent || (tangent == best_tangent && point.x() > self.point(visible).x()) {
+                    best_tangent = tangent;
+                    visible = node;
+                }
+            }
+            node = self.nodes[node].next;
+            if node == outline {
+                break;
+            }
+        }
+        let hole_previous = self.nodes[rightmost].previous;
+        let visible_next = self.nodes[visible].next;
+        let hole_copy = self.nodes.len();
+        let visible_copy = hole_copy + 1;
+        self.nodes.push(EarNode { index: self.nodes[rightmost].index, previous: hole_previous, next: visible_copy });
+        self.nodes.push(EarNode { index: self.nodes[visible].index, previous: hole_copy, next: visible_next });
+        self.nodes[hole_previous].next = hole_copy;
+        self.nodes[visible_next].previous = visible_copy;
+        self.nodes[visible].next = rightmost;
+        self.nodes[rightmost].previous = visible;
+    }
+    /// Whether the corner at `node` can be cut off without crossing the rest
+    /// of the polygon.
+    fn is_ear(&self, node: usize) -> bool {
+        let EarNode { previous, next, .. } = self.nodes[node];
+        let (a, b, c) = (self.point(previous), self.point(node), self.point(next));
+        if orient2d(a, b, c) <= T::ZERO {
+            return false;
+        }
+        let mut other = self.nodes[next].next;
+        while other != previous {
+            let point = self.point(other);
+            // bridges put the same point in the list twice
+            if point != a && point != b && point != c && point_in_triangle(a, b, c, point) {
+                return false;
+            }
+            other = self.nodes[other].next;
+        }
+        true
+    }
+    fn clip(&mut self, start: usize, count: usize, triangles: &mut Vec<u32>) {
+        let mut node = start;
+        let mut remaining = count;
+        let mut since_last_ear = 0;
+        while remaining > 3 {
+            let EarNode { previous, next, index } = self.nodes[node];
+            let (a, b, c) = (self.point(previous), self.point(node), self.point(next));
+            let area = orient2d(a, b, c);
+            if self.is_ear(node) {
+                triangles.extend_from_slice(&[self.nodes[previous].index, index, self.nodes[next].index]);
+            } else if area != T::ZERO && since_last_ear < remaining {
+                node = next;
+                since_last_ear += 1;
+                continue;
+            }
+            // collinear corners are dropped, and when no ear is left because
+            // the input wasn't simple the corner is cut anyway
+            if area > T::ZERO && !self.is_ear(node) {
+                triangles.extend_from_slice(&[self.nodes[previous].index, index, self.nodes[next].index]);
+            }
+            self.remove(node);
+            remaining -= 1;
+            since_last_ear = 0;
+            node = next;
+        }
+        let EarNode { previous, next, index } = self.nodes[node];
+        if orient2d(self.point(previous), self.point(node), self.point(next)) != T::ZERO {
+            triangles.extend_from_slice(&[self.nodes[previous].index, index, self.nodes[next].index]);
+        }
+    }
+}
+
+/// Whether `point` is inside or on the edge of the counterclockwise triangle.
+fn point_in_triangle<T: Real>(a: Vector2<T>, b: Vector2<T>, c: Vector2<T>, point: Vector2<T>) -> bool {
+    orient2d(a, b, point) >= T::ZERO && orient2d(b, c, point) >= T::ZERO && orient2d(c, a, point) >= T::ZERO
+}
+
+/// Triangulates a simple polygon with holes by repeatedly cutting off ears,
+/// which are corners whose triangle has nothing else inside. Each hole is
+/// first joined to the outline by a bridge of two coincident edges.
+///
+/// The indices refer to the vertices of `outline` followed by the vertices
+/// of each hole in order, and every triangle winds counterclockwise. This is
+/// quadratic in the amount of vertices, [`triangulate_monotone`] is faster for
+/// large polygons.
+pub fn triangulate_ear_clipping<T: Real>(outline: &[Vector2<T>], holes: &[&[Vector2<T>]]) -> Vec<u32> {
+    let (points, rings) = rings(outline, holes);
+    let mut triangles = Vec::new();
+    if rings.is_empty() {
+        return triangles;
+    }
+    let mut clipper = EarClipper { points: &points, nodes: Vec::new() };
+    let outline = clipper.add_ring(&rings[0]);
+    let mut holes: Vec<(T, usize)> = rings[1..].iter().map(|ring| {
+        let rightmost = ring.iter().map(|&index| points[index as usize].x()).fold(-T::INFINITY, |max, x| max.max(x));
+        (rightmost, clipper.add_ring(ring))
+    }).collect();
+    // holes further right are bridged first so later bridges can't cross them
+    holes.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+    for &(_, hole) in holes.iter() {
+        clipper.bridge(outline, hole);
+    }
+    let mut count = 1;
+    let mut node = clipper.nodes[outline].next;
+    while node != outline {
+        count += 1;
+        node = clipper.nodes[node].next;
+    }
+    clipper.clip(outline, count, &mut triangles);
+    triangles
+}
+
+/// Whether `a` comes before `b` when sweeping from the top down, ties are
+/// broken from left to right.
+fn is_above<T: Real>(a: Vector2<T>, b: Vector2<T>) -> bool {
+    a.y() > b.y() || (a.y() == b.y() && a.x() < b.x())
+}
+
+fn sweep_order<T: Real>(a: Vector2<T>, b: Vector2<T>) -> Ordering {
+    if is_above(a, b) {
+        Ordering::Less
+    } else if is_above(b, a) {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VertexKind {
+    Start,
+    End,
+    Split,
+    Merge,
+    Regular,
+}
+
+/// Splits polygons into pieces that are monotone along the y axis by adding
+/// diagonals, following chapter 3 of "Computational Geometry: Algorithms and
+/// Applications".
+struct MonotoneSplitter<'a, T: Real> {
+    points: &'a [Vector2<T>],
+    previous: Vec<u32>,
+    next: Vec<u32>,
+    /// The edges crossing the sweep line, stored by the vertex they start
+    /// at, along with their helper.
+    status: Vec<(u32, u32)>,
+    kinds: Vec<VertexKind>,
+    diagonals: Vec<(u32, u32)>,
+}
+impl<T: Real> MonotoneSplitter<'_, T> {
+    fn point(&self, index: u32) -> Vector2<T> {
+        self.points[index as usize]
+    }
+    /// Where the edge starting at `edge` crosses the horizontal line at `y`.
+    fn edge_x(&self, edge: u32, y: T) -> T {
+        let (a, b) = (self.point(edge), self.point(self.next[edge as usize]));
+        if a.y() == b.y() {
+            return a.x().max(b.x());
+        }
+        a.x() + (y - a.y())*(b.x() - a.x())/(b.y() - a.y())
+    }
+    /// The position in the status of the edge directly left of the vertex.
+    fn left_of(&self, vertex: u32) -> Option<usize> {
+        let point = self.point(vertex);
+        let mut closest: Option<(T, usize)> = None;
+        for (position, &(edge, _)) in self.status.iter().enumerate() {
+            let x = self.edge_x(edge, point.y());
+            if x <= point.x() && closest.is_none_or(|(closest, _)| x > closest) {
+                closest = Some((x, position));
+            }
+        }
+        closest.map(|(_, position)| position)
+    }
+    fn remove_edge(&mut self, edge: u32) -> Option<u32> {
+        let position = self.status.iter().position(|&(other, _)| other == edge)?;
+        Some(self.status.swap_remove(position).1)
+    }
+    fn connect_to_merge_helper(&mut self, vertex: u32, helper: Option<u32>) {
+        if let Some(helper) = helper.filter(|&helper| self.kinds[helper as usize] == VertexKind::Merge) {
+            self.diagonals.push((vertex, helper));
+        }
+    }
+    fn handle(&mut self, vertex: u32) {
+        let previous = self.previous[vertex as usize];
+        match self.kinds[vertex as usize] {
+            VertexKind::Start => self.status.push((vertex, vertex)),
+            VertexKind::End => {
+                let helper = self.remove_edge(previous);
+                self.connect_to_merge_helper(vertex, helper);
+            },
+            VertexKind::Split => {
+                if let Some(left) = self.left_of(vertex) {
+                    self.diagonals.push((vertex, self.status[left].1));
+                    self.status[left].1 = vertex;
+                }
+                self.status.push((vertex, vertex));
+            },
+            VertexKind::Merge => {
+                let helper = self.remove_edge(previous);
+                self.connect_to_merge_helper(vertex, helper);
+                if let Some(left) = self.left_of(vertex) {
+                    let helper = self.status[left].1;
+                    self.connect_to_merge_helper(vertex, Some(helper));
+                    self.status[left].1 = vertex;
+                }
+            },
+            VertexKind::Regular => {
+                // on the left side of the polygon the inside is to the right
+                if is_above(self.point(previous), self.point(vertex)) {
+                    let helper = self.remove_edge(previous);
+                    self.connect_to_merge_helper(vertex, helper);
+                    self.status.push((vertex, vertex));
+                } else if let Some(left) = self.left_of(vertex) {
+                    let helper = self.status[left].1;
+                    self.connect_to_merge_helper(vertex, Some(helper));
+                    self.status[left].1 = vertex;
+                }
+            },
+        }
+    }
+}
+
+/// Walks the faces made by the rings and diagonals, each of which is a y
+/// monotone piece of the polygon winding counterclockwise.
+fn monotone_pieces<T: Real>(points: &[Vector2<T>], next: &[u32], diagonals: &[(u32, u32)]) -> Vec<Vec<u32>> {
+    let mut outgoing: Vec<Vec<u32>> = alloc::vec![Vec::new(); points.len()];
+    for (from, &to) in next.iter().enumerate() {
+        if to != u32::MAX {
+            outgoing[from].push(to);
+        }
+    }
+    for &(a, b) in diagonals {
+        outgoing[a as usize].push(b);
+        outgoing[b as usize].push(a);
+    }
+    let mut used: Vec<Vec<bool>> = outgoing.iter().map(|edges| alloc::vec![false; edges.len()]).collect();
+    let mut pieces = Vec::new();
+    for start in 0..points.len() {
+        for first in 0..outgoing[start].len() {
+            if used[start][first] {
+                continue;
+            }
+            let mut piece = Vec::new();
+            let (mut from, mut edge) = (start, first);
+            while !used[from][edge] {
+                used[from][edge] = true;
+                piece.push(from as u32);
+                let to = outgoing[from][edge] as usize;
+                // the next edge of the face is the first one clockwise from
+                // the way back, keeping the inside on the left
+                let back = points[from] - points[to];
+                let clockwise_angle = |other: u32| {
+                    let direction = points[other as usize] - points[to];
+                    let angle = direction.cross(&back).atan2(direction.dot(&back));
+                    if angle <= T::ZERO { angle + T::TAU } else { angle }
+                };
+                let next_edge = (0..outgoing[to].len()).fold(None, |best: Option<(T, usize)>, candidate| {
+                    let angle = clockwise_angle(outgoing[to][candidate]);
+                    match best {
+                        Some((best_angle, _)) if best_angle <= angle => best,
+                        _ => Some((angle, candidate)),
+                    }
+                });
+                let Some((_, next_edge)) = next_edge else {
+                    break;
+                };
+                (from, edge) = (to, next_edge);
+            }
+            pieces.push(piece);
+        }
+    }
+    pieces
+}
+
+/// Triangulates a counterclockwise y monotone polygon with the stack
+/// algorithm.
+fn triangulate_monotone_piece<T: Real>(points: &[Vector2<T>], piece: &[u32], triangles: &mut Vec<u32>) {
+    if piece.len() < 3 {
+        return;
+    }
+    let point = |index: u32| points[index as usize];
+    let mut push = |a: u32, b: u32, c: u32| {
+        let area = orient2d(point(a), point(b), point(c));
+        if area > T::ZERO {
+            triangles.extend_from_slice(&[a, b, c]);
+        } else if area < T::ZERO {
+            triangles.extend_from_slice(&[a, c, b]);
+        }
+    };
+    let top = (0..piece.len()).min_by(|&a, &b| sweep_order(point(piece[a]), point(piece[b]))).unwrap_or(0);
+    let bottom = (0..piece.len()).max_by(|&a, &b| sweep_order(point(piece[a]), point(piece[b]))).unwrap_or(0);
+    // going forward from the top descends the left side of the piece
+    let mut on_left = alloc::vec![false; piece.len()];
+    let mut position = top;
+    while position != bottom {
+        on_left[position] = true;
+        position = (position + 1)%piece.len();
+    }
+    let mut order: Vec<usize> = (0..piece.len()).collect();
+    order.sort_by(|&a, &b| sweep_order(point(piece[a]), point(piece[b])));
+    let mut stack = alloc::vec![order[0], order[1]];
+    for &current in &order[2..order.len()-1] {
+        let top_of_stack = stack[stack.len()-1];
+        if on_left[current] != on_left[top_of_stack] {
+            while stack.len() > 1 {
+                let popped = stack.pop().unwrap_or(current);
+                push(piece[current], piece[popped], piece[stack[stack.len()-1]]);
+            }
+            stack.clear();
+            stack.push(top_of_stack);
+            stack.push(current);
+        } else {
+            let mut last = stack.pop().unwrap_or(current);
+            while let Some(&next) = stack.last() {
+                let turn = orient2d(point(piece[next]), point(piece[last]), point(piece[current]));
+                let inside = if on_left[current] { turn > T::ZERO } else { turn < T::ZERO };
+                if !inside {
+                    break;
+                }
+                push(piece[current], piece[last], piece[next]);
+                last = next;
+                stack.pop();
+            }
+            stack.push(last);
+            stack.push(current);
+        }
+    }
+    let bottom = order[order.len()-1];
+    for pair in stack.windows(2) {
+        push(piece[bottom], piece[pair[1]], piece[pair[0]]);
+    }
+}
+
+/// Triangulates a simple polygon with holes by splitting it into y monotone
+/// pieces with a sweep line, then triangulating each piece in linear time.
+/// This takes O(n²) time in the worst case due to the simple sweep status,
+/// but is close to O(n log n) for typical shapes like text outlines.
+///
+/// The indices refer to the vertices of `outline` followed by the vertices
+/// of each hole in order, and every triangle winds counterclockwise.
+pub fn triangulate_monotone<T: Real>(outline: &[Vector2<T>], holes: &[&[Vector2<T>]]) -> Vec<u32> {
+    let (points, rings) = rings(outline, holes);
+    let mut triangles = Vec::new();
+    if rings.is_empty() {
+        return triangles;
+    }
+    let mut previous = alloc::vec![u32::MAX; points.len()];
+    let mut next = alloc::vec![u32::MAX; points.len()];
+    for ring in rings.iter() {
+        for (i, &index) in ring.iter().enumerate() {
+            previous[index as usize] = ring[(i + ring.len() - 1)%ring.len()];
+            next[index as usize] = ring[(i + 1)%ring.len()];
+        }
+    }
+    let kinds = (0..points.len()).map(|index| {
+        if next[index] == u32::MAX {
+            return VertexKind::Regular;
+        }
+        let (before, point, after) = (points[previous[index] as usize], points[index], points[next[index] as usize]);
+        let convex = orient2d(before, point, after) > T::ZERO;
+        match (is_above(point, before), is_above(point, after)) {
+            (true, true) if convex => VertexKind::Start,
+            (true, true) => VertexKind::Split,
+            (false, false) if convex => VertexKind::End,
+            (false, false) => VertexKind::Merge,
+            _ => VertexKind::Regular,
+        }
+    }).collect();
+    let mut splitter = MonotoneSplitter { points: &points, previous, next, status: Vec::new(), kinds, diagonals: Vec::new() };
+    let mut order: Vec<u32> = rings.iter().flatten().copied().collect();
+    order.sort_by(|&a, &b| sweep_order(points[a as usize], points[b as usize]));
+    for &vertex in order.iter() {
+        splitter.handle(vertex);
+    }
+    for piece in monotone_pieces(&points, &splitter.next, &splitter.diagonals) {
+        triangulate_monotone_piece(&points, &piece, &mut triangles);
+    }
+    triangles
+}
+
+impl<T: Real> Polygon2D<T> {
+    /// Triangle indices covering the polygon, see [`triangulate_monotone`].
+    pub fn triangulate(&self) -> Vec<u32> {
+        triangulate_monotone(&self.vertices, &[])
+    }
+    /// Triangle indices covering the polygon without its holes, indexing the
+    /// vertices of the polygon followed by the vertices of each hole.
+    pub fn triangulate_with_holes(&self, holes: &[Polygon2D<T>]) -> Vec<u32> {
+        let holes: Vec<&[Vector2<T>]> = holes.iter().map(|hole| hole.vertices.as_slice()).collect();
+        triangulate_monotone(&self.vertices, &holes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::{triangulate_ear_clipping, triangulate_monotone, Triangle2D}, vector::DVec2};
+
+    use super::alloc::vec::Vec;
+
+    /// Collinear vertices can be left out, so there can be fewer triangles
+    /// than the vertices would need.
+    fn check(points: &[DVec2], triangles: &[u32], expected_area: f64, max_count: usize) {
+        assert!(triangles.len() <= max_count*3);
+        let mut area = 0.0;
+        for triangle in triangles.chunks_exact(3) {
+            let triangle = Triangle2D::new(points[triangle[0] as usize], points[triangle[1] as usize], points[triangle[2] as usize]);
+            assert!(triangle.signed_area() > 0.0);
+            area += triangle.signed_area();
+        }
+        assert!((area - expected_area).abs() < 1e-9);
+    }
+
+    #[test]
+    fn triangulate_polygons() {
+        // a comb has split and merge vertices for the sweep
+        let comb = [
+            DVec2::new(0.0, 0.0), DVec2::new(5.0, 0.0), DVec2::new(5.0, 3.0), DVec2::new(4.0, 3.0),
+            DVec2::new(4.0, 1.0), DVec2::new(3.0, 1.0), DVec2::new(3.0, 3.0), DVec2::new(2.0, 3.0),
+            DVec2::new(2.0, 1.0), DVec2::new(1.0, 1.0), DVec2::new(1.0, 3.0), DVec2::new(0.0, 3.0),
+        ];
+        check(&comb, &triangulate_ear_clipping(&comb, &[]), 11.0, 10);
+        check(&comb, &triangulate_monotone(&comb, &[]), 11.0, 10);
+        let upside_down: Vec<DVec2> = comb.iter().map(|point| DVec2::new(point.x(), -point.y())).collect();
+        check(&upside_down, &triangulate_monotone(&upside_down, &[]), 11.0, 10);
+
+        // a square with two square holes, the outline wound clockwise
+        let outline = [DVec2::new(0.0, 0.0), DVec2::new(0.0, 4.0), DVec2::new(6.0, 4.0), DVec2::new(6.0, 0.0)];
+        let hole1 = [DVec2::new(1.0, 1.0), DVec2::new(2.0, 1.0), DVec2::new(2.0, 3.0), DVec2::new(1.0, 3.0)];
+        let hole2 = [DVec2::new(4.0, 1.0), DVec2::new(5.0, 2.0), DVec2::new(4.0, 3.0)];
+        let points: Vec<DVec2> = outline.iter().chain(hole1.iter()).chain(hole2.iter()).copied().collect();
+        let holes: [&[DVec2]; 2] = [&hole1, &hole2];
+        check(&points, &triangulate_ear_clipping(&outline, &holes), 21.0, 15);
+        check(&points, &triangulate_monotone(&outline, &holes), 21.0, 15);
+    }
+}
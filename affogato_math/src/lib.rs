@@ -9,6 +9,8 @@ pub mod vector;
 pub mod mappings;
 pub mod geometry;
 pub mod transformations;
+#[cfg(feature="alloc")]
+pub mod scene;
 pub use animation::*;
 pub use transformations::*;
 pub use util::*;
@@ -0,0 +1,237 @@
+use affogato_core::{num::{Number, Zero}, sets::Real};
+use bytemuck::{Pod, Zeroable};
+
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{matrix::{Matrix2, Matrix3, Matrix4, SquareMatrix}, vector::{Vector2, Vector3, Vector4}, Transform2D, Transform3D};
+
+/// An affine transformation of the plane, a 2x2 linear part followed by a
+/// translation. It's the same as a [`Matrix3`] whose last row is `0, 0, 1`
+/// without storing or multiplying that row.
+#[repr(C)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine2<T: Number> {
+    pub linear: Matrix2<T>,
+    pub translation: Vector2<T>,
+}
+impl<T: Number> Affine2<T> {
+    pub const fn new(linear: Matrix2<T>, translation: Vector2<T>) -> Self {
+        Self { linear, translation }
+    }
+    pub fn identity() -> Self {
+        Self::new(Matrix2::identity(), Vector2::ZERO)
+    }
+    pub fn from_translation(translation: Vector2<T>) -> Self {
+        Self::new(Matrix2::identity(), translation)
+    }
+    pub fn from_scale(scale: Vector2<T>) -> Self {
+        Self::new(Matrix2::diagonal(scale), Vector2::ZERO)
+    }
+    pub fn transform_point(&self, point: Vector2<T>) -> Vector2<T> {
+        self.linear*point + self.translation
+    }
+    /// Transforms a direction or an offset, which the translation doesn't
+    /// move.
+    pub fn transform_vector(&self, vector: Vector2<T>) -> Vector2<T> {
+        self.linear*vector
+    }
+    pub fn to_matrix(&self) -> Matrix3<T> {
+        Matrix3::from_vec(
+            Vector3::new(self.linear.x.x(), self.linear.x.y(), T::ZERO),
+            Vector3::new(self.linear.y.x(), self.linear.y.y(), T::ZERO),
+            Vector3::new(self.translation.x(), self.translation.y(), T::ONE),
+        )
+    }
+}
+impl<T: Real> Affine2<T> {
+    /// Scales, then rotates by `rotation` radians and then translates.
+    pub fn from_transform(translation: Vector2<T>, rotation: T, scale: Vector2<T>) -> Self {
+        Self::new(Matrix2::from_rotation(rotation)*Matrix2::diagonal(scale), translation)
+    }
+    /// Inverts the linear part and moves the translation back through it,
+    /// which is much less work than inverting the whole [`Matrix3`]. Returns
+    /// [`None`] when the linear part can't be inverted.
+    pub fn inverse(&self) -> Option<Self> {
+        let linear = self.linear.inverse()?;
+        Some(Self::new(linear, -(linear*self.translation)))
+    }
+}
+impl<T: Number> Default for Affine2<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+/// Applies `rhs` first and then `self`, like multiplying the matrices.
+impl<T: Number> core::ops::Mul for Affine2<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(self.linear*rhs.linear, self.linear*rhs.translation + self.translation)
+    }
+}
+impl<T: Number> From<Affine2<T>> for Matrix3<T> {
+    fn from(value: Affine2<T>) -> Self {
+        value.to_matrix()
+    }
+}
+impl<T: Real> From<Transform2D<T>> for Affine2<T> {
+    fn from(value: Transform2D<T>) -> Self {
+        Self::from_transform(value.position, value.rotation, value.scale)
+    }
+}
+unsafe impl<T: Number> Zeroable for Affine2<T> {
+    fn zeroed() -> Self {
+        Self::new(Matrix2::ZERO, Vector2::ZERO)
+    }
+}
+unsafe impl<T: Number + Pod> Pod for Affine2<T> {}
+
+/// An affine transformation of space, a 3x3 linear part followed by a
+/// translation. It's the same as a [`Matrix4`] whose last row is
+/// `0, 0, 0, 1` without storing or multiplying that row.
+#[repr(C)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine3<T: Number> {
+    pub linear: Matrix3<T>,
+    pub translation: Vector3<T>,
+}
+impl<T: Number> Affine3<T> {
+    pub const fn new(linear: Matrix3<T>, translation: Vector3<T>) -> Self {
+        Self { linear, translation }
+    }
+    pub fn identity() -> Self {
+        Self::new(Matrix3::identity(), Vector3::ZERO)
+    }
+    pub fn from_translation(translation: Vector3<T>) -> Self {
+        Self::new(Matrix3::identity(), translation)
+    }
+    pub fn from_scale(scale: Vector3<T>) -> Self {
+        Self::new(Matrix3::diagonal(scale), Vector3::ZERO)
+    }
+    pub fn transform_point(&self, point: Vector3<T>) -> Vector3<T> {
+        self.linear*point + self.translation
+    }
+    /// Transforms a direction or an offset, which the translation doesn't
+    /// move.
+    pub fn transform_vector(&self, vector: Vector3<T>) -> Vector3<T> {
+        self.linear*vector
+    }
+    pub fn to_matrix(&self) -> Matrix4<T> {
+        let mut matrix = Matrix4::from(self.linear);
+        matrix.w = Vector4::from(self.translation);
+        matrix
+    }
+}
+impl<T: Real> Affine3<T> {
+    /// Inverts the linear part and moves the translation back through it,
+    /// which is much less work than inverting the whole [`Matrix4`]. Returns
+    /// [`None`] when the linear part can't be inverted.
+    pub fn inverse(&self) -> Option<Self> {
+        let linear = self.linear.inverse()?;
+        Some(Self::new(linear, -(linear*self.translation)))
+    }
+}
+impl<T: Number> Default for Affine3<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+/// Applies `rhs` first and then `self`, like multiplying the matrices.
+impl<T: Number> core::ops::Mul for Affine3<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(self.linear*rhs.linear, self.linear*rhs.translation + self.translation)
+    }
+}
+impl<T: Number> From<Affine3<T>> for Matrix4<T> {
+    fn from(value: Affine3<T>) -> Self {
+        value.to_matrix()
+    }
+}
+impl<T: Real> From<Transform3D<T>> for Affine3<T> {
+    fn from(value: Transform3D<T>) -> Self {
+        Self::new(Matrix3::from(value.rotation)*Matrix3::diagonal(value.scale), value.position)
+    }
+}
+unsafe impl<T: Number> Zeroable for Affine3<T> {
+    fn zeroed() -> Self {
+        Self::new(Matrix3::ZERO, Vector3::ZERO)
+    }
+}
+unsafe impl<T: Number + Pod> Pod for Affine3<T> {}
+
+/// An [`Affine3`] stored as three rows of four values for uploading to the
+/// gpu. Every row is a full `vec4`, so there is no padding under std140 or
+/// std430, and a shader gets the transformed point of `p` as
+/// `vec3(dot(rows[0], p), dot(rows[1], p), dot(rows[2], p))` with
+/// `p = vec4(point, 1.0)`.
+#[repr(C)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine3x4<T: Number> {
+    pub rows: [Vector4<T>; 3],
+}
+impl<T: Number> From<Affine3<T>> for Affine3x4<T> {
+    fn from(value: Affine3<T>) -> Self {
+        let (linear, translation) = (value.linear, value.translation);
+        Self { rows: [
+            Vector4::new(linear.x.x(), linear.y.x(), linear.z.x(), translation.x()),
+            Vector4::new(linear.x.y(), linear.y.y(), linear.z.y(), translation.y()),
+            Vector4::new(linear.x.z(), linear.y.z(), linear.z.z(), translation.z()),
+        ] }
+    }
+}
+impl<T: Number> From<Affine3x4<T>> for Affine3<T> {
+    fn from(value: Affine3x4<T>) -> Self {
+        let [x, y, z] = value.rows;
+        Self::new(
+            Matrix3::new(x.x(), y.x(), z.x(), x.y(), y.y(), z.y(), x.z(), y.z(), z.z()),
+            Vector3::new(x.w(), y.w(), z.w()),
+        )
+    }
+}
+unsafe impl<T: Number> Zeroable for Affine3x4<T> {
+    fn zeroed() -> Self {
+        Self { rows: [Vector4::ZERO; 3] }
+    }
+}
+unsafe impl<T: Number + Pod> Pod for Affine3x4<T> {}
+
+#[cfg(test)]
+mod tests {
+    use affogato_core::groups::vector_spaces::{NormedVectorSpace, VectorSpace};
+
+    use crate::{algebra::Quaternion, matrix::Matrix2, vector::{DVec2, DVec3, DVec4, FVec3, FVec4}, Transform2D, Transform3D, Transformation};
+
+    use super::{Affine2, Affine3, Affine3x4};
+
+    #[test]
+    fn affine_transforms() {
+        let transform = Transform2D::new(DVec2::new(1.0, -2.0), 0.4, DVec2::new(2.0, 0.5));
+        let affine = Affine2::from(transform);
+        let point = DVec2::new(3.0, 1.5);
+        assert!((affine.transform_point(point) - transform.transform_point(point)).length() < 1e-12);
+        assert!((affine.transform_vector(point) - transform.transform_direction(point)).length() < 1e-12);
+        let moved = affine.to_matrix()*DVec3::new(point.x(), point.y(), 1.0);
+        assert!((DVec2::new(moved.x(), moved.y()) - affine.transform_point(point)).length() < 1e-12);
+        assert!(((affine.inverse().unwrap()*affine).transform_point(point) - point).length() < 1e-12);
+        assert!(Affine2::new(Matrix2::new(1.0, 2.0, 2.0, 4.0), DVec2::new(0.0, 0.0)).inverse().is_none());
+
+        let a = Affine3::from(Transform3D::new(DVec3::new(1.0, 2.0, 3.0), Quaternion::angle_axis(0.8, DVec3::new(0.0, 0.6, 0.8)), DVec3::new(2.0, 1.0, 3.0)));
+        let b = Affine3::from_translation(DVec3::new(-4.0, 0.0, 1.0))*Affine3::from_scale(DVec3::new(1.0, 2.0, 1.0));
+        let point = DVec3::new(0.3, -1.0, 2.0);
+        assert!(((a*b).transform_point(point) - a.transform_point(b.transform_point(point))).length() < 1e-12);
+        assert!(((a*b).to_matrix()*DVec4::from(point)).xyz().epsilon_eq(a.transform_point(b.transform_point(point)), 1e-12));
+        assert!(((a.inverse().unwrap()*a).transform_point(point) - point).length() < 1e-12);
+
+        let rows = Affine3x4::from(a);
+        let p = DVec4::from(point);
+        assert!((DVec3::new(rows.rows[0].dot(&p), rows.rows[1].dot(&p), rows.rows[2].dot(&p)) - a.transform_point(point)).length() < 1e-12);
+        assert_eq!(Affine3::from(rows), a);
+        let gpu = Affine3x4::<f32>::from(Affine3::from_translation(FVec3::new(1.0, 2.0, 3.0)));
+        assert_eq!(bytemuck::bytes_of(&gpu).len(), 48);
+        assert_eq!(bytemuck::cast::<_, [FVec4; 3]>(gpu)[1], FVec4::new(0.0, 1.0, 0.0, 2.0));
+    }
+}
@@ -10,9 +10,11 @@ use crate::{algebra::Quaternion, vector::{Vector2, Vector3, Vector4}};
 mod mat2;
 mod mat3;
 mod mat4;
+mod affine;
 pub use mat2::*;
 pub use mat3::*;
 pub use mat4::*;
+pub use affine::*;
 
 pub trait SquareMatrix: Sized {
     type Column: VectorSpace;
@@ -0,0 +1,338 @@
+extern crate alloc;
+use alloc::vec::Vec;
+use core::cell::Cell;
+
+use affogato_core::{groups::vector_spaces::VectorSpace, sets::Real};
+
+use crate::{matrix::SquareMatrix, Transform2D, Transform3D, Transformation};
+
+/// A handle to a node in a [`SceneGraph`]. Handles of removed nodes stay
+/// invalid even when their slot is reused by a new node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    index: usize,
+    generation: usize,
+}
+
+struct Node<Tr: Transformation> {
+    local: Tr,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    /// The world matrix as of the last time it was asked for, only valid
+    /// while `dirty` is false. A clean node always has a clean parent, so
+    /// marking a node dirty can stop at nodes that already are.
+    world: Cell<Tr::Matrix>,
+    dirty: Cell<bool>,
+}
+
+struct Slot<Tr: Transformation> {
+    generation: usize,
+    node: Option<Node<Tr>>,
+}
+
+/// A hierarchy of nodes that each hold a transformation relative to their
+/// parent. World matrices are cached, and only worked out again for nodes
+/// whose transformation, or one of whose ancestors' transformation, changed.
+pub struct SceneGraph<Tr: Transformation> {
+    slots: Vec<Slot<Tr>>,
+    free: Vec<usize>,
+    roots: Vec<NodeId>,
+}
+pub type SceneGraph2D<T> = SceneGraph<Transform2D<T>>;
+pub type SceneGraph3D<T> = SceneGraph<Transform3D<T>>;
+
+impl<Tr: Transformation> Default for SceneGraph<Tr> {
+    fn default() -> Self {
+        Self { slots: Vec::new(), free: Vec::new(), roots: Vec::new() }
+    }
+}
+
+impl<Tr: Transformation> SceneGraph<Tr>
+    where Tr::Matrix: SquareMatrix + Copy + core::ops::Mul<Output = Tr::Matrix> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.node(id).is_some()
+    }
+    fn node(&self, id: NodeId) -> Option<&Node<Tr>> {
+        self.slots.get(id.index).filter(|slot| slot.generation == id.generation)?.node.as_ref()
+    }
+    fn node_mut(&mut self, id: NodeId) -> Option<&mut Node<Tr>> {
+        self.slots.get_mut(id.index).filter(|slot| slot.generation == id.generation)?.node.as_mut()
+    }
+    fn insert(&mut self, local: Tr, parent: Option<NodeId>) -> NodeId {
+        let node = Node { local, parent, children: Vec::new(), world: Cell::new(Tr::Matrix::identity()), dirty: Cell::new(true) };
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index];
+            slot.node = Some(node);
+            NodeId { index, generation: slot.generation }
+        } else {
+            self.slots.push(Slot { generation: 0, node: Some(node) });
+            NodeId { index: self.slots.len() - 1, generation: 0 }
+        }
+    }
+    /// Adds a node without a parent, its local transformation is its world
+    /// transformation.
+    pub fn add_root(&mut self, local: Tr) -> NodeId {
+        let id = self.insert(local, None);
+        self.roots.push(id);
+        id
+    }
+    /// Adds a node under `parent`, or returns [`None`] when `parent` isn't in
+    /// the graph.
+    pub fn add_child(&mut self, parent: NodeId, local: Tr) -> Option<NodeId> {
+        if !self.contains(parent) {
+            return None;
+        }
+        let id = self.insert(local, Some(parent));
+        self.node_mut(parent)?.children.push(id);
+        Some(id)
+    }
+    /// Removes `id` along with everything under it, giving back the local
+    /// transformation of `id`.
+    pub fn remove(&mut self, id: NodeId) -> Option<Tr> {
+        let parent = self.node(id)?.parent;
+        self.detach(id, parent);
+        let mut removed = None;
+        let mut stack = alloc::vec![id];
+        while let Some(current) = stack.pop() {
+            let slot = &mut self.slots[current.index];
+            let node = slot.node.take()?;
+            slot.generation += 1;
+            self.free.push(current.index);
+            stack.extend(node.children);
+            if current == id {
+                removed = Some(node.local);
+            }
+        }
+        removed
+    }
+    fn detach(&mut self, id: NodeId, parent: Option<NodeId>) {
+        let siblings = match parent {
+            Some(parent) => match self.node_mut(parent) {
+                Some(node) => &mut node.children,
+                None => return,
+            },
+            None => &mut self.roots,
+        };
+        siblings.retain(|&sibling| sibling != id);
+    }
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id)?.parent
+    }
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        self.node(id).map_or(&[], |node| &node.children)
+    }
+    pub fn local(&self, id: NodeId) -> Option<&Tr> {
+        Some(&self.node(id)?.local)
+    }
+    /// The local transformation of `id` to change. Everything under `id` has
+    /// its world matrix worked out again the next time it is asked for.
+    pub fn local_mut(&mut self, id: NodeId) -> Option<&mut Tr> {
+        self.node(id)?;
+        self.mark_dirty(id);
+        Some(&mut self.node_mut(id)?.local)
+    }
+    pub fn set_local(&mut self, id: NodeId, local: Tr) -> bool {
+        match self.local_mut(id) {
+            Some(current) => {
+                *current = local;
+                true
+            },
+            None => false,
+        }
+    }
+    fn mark_dirty(&self, id: NodeId) {
+        let mut stack = alloc::vec![id];
+        while let Some(current) = stack.pop() {
+            let Some(node) = self.node(current) else { continue };
+            if !node.dirty.replace(true) {
+                stack.extend_from_slice(&node.children);
+            }
+        }
+    }
+    /// The matrix taking points from the space of `id` to world space.
+    pub fn world_matrix(&self, id: NodeId) -> Option<Tr::Matrix> {
+        let node = self.node(id)?;
+        if node.dirty.get() {
+            let local = node.local.to_matrix();
+            let world = match node.parent {
+                Some(parent) => self.world_matrix(parent)?*local,
+                None => local,
+            };
+            node.world.set(world);
+            node.dirty.set(false);
+        }
+        Some(node.world.get())
+    }
+    /// The transformation of `id` in world space, split out of
+    /// [`SceneGraph::world_matrix`], see [`Transformation::from_matrix`] for
+    /// when that can't be exact.
+    pub fn world_transform(&self, id: NodeId) -> Option<Tr> {
+        self.world_matrix(id).map(|matrix| Tr::from_matrix(&matrix))
+    }
+    /// Whether `descendant` is `ancestor` or somewhere under it.
+    pub fn is_descendant(&self, descendant: NodeId, ancestor: NodeId) -> bool {
+        self.ancestors(descendant).any(|id| id == ancestor)
+    }
+    /// Moves `id` under `parent`, or makes it a root when `parent` is
+    /// [`None`], keeping where it is in world space. The new local
+    /// transformation is split out of the matrix taking it from the new
+    /// parent to world space, so it is exact unless that needs a shear, see
+    /// [`Transformation::from_matrix`]. Returns false when either node isn't
+    /// in the graph, when `parent` is under `id` or when the world matrix of
+    /// `parent` can't be inverted.
+    pub fn set_parent(&mut self, id: NodeId, parent: Option<NodeId>) -> bool
+        where <Tr::Matrix as SquareMatrix>::Column: VectorSpace<Scalar: Real>,
+            Tr::Matrix: core::ops::Mul<<<Tr::Matrix as SquareMatrix>::Column as VectorSpace>::Scalar, Output = Tr::Matrix> {
+        let (Some(world), Some(old_parent)) = (self.world_matrix(id), self.node(id).map(|node| node.parent)) else {
+            return false;
+        };
+        let local = match parent {
+            Some(parent) if self.is_descendant(parent, id) => return false,
+            Some(parent) => match self.world_matrix(parent).and_then(|parent_world| parent_world.inverse()) {
+                Some(parent_inverse) => Tr::from_matrix(&(parent_inverse*world)),
+                None => return false,
+            },
+            None => Tr::from_matrix(&world),
+        };
+        self.detach(id, old_parent);
+        match parent.and_then(|parent| self.node_mut(parent)) {
+            Some(node) => node.children.push(id),
+            None => self.roots.push(id),
+        }
+        if let Some(node) = self.node_mut(id) {
+            node.parent = parent;
+            node.local = local;
+        }
+        self.mark_dirty(id);
+        true
+    }
+    /// `id` and then its parent, up to the root.
+    pub fn ancestors(&self, id: NodeId) -> Ancestors<'_, Tr> {
+        Ancestors { graph: self, current: self.node(id).map(|_| id) }
+    }
+    /// `id` and everything under it, each node before its children.
+    pub fn descendants(&self, id: NodeId) -> DepthFirst<'_, Tr> {
+        let stack = if self.contains(id) { alloc::vec![id] } else { Vec::new() };
+        DepthFirst { graph: self, stack }
+    }
+    /// Every node in the graph, each node before its children.
+    pub fn iter(&self) -> DepthFirst<'_, Tr> {
+        DepthFirst { graph: self, stack: self.roots.iter().rev().copied().collect() }
+    }
+}
+
+pub struct Ancestors<'a, Tr: Transformation> {
+    graph: &'a SceneGraph<Tr>,
+    current: Option<NodeId>,
+}
+impl<Tr: Transformation> Iterator for Ancestors<'_, Tr>
+    where Tr::Matrix: SquareMatrix + Copy + core::ops::Mul<Output = Tr::Matrix> {
+    type Item = NodeId;
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+        self.current = self.graph.parent(current);
+        Some(current)
+    }
+}
+
+pub struct DepthFirst<'a, Tr: Transformation> {
+    graph: &'a SceneGraph<Tr>,
+    stack: Vec<NodeId>,
+}
+impl<Tr: Transformation> Iterator for DepthFirst<'_, Tr>
+    where Tr::Matrix: SquareMatrix + Copy + core::ops::Mul<Output = Tr::Matrix> {
+    type Item = NodeId;
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.stack.pop()?;
+        self.stack.extend(self.graph.children(current).iter().rev());
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use affogato_core::groups::vector_spaces::NormedVectorSpace;
+
+    use crate::{algebra::Quaternion, vector::{DVec2, DVec3, DVec4}, Transform2D, Transform3D, Transformation};
+
+    extern crate alloc;
+
+    use super::{SceneGraph2D, SceneGraph3D};
+
+    fn world_point(graph: &SceneGraph3D<f64>, id: super::NodeId, point: DVec3) -> DVec3 {
+        (graph.world_matrix(id).unwrap()*DVec4::from(point)).xyz()
+    }
+
+    #[test]
+    fn scene_graph() {
+        let mut graph = SceneGraph3D::new();
+        let turn = Quaternion::angle_axis(PI/2.0, DVec3::new(0.0, 0.0, 1.0));
+        let root = graph.add_root(Transform3D::new(DVec3::new(10.0, 0.0, 0.0), turn, DVec3::new(2.0, 2.0, 2.0)));
+        let arm = graph.add_child(root, Transform3D::new(DVec3::new(1.0, 0.0, 0.0), Quaternion::identity(), DVec3::new(1.0, 1.0, 1.0))).unwrap();
+        let hand = graph.add_child(arm, Transform3D::new(DVec3::new(1.0, 0.0, 0.0), Quaternion::identity(), DVec3::new(1.0, 1.0, 1.0))).unwrap();
+        let other = graph.add_root(Transform3D::new(DVec3::new(0.0, 5.0, 0.0), Quaternion::identity(), DVec3::new(1.0, 1.0, 1.0)));
+        assert!((world_point(&graph, hand, DVec3::new(0.0, 0.0, 0.0)) - DVec3::new(10.0, 4.0, 0.0)).length() < 1e-12);
+
+        // moving the root moves everything under it
+        graph.local_mut(root).unwrap().position = DVec3::new(0.0, 0.0, 0.0);
+        assert!((world_point(&graph, hand, DVec3::new(0.0, 0.0, 0.0)) - DVec3::new(0.0, 4.0, 0.0)).length() < 1e-12);
+        assert!((world_point(&graph, arm, DVec3::new(0.0, 0.0, 0.0)) - DVec3::new(0.0, 2.0, 0.0)).length() < 1e-12);
+
+        // reparenting keeps the hand where it is
+        assert!(graph.set_parent(hand, Some(other)));
+        assert!((world_point(&graph, hand, DVec3::new(0.0, 0.0, 0.0)) - DVec3::new(0.0, 4.0, 0.0)).length() < 1e-12);
+        assert!((world_point(&graph, hand, DVec3::new(1.0, 0.0, 0.0)) - DVec3::new(0.0, 6.0, 0.0)).length() < 1e-12);
+        assert_eq!(graph.parent(hand), Some(other));
+        assert!(graph.children(arm).is_empty());
+        assert!(!graph.set_parent(other, Some(hand)));
+
+        assert_eq!(graph.iter().collect::<alloc::vec::Vec<_>>(), [root, arm, other, hand]);
+        assert_eq!(graph.ancestors(hand).collect::<alloc::vec::Vec<_>>(), [hand, other]);
+
+        graph.remove(other);
+        assert_eq!(graph.len(), 2);
+        assert!(!graph.contains(hand));
+        let reused = graph.add_child(arm, Transform3D::identity()).unwrap();
+        assert!(graph.contains(reused) && !graph.contains(hand) && graph.local(hand).is_none());
+        assert_eq!(graph.descendants(root).collect::<alloc::vec::Vec<_>>(), [root, arm, reused]);
+
+        // under a non uniformly scaled parent the new local has to undo the
+        // stretch along the turned axis
+        let mut graph = SceneGraph3D::new();
+        let stretched = graph.add_root(Transform3D::new(DVec3::new(0.0, 0.0, 0.0), turn, DVec3::new(2.0, 1.0, 1.0)));
+        let node = graph.add_root(Transform3D::new(DVec3::new(1.0, 0.0, 0.0), Quaternion::identity(), DVec3::new(1.0, 1.0, 1.0)));
+        assert!(graph.set_parent(node, Some(stretched)));
+        for point in [DVec3::new(0.0, 0.0, 0.0), DVec3::new(1.0, 0.0, 0.0), DVec3::new(0.0, 1.0, 2.0)] {
+            assert!((world_point(&graph, node, point) - (point + DVec3::new(1.0, 0.0, 0.0))).length() < 1e-12);
+        }
+        assert!((graph.world_transform(node).unwrap().transform_point(DVec3::new(0.0, 1.0, 0.0)) - DVec3::new(1.0, 1.0, 0.0)).length() < 1e-12);
+        // a turned child of the stretched parent is sheared in world space,
+        // making it a root keeps its position but not the shear
+        let sheared = graph.add_child(stretched, Transform3D::new(DVec3::new(1.0, 1.0, 0.0), Quaternion::angle_axis(PI/4.0, DVec3::new(0.0, 0.0, 1.0)), DVec3::new(1.0, 1.0, 1.0))).unwrap();
+        let before = world_point(&graph, sheared, DVec3::new(0.0, 0.0, 0.0));
+        assert!(graph.set_parent(sheared, None));
+        assert!((world_point(&graph, sheared, DVec3::new(0.0, 0.0, 0.0)) - before).length() < 1e-12);
+
+        let mut graph = SceneGraph2D::new();
+        let root = graph.add_root(Transform2D::new(DVec2::new(1.0, 1.0), PI/2.0, DVec2::new(1.0, 1.0)));
+        let child = graph.add_child(root, Transform2D::new(DVec2::new(2.0, 0.0), 0.0, DVec2::new(1.0, 1.0))).unwrap();
+        let position = graph.world_transform(child).unwrap().position;
+        let matrix = graph.world_matrix(child).unwrap();
+        assert!((position - DVec2::new(1.0, 3.0)).length() < 1e-12);
+        assert!((DVec2::new(matrix.z.x(), matrix.z.y()) - position).length() < 1e-12);
+    }
+}
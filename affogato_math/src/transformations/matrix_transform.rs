@@ -0,0 +1,351 @@
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{Bounds, Zero}, sets::Real};
+
+use crate::{geometry::{symmetric_eigenvectors, ArcSegment2D, BezierCurve, Capsule, Capsule2D, Circle, Cone, CubicSegment2D, CubicSegment3D, Cylinder, Ellipse, LinearSegment2D, LinearSegment3D, OrientedBox3D, OrientedRect, Plane, QuadraticSegment2D, QuadraticSegment3D, Ray, Ray2D, Ray3D, Rect, Rect3D, RoundedRect, Segment2D, Segment3D, Sphere, Tetrahedron, Torus, Triangle2D, Triangle3D}, matrix::{Matrix3, Matrix4}, algebra::Quaternion, vector::{Vector2, Vector3, Vector4}};
+#[cfg(feature="alloc")]
+use crate::geometry::{MultiPolygon2D, Path2D, Polygon2D, Polygon3D};
+
+/// Moves a shape by an affine transformation, a [`Matrix3`] for shapes in the
+/// plane and a [`Matrix4`] for shapes in space.
+///
+/// Points go through the whole matrix and directions only through its linear
+/// part. Shapes that can't hold every transformation grow to enclose the
+/// result instead, a [`Rect`] becomes the bounds of its turned corners and a
+/// [`Circle`] takes the largest stretch of the matrix as its radius.
+pub trait MatrixTransform<Matrix> {
+    fn transform_by(&self, matrix: &Matrix) -> Self;
+}
+
+fn point2<T: Real>(matrix: &Matrix3<T>, point: Vector2<T>) -> Vector2<T> {
+    let point = *matrix*Vector3::new(point.x(), point.y(), T::ONE);
+    Vector2::new(point.x(), point.y())
+}
+fn direction2<T: Real>(matrix: &Matrix3<T>, direction: Vector2<T>) -> Vector2<T> {
+    let direction = *matrix*Vector3::new(direction.x(), direction.y(), T::ZERO);
+    Vector2::new(direction.x(), direction.y())
+}
+fn point3<T: Real>(matrix: &Matrix4<T>, point: Vector3<T>) -> Vector3<T> {
+    (*matrix*Vector4::new(point.x(), point.y(), point.z(), T::ONE)).xyz()
+}
+fn direction3<T: Real>(matrix: &Matrix4<T>, direction: Vector3<T>) -> Vector3<T> {
+    (*matrix*Vector4::new(direction.x(), direction.y(), direction.z(), T::ZERO)).xyz()
+}
+/// Normals have to go through the inverse transpose to stay perpendicular to
+/// the surface, which is the cofactor matrix divided by the determinant.
+fn normal3<T: Real>(matrix: &Matrix4<T>, normal: Vector3<T>) -> Vector3<T> {
+    let (x, y, z) = (matrix.x.xyz(), matrix.y.xyz(), matrix.z.xyz());
+    let cofactor = y.cross(&z)*normal.x() + z.cross(&x)*normal.y() + x.cross(&y)*normal.z();
+    if x.dot(&y.cross(&z)) < T::ZERO {
+        -cofactor.normalize()
+    } else {
+        cofactor.normalize()
+    }
+}
+/// The most the linear part of `matrix` lengthens any direction, its
+/// largest singular value.
+fn stretch2<T: Real>(matrix: &Matrix3<T>) -> T {
+    let (x, y) = (direction2(matrix, Vector2::new(T::ONE, T::ZERO)), direction2(matrix, Vector2::new(T::ZERO, T::ONE)));
+    let (xx, xy, yy) = (x.dot(&x), x.dot(&y), y.dot(&y));
+    let half = T::from_f64(0.5);
+    ((xx + yy)*half + (((xx - yy)*half)*((xx - yy)*half) + xy*xy).sqrt()).sqrt()
+}
+fn stretch3<T: Real>(matrix: &Matrix4<T>) -> T {
+    let columns = [matrix.x.xyz(), matrix.y.xyz(), matrix.z.xyz()];
+    let gram = columns.map(|a| columns.map(|b| a.dot(&b)));
+    direction3(matrix, symmetric_eigenvectors(gram)[0]).length()
+}
+fn mirrors2<T: Real>(matrix: &Matrix3<T>) -> bool {
+    matrix.x.x()*matrix.y.y() - matrix.x.y()*matrix.y.x() < T::ZERO
+}
+
+macro_rules! impl_point_transform {
+    ($shape:ident, $matrix:ident, $point:ident, $($field:ident),+) => {
+        impl<T: Real> MatrixTransform<$matrix<T>> for $shape<T> {
+            fn transform_by(&self, matrix: &$matrix<T>) -> Self {
+                Self { $($field: $point(matrix, self.$field)),+ }
+            }
+        }
+    };
+}
+impl_point_transform!(LinearSegment2D, Matrix3, point2, start, end);
+impl_point_transform!(QuadraticSegment2D, Matrix3, point2, start, control, end);
+impl_point_transform!(CubicSegment2D, Matrix3, point2, start, control1, control2, end);
+impl_point_transform!(LinearSegment3D, Matrix4, point3, start, end);
+impl_point_transform!(QuadraticSegment3D, Matrix4, point3, start, control, end);
+impl_point_transform!(CubicSegment3D, Matrix4, point3, start, control1, control2, end);
+
+impl<T: Real> MatrixTransform<Matrix3<T>> for ArcSegment2D<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        ArcSegment2D::transform_by(self, matrix)
+    }
+}
+impl<T: Real> MatrixTransform<Matrix3<T>> for Segment2D<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        match self {
+            Segment2D::Linear(linear) => Segment2D::Linear(linear.transform_by(matrix)),
+            Segment2D::Quadratic(quadratic) => Segment2D::Quadratic(quadratic.transform_by(matrix)),
+            Segment2D::Cubic(cubic) => Segment2D::Cubic(cubic.transform_by(matrix)),
+            Segment2D::Arc(arc) => Segment2D::Arc(ArcSegment2D::transform_by(arc, matrix)),
+        }
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Segment3D<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        match self {
+            Segment3D::Linear(linear) => Segment3D::Linear(linear.transform_by(matrix)),
+            Segment3D::Quadratic(quadratic) => Segment3D::Quadratic(quadratic.transform_by(matrix)),
+            Segment3D::Cubic(cubic) => Segment3D::Cubic(cubic.transform_by(matrix)),
+        }
+    }
+}
+impl<T: Real, const N: usize> MatrixTransform<Matrix3<T>> for BezierCurve<Vector2<T>, N> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        BezierCurve::new(self.points.map(|point| point2(matrix, point)))
+    }
+}
+impl<T: Real, const N: usize> MatrixTransform<Matrix4<T>> for BezierCurve<Vector3<T>, N> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        BezierCurve::new(self.points.map(|point| point3(matrix, point)))
+    }
+}
+
+impl<T: Real> MatrixTransform<Matrix3<T>> for Triangle2D<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        Triangle2D::new(point2(matrix, self[0]), point2(matrix, self[1]), point2(matrix, self[2]))
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Triangle3D<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        Triangle3D::new(point3(matrix, self[0]), point3(matrix, self[1]), point3(matrix, self[2]))
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Tetrahedron<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        Tetrahedron::new(point3(matrix, self[0]), point3(matrix, self[1]), point3(matrix, self[2]), point3(matrix, self[3]))
+    }
+}
+
+impl<T: Real> MatrixTransform<Matrix3<T>> for Circle<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        Circle::new(point2(matrix, self.center), self.radius*stretch2(matrix))
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Sphere<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        Sphere::new(point3(matrix, self.center), self.radius*stretch3(matrix))
+    }
+}
+/// The image of an ellipse under an affine transformation is another
+/// ellipse, so this one is exact.
+impl<T: Real> MatrixTransform<Matrix3<T>> for Ellipse<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        let arc = ArcSegment2D::new(self.center, self.radii, self.rotation, T::ZERO, T::TAU).transform_by(matrix);
+        Ellipse::new(arc.center, arc.radii, arc.rotation)
+    }
+}
+impl<T: Real> MatrixTransform<Matrix3<T>> for Capsule2D<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        Capsule2D::new(point2(matrix, self.start), point2(matrix, self.end), self.radius*stretch2(matrix))
+    }
+}
+
+impl<T: Real> MatrixTransform<Matrix3<T>> for Rect<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        let corners = [self.min, Vector2::new(self.max.x(), self.min.y()), self.max, Vector2::new(self.min.x(), self.max.y())]
+            .map(|corner| point2(matrix, corner));
+        let (min, max) = corners.iter().fold((corners[0], corners[0]), |(min, max), &corner| (min.min(corner), max.max(corner)));
+        Rect::new(min, max)
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Rect3D<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        let corners: [Vector3<T>; 8] = core::array::from_fn(|index| point3(matrix, Vector3::new(
+            if index & 1 == 0 { self.min.x() } else { self.max.x() },
+            if index & 2 == 0 { self.min.y() } else { self.max.y() },
+            if index & 4 == 0 { self.min.z() } else { self.max.z() },
+        )));
+        let (min, max) = corners.iter().fold((corners[0], corners[0]), |(min, max), &corner| (min.min(corner), max.max(corner)));
+        Rect3D::new(min, max)
+    }
+}
+/// The rect inside the rounded corners grows to the bounds of its turned
+/// corners, and the corners take the largest stretch of the matrix.
+impl<T: Real> MatrixTransform<Matrix3<T>> for RoundedRect<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        let inset = Vector2::new(self.radius, self.radius);
+        let inner = Rect::new(self.rect.min + inset, self.rect.max - inset).transform_by(matrix);
+        let radius = self.radius*stretch2(matrix);
+        let outset = Vector2::new(radius, radius);
+        RoundedRect::new(Rect::new(inner.min - outset, inner.max + outset), radius)
+    }
+}
+/// Exact unless the matrix shears the rect, which would turn it into a
+/// parallelogram.
+impl<T: Real> MatrixTransform<Matrix3<T>> for OrientedRect<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        let [x, y] = self.axes().map(|axis| direction2(matrix, axis));
+        OrientedRect::new(
+            point2(matrix, self.center),
+            Vector2::new(self.half_extents.x()*x.length(), self.half_extents.y()*y.length()),
+            x.y().atan2(x.x()),
+        )
+    }
+}
+/// Exact unless the matrix shears the box, which would turn it into a
+/// parallelepiped.
+impl<T: Real> MatrixTransform<Matrix4<T>> for OrientedBox3D<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        let [x, y, z] = self.axes().map(|axis| direction3(matrix, axis));
+        let right = x.normalize();
+        let up = (y - right*right.dot(&y)).normalize();
+        OrientedBox3D::new(
+            point3(matrix, self.center),
+            self.half_extents*Vector3::new(x.length(), y.length(), z.length()),
+            Quaternion::from_axes(right, up, right.cross(&up)),
+        )
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Capsule<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        Capsule::new(point3(matrix, self.start), point3(matrix, self.end), self.radius*stretch3(matrix))
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Cylinder<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        Cylinder::new(point3(matrix, self.start), point3(matrix, self.end), self.radius*stretch3(matrix))
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Cone<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        Cone::new(point3(matrix, self.base), point3(matrix, self.apex), self.radius*stretch3(matrix))
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Torus<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        let stretch = stretch3(matrix);
+        Torus::new(point3(matrix, self.center), normal3(matrix, self.axis), self.major_radius*stretch, self.minor_radius*stretch)
+    }
+}
+
+impl<T: Real> MatrixTransform<Matrix4<T>> for Plane<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        let point = point3(matrix, self.normal()*self.distance());
+        let normal = normal3(matrix, self.normal());
+        // SAFETY: `normal3` always gives back a unit vector.
+        unsafe { Plane::from_normal_distance_unchecked(normal, normal.dot(&point)) }
+    }
+}
+impl<T: Real> MatrixTransform<Matrix3<T>> for Ray2D<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        let origin = point2(matrix, *self.origin());
+        Ray2D::new(origin, origin + direction2(matrix, *self.direction()))
+    }
+}
+impl<T: Real> MatrixTransform<Matrix4<T>> for Ray3D<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        let origin = point3(matrix, *self.origin());
+        Ray3D::new(origin, origin + direction3(matrix, *self.direction()))
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T: Real> MatrixTransform<Matrix3<T>> for Polygon2D<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        Polygon2D { vertices: self.vertices.iter().map(|&vertex| point2(matrix, vertex)).collect() }
+    }
+}
+#[cfg(feature="alloc")]
+impl<T: Real> MatrixTransform<Matrix4<T>> for Polygon3D<T> {
+    fn transform_by(&self, matrix: &Matrix4<T>) -> Self {
+        Polygon3D { vertices: self.vertices.iter().map(|&vertex| point3(matrix, vertex)).collect() }
+    }
+}
+/// A mirroring matrix would flip the winding of every contour, so they are
+/// reversed to keep outlines counterclockwise and holes clockwise.
+#[cfg(feature="alloc")]
+impl<T: Real> MatrixTransform<Matrix3<T>> for MultiPolygon2D<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        let mirrors = mirrors2(matrix);
+        let mut multi = self.clone();
+        for contour in multi.contours.iter_mut() {
+            contour.polygon = contour.polygon.transform_by(matrix);
+            if mirrors {
+                contour.polygon.vertices.reverse();
+            }
+        }
+        multi
+    }
+}
+#[cfg(feature="alloc")]
+impl<T: Real> MatrixTransform<Matrix3<T>> for Path2D<T> {
+    fn transform_by(&self, matrix: &Matrix3<T>) -> Self {
+        Path2D::transform_by(self, matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use affogato_core::groups::vector_spaces::{NormedVectorSpace, VectorSpace};
+
+    use crate::{algebra::Quaternion, geometry::{CalculateCentroid, Ellipse, Plane, Rect3D, Sphere, Triangle2D}, matrix::{Matrix3, Matrix4, SquareMatrix}, sdf::SignedDistance, vector::{DVec2, DVec3, DVec4}, Rotation, Scaling, Translation};
+
+    use super::MatrixTransform;
+
+    #[test]
+    fn moving_shapes() {
+        let mut matrix = Matrix4::<f64>::identity();
+        Translation::translate(&mut matrix, DVec3::new(1.0, 2.0, 3.0));
+        assert!((matrix*DVec4::new(0.0, 0.0, 0.0, 1.0) - DVec4::new(1.0, 2.0, 3.0, 1.0)).length() < 1e-12);
+
+        let mut triangle = Triangle2D::new(DVec2::new(0.0, 0.0), DVec2::new(3.0, 0.0), DVec2::new(0.0, 3.0));
+        let half_turn = triangle.transform_by(&Matrix3::from_transform(DVec2::new(2.0, 2.0), DVec2::new(1.0, 1.0), PI));
+        triangle.rotate(&(PI/2.0));
+        triangle.rotate(&(PI/2.0));
+        for i in 0..3 {
+            assert!((triangle[i] - half_turn[i]).length() < 1e-12);
+        }
+        assert!((triangle.centroid() - DVec2::new(1.0, 1.0)).length() < 1e-12);
+
+        // x + y = 2 goes through (2, 0, 0) and (0, 2, 0)
+        let mut plane = Plane::from_normal_distance(DVec3::new(1.0, 1.0, 0.0), 2.0_f64.sqrt());
+        let stretched = plane.transform_by(&Matrix4::from_scale(DVec3::new(2.0, 1.0, 1.0)));
+        plane.scale(DVec3::new(2.0, 1.0, 1.0));
+        plane.translate(DVec3::new(0.0, 0.0, 5.0));
+        for point in [DVec3::new(4.0, 0.0, 0.0), DVec3::new(0.0, 2.0, 7.0)] {
+            assert!((plane.normal().dot(&point) - plane.distance()).abs() < 1e-12);
+            assert!((stretched.normal().dot(&point) - stretched.distance()).abs() < 1e-12);
+        }
+        // flattening x squashes the plane onto x = 0, mirroring x keeps its side
+        let mut flattened = Plane::from_normal_distance(DVec3::new(1.0, 1.0, 0.0), 2.0_f64.sqrt());
+        flattened.scale(DVec3::new(0.0, 1.0, 1.0));
+        assert!((flattened.normal() - DVec3::new(1.0, 0.0, 0.0)).length() < 1e-12 && flattened.distance().abs() < 1e-12);
+        let mut mirrored = Plane::from_normal_distance(DVec3::new(1.0, 1.0, 0.0), 2.0_f64.sqrt());
+        mirrored.scale(DVec3::new(-1.0, 1.0, 1.0));
+        assert!((mirrored.normal() - DVec3::new(-1.0, 1.0, 0.0)*0.5_f64.sqrt()).length() < 1e-12);
+        assert!((mirrored.distance() - 2.0_f64.sqrt()).abs() < 1e-12);
+
+        let sphere = Sphere::new(DVec3::new(1.0, 0.0, 0.0), 1.0).transform_by(&Matrix4::from_scale(DVec3::new(1.0, 3.0, 1.0)));
+        assert!((sphere.radius - 3.0).abs() < 1e-12);
+
+        let quarter = Quaternion::from_scalar_vector(DVec3::new(0.0, 0.0, (PI/4.0).sin()), (PI/4.0).cos());
+        let rect = Rect3D::new(DVec3::new(0.0, 0.0, 0.0), DVec3::new(2.0, 4.0, 2.0));
+        let turned = rect.rotated(quarter);
+        let moved = rect.rotated(Quaternion::identity()).transform_by(&Matrix4::from_transform(DVec3::new(-3.0, 0.0, 0.0), quarter, DVec3::new(1.0, 1.0, 1.0)));
+        assert!((moved.center - DVec3::new(-5.0, 1.0, 1.0)).length() < 1e-12);
+        assert!((moved.half_extents - turned.half_extents).length() < 1e-12);
+        for (a, b) in moved.axes().into_iter().zip(turned.axes()) {
+            assert!((a - b).length() < 1e-12);
+        }
+
+        // the image of an ellipse is exact even when the matrix shears it
+        let ellipse = Ellipse::new(DVec2::new(1.0, -1.0), DVec2::new(3.0, 1.0), 0.3);
+        let mut shear = Matrix3::from_transform(DVec2::new(2.0, 5.0), DVec2::new(1.0, 2.0), 0.7);
+        shear.y.set_x(shear.y.x() + 1.5);
+        let sheared = ellipse.transform_by(&shear);
+        for angle in [0.0, 1.0, 2.5, 4.0] {
+            let point = shear*DVec3::new(ellipse.point_at(angle).x(), ellipse.point_at(angle).y(), 1.0);
+            assert!(sheared.sdf(&DVec2::new(point.x(), point.y())).abs() < 1e-9);
+        }
+    }
+}
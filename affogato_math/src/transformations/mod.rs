@@ -1,14 +1,43 @@
 mod translation;
 mod rotate;
 mod scale;
+mod matrix_transform;
+mod transform;
 pub use translation::*;
 pub use rotate::*;
 pub use scale::*;
+pub use matrix_transform::*;
+pub use transform::*;
 
-pub trait Transformation: Translation<Self::Translate> + Rotation<Self::Rotate> + Scaling<Self::Scale> {
-    type Scale;
-    type Rotate;
-    type Translate;
-    fn transform(position: Self::Translate, rotation: Self::Rotate, scaling: Self::Scale);
-}
+use affogato_core::groups::vector_spaces::VectorSpace;
 
+/// Where an object is, how it is turned and how big it is. Points are first
+/// scaled, then rotated and then moved to the position.
+///
+/// Composition and inversion are exact as long as the scale is uniform, a
+/// non uniform scale under a rotation would need a shear to represent.
+pub trait Transformation: Translation<Self::Vector> + Rotation<Self::Rotation> + Scaling<Self::Vector> + Sized {
+    type Vector: VectorSpace;
+    type Rotation;
+    type Matrix;
+    fn from_parts(position: Self::Vector, rotation: Self::Rotation, scale: Self::Vector) -> Self;
+    /// The transformation that leaves everything where it is.
+    fn identity() -> Self;
+    /// The transformation that applies `inner` first and then `self`, the
+    /// same as multiplying their matrices as `self*inner`.
+    fn compose(&self, inner: &Self) -> Self;
+    /// The transformation that undoes this one.
+    fn inverse(&self) -> Self;
+    fn transform_point(&self, point: Self::Vector) -> Self::Vector;
+    /// Scales and rotates `direction` without moving it.
+    fn transform_direction(&self, direction: Self::Vector) -> Self::Vector;
+    /// Blends towards `other` as `t` goes from 0.0 to 1.0, turning along the
+    /// shortest way.
+    fn interpolate(&self, other: &Self, t: <Self::Vector as VectorSpace>::Scalar) -> Self;
+    fn to_matrix(&self) -> Self::Matrix;
+    /// Splits a matrix made of a scale, a rotation and a translation back
+    /// into them. A matrix with a shear, like a non uniformly scaled parent
+    /// above a rotated child, has no exact transformation, the position is
+    /// still kept but the shear is dropped.
+    fn from_matrix(matrix: &Self::Matrix) -> Self;
+}
@@ -1,7 +1,13 @@
 use affogato_core::{num::{Number, One, Zero}, sets::Real};
 
-use crate::{algebra::Quaternion, geometry::{CalculateCentroid, CubicSegment2D, LinearSegment2D, QuadraticSegment2D}, matrix::{Matrix2, Matrix3, Matrix4}, vector::{FMat3, FVec2, Vector2, Vector3}};
+use crate::{algebra::Quaternion, geometry::{CalculateCentroid, Circle, CubicSegment2D, LinearSegment2D, OrientedBox3D, OrientedRect, Plane, QuadraticSegment2D, Ray, Ray2D, Ray3D, Sphere, Tetrahedron, Triangle2D, Triangle3D}, matrix::{Matrix2, Matrix3, Matrix4}, vector::{FMat3, FVec2, Vector2, Vector3}};
 
+/// Turns an object by an angle in radians in 2D or by a [`Quaternion`] in
+/// 3D. Vectors and matrices turn about the origin. Shapes turn in place
+/// about their own pivot instead: segments, triangles and tetrahedra about
+/// their centroid, boxes, rects, circles and spheres about their center, and
+/// rays, which have no centroid, about their origin. A [`Plane`] has no pivot
+/// either and turns about the world origin.
 pub trait Rotation<Rot> {
     fn rotate(&mut self, rotate_by: &Rot);
 }
@@ -75,4 +81,76 @@ impl<T: Real> Rotation<T> for CubicSegment2D<T> {
         self.control2 = (rotation*(self.control2-centroid))+centroid;
         self.end = (rotation*(self.end-centroid))+centroid;
     }
-}
\ No newline at end of file
+}
+/// A circle looks the same at every angle around its center, so this does nothing.
+impl<T: Real> Rotation<T> for Circle<T> {
+    fn rotate(&mut self, _rotate_by: &T) {}
+}
+
+/// A sphere looks the same at every angle around its center, so this does nothing.
+impl<T: Real> Rotation<Quaternion<T>> for Sphere<T> {
+    fn rotate(&mut self, _rotate_by: &Quaternion<T>) {}
+}
+
+impl<T: Real> Rotation<T> for Triangle2D<T> {
+    fn rotate(&mut self, rotate_by: &T) {
+        let centroid = self.centroid();
+        let rotation = Matrix2::from_rotation(*rotate_by);
+        for i in 0..3 {
+            self[i] = (rotation*(self[i]-centroid))+centroid;
+        }
+    }
+}
+
+impl<T: Real> Rotation<Quaternion<T>> for Triangle3D<T> {
+    fn rotate(&mut self, rotate_by: &Quaternion<T>) {
+        let centroid = self.centroid();
+        for i in 0..3 {
+            self[i] = (*rotate_by*(self[i]-centroid))+centroid;
+        }
+    }
+}
+
+impl<T: Real> Rotation<Quaternion<T>> for Tetrahedron<T> {
+    fn rotate(&mut self, rotate_by: &Quaternion<T>) {
+        let centroid = self.centroid();
+        for i in 0..4 {
+            self[i] = (*rotate_by*(self[i]-centroid))+centroid;
+        }
+    }
+}
+
+/// Turns the plane around the origin, which keeps its distance to the origin.
+impl<T: Real> Rotation<Quaternion<T>> for Plane<T> {
+    fn rotate(&mut self, rotate_by: &Quaternion<T>) {
+        *self = Plane::from_normal_distance(*rotate_by*self.normal(), self.distance());
+    }
+}
+
+/// Turns the ray around its origin.
+impl<T: Real> Rotation<T> for Ray2D<T> {
+    fn rotate(&mut self, rotate_by: &T) {
+        let origin = *self.origin();
+        *self = Ray2D::new(origin, origin+Matrix2::from_rotation(*rotate_by)**self.direction());
+    }
+}
+
+/// Turns the ray around its origin.
+impl<T: Real> Rotation<Quaternion<T>> for Ray3D<T> {
+    fn rotate(&mut self, rotate_by: &Quaternion<T>) {
+        let origin = *self.origin();
+        *self = Ray3D::new(origin, origin+*rotate_by**self.direction());
+    }
+}
+
+impl<T: Real> Rotation<T> for OrientedRect<T> {
+    fn rotate(&mut self, rotate_by: &T) {
+        self.rotation += *rotate_by;
+    }
+}
+
+impl<T: Real> Rotation<Quaternion<T>> for OrientedBox3D<T> {
+    fn rotate(&mut self, rotate_by: &Quaternion<T>) {
+        self.rotation = (*rotate_by*self.rotation).normalize();
+    }
+}
@@ -1,6 +1,6 @@
-use affogato_core::{num::Number, sets::Real};
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{Number, Signed, Zero}, sets::Real};
 
-use crate::{geometry::{CubicSegment2D, LinearSegment2D, QuadraticSegment2D}, matrix::{Matrix3, Matrix4}, vector::{Vector2, Vector3, Vector4}};
+use crate::{geometry::{Circle, CubicSegment2D, LinearSegment2D, Plane, QuadraticSegment2D, Ray, Ray2D, Ray3D, Sphere, Tetrahedron, Triangle2D, Triangle3D}, matrix::{Matrix3, Matrix4}, vector::{Vector2, Vector3, Vector4}};
 
 pub trait Scaling<Scale> {
     fn scale(&mut self, scale: Scale);
@@ -61,4 +61,75 @@ impl<T: Number> Scaling<Vector2<T>> for CubicSegment2D<T> {
         self.control2 *= scale;
         self.end *= scale;
     }
-}
\ No newline at end of file
+}
+/// Only a uniform scale keeps a circle a circle. The center is scaled from
+/// the origin like every other point.
+impl<T: Number + Signed> Scaling<T> for Circle<T> {
+    fn scale(&mut self, scale: T) {
+        self.center *= scale;
+        self.radius *= scale.abs();
+    }
+}
+
+/// Only a uniform scale keeps a sphere a sphere. The center is scaled from
+/// the origin like every other point.
+impl<T: Number + Signed> Scaling<T> for Sphere<T> {
+    fn scale(&mut self, scale: T) {
+        self.center *= scale;
+        self.radius *= scale.abs();
+    }
+}
+
+impl<T: Number> Scaling<Vector2<T>> for Triangle2D<T> {
+    fn scale(&mut self, scale: Vector2<T>) {
+        *self = *self*scale;
+    }
+}
+
+impl<T: Number> Scaling<Vector3<T>> for Triangle3D<T> {
+    fn scale(&mut self, scale: Vector3<T>) {
+        *self = *self*scale;
+    }
+}
+
+impl<T: Number> Scaling<Vector3<T>> for Tetrahedron<T> {
+    fn scale(&mut self, scale: Vector3<T>) {
+        *self = *self*scale;
+    }
+}
+
+/// The normal is scaled by the cofactors of `scale`, which keeps it
+/// perpendicular to the plane and stays finite when a component is zero. Scaling an axis to
+/// zero flattens space, a plane crossing that axis becomes the plane where it
+/// is zero, and a plane that would be flattened into a line is left as it is.
+impl<T: Real> Scaling<Vector3<T>> for Plane<T> {
+    fn scale(&mut self, scale: Vector3<T>) {
+        let point = self.normal()*self.distance()*scale;
+        let cofactors = Vector3::new(scale.y()*scale.z(), scale.x()*scale.z(), scale.x()*scale.y());
+        let mut normal = self.normal()*cofactors;
+        if normal == Vector3::ZERO {
+            return;
+        }
+        // the cofactors are the inverse times the determinant, whose sign
+        // would flip the plane under a mirroring scale
+        if scale.x()*scale.y()*scale.z() < T::ZERO {
+            normal = -normal;
+        }
+        let normal = normal.normalize();
+        *self = Plane::from_normal_distance(normal, normal.dot(&point));
+    }
+}
+
+impl<T: Real> Scaling<Vector2<T>> for Ray2D<T> {
+    fn scale(&mut self, scale: Vector2<T>) {
+        let origin = *self.origin()*scale;
+        *self = Ray2D::new(origin, origin+*self.direction()*scale);
+    }
+}
+
+impl<T: Real> Scaling<Vector3<T>> for Ray3D<T> {
+    fn scale(&mut self, scale: Vector3<T>) {
+        let origin = *self.origin()*scale;
+        *self = Ray3D::new(origin, origin+*self.direction()*scale);
+    }
+}
@@ -0,0 +1,255 @@
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{One, Zero}, sets::Real};
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize};
+
+use crate::{algebra::{normalize_radians, Quaternion}, lerp, matrix::{Matrix2, Matrix3, Matrix4}, vector::{Vector2, Vector3, Vector4}};
+
+use super::{Rotation, Scaling, Transformation, Translation};
+
+/// A position, an angle in radians and a scale in the plane.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform2D<T: Real> {
+    pub position: Vector2<T>,
+    pub rotation: T,
+    pub scale: Vector2<T>,
+}
+impl<T: Real> Transform2D<T> {
+    pub fn new(position: Vector2<T>, rotation: T, scale: Vector2<T>) -> Self {
+        Self { position, rotation, scale }
+    }
+}
+impl<T: Real> Default for Transform2D<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+impl<T: Real> Transformation for Transform2D<T> {
+    type Vector = Vector2<T>;
+    type Rotation = T;
+    type Matrix = Matrix3<T>;
+    fn from_parts(position: Vector2<T>, rotation: T, scale: Vector2<T>) -> Self {
+        Self::new(position, rotation, scale)
+    }
+    fn identity() -> Self {
+        Self::new(Vector2::ZERO, T::ZERO, Vector2::ONE)
+    }
+    fn compose(&self, inner: &Self) -> Self {
+        Self::new(self.transform_point(inner.position), self.rotation + inner.rotation, self.scale*inner.scale)
+    }
+    fn inverse(&self) -> Self {
+        let scale = Vector2::ONE/self.scale;
+        let position = Matrix2::from_rotation(-self.rotation)*(-self.position)*scale;
+        Self::new(position, -self.rotation, scale)
+    }
+    fn transform_point(&self, point: Vector2<T>) -> Vector2<T> {
+        self.position + self.transform_direction(point)
+    }
+    fn transform_direction(&self, direction: Vector2<T>) -> Vector2<T> {
+        Matrix2::from_rotation(self.rotation)*(direction*self.scale)
+    }
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        Self::new(
+            lerp(self.position, other.position, t),
+            self.rotation + normalize_radians(other.rotation - self.rotation)*t,
+            lerp(self.scale, other.scale, t),
+        )
+    }
+    fn to_matrix(&self) -> Matrix3<T> {
+        let (sin, cos) = self.rotation.sin_cos();
+        Matrix3::new(
+            cos*self.scale.x(), sin*self.scale.x(), T::ZERO,
+            -sin*self.scale.y(), cos*self.scale.y(), T::ZERO,
+            self.position.x(), self.position.y(), T::ONE
+        )
+    }
+    fn from_matrix(matrix: &Matrix3<T>) -> Self {
+        let (x, y) = (Vector2::new(matrix.x.x(), matrix.x.y()), Vector2::new(matrix.y.x(), matrix.y.y()));
+        let scale_x = x.length();
+        let right = x/scale_x;
+        let up = Vector2::new(-right.y(), right.x());
+        Self::new(Vector2::new(matrix.z.x(), matrix.z.y()), right.y().atan2(right.x()), Vector2::new(scale_x, up.dot(&y)))
+    }
+}
+impl<T: Real> core::ops::Mul for Transform2D<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+impl<T: Real> From<Transform2D<T>> for Matrix3<T> {
+    fn from(value: Transform2D<T>) -> Self {
+        value.to_matrix()
+    }
+}
+impl<T: Real> Translation<Vector2<T>> for Transform2D<T> {
+    fn translate(&mut self, translation: Vector2<T>) {
+        self.position += translation;
+    }
+}
+/// Turns the object in place, the position stays where it is.
+impl<T: Real> Rotation<T> for Transform2D<T> {
+    fn rotate(&mut self, rotate_by: &T) {
+        self.rotation += *rotate_by;
+    }
+}
+impl<T: Real> Scaling<Vector2<T>> for Transform2D<T> {
+    fn scale(&mut self, scale: Vector2<T>) {
+        self.scale *= scale;
+    }
+}
+
+/// A position, a unit [`Quaternion`] and a scale in space.
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug)]
+pub struct Transform3D<T: Real> {
+    pub position: Vector3<T>,
+    pub rotation: Quaternion<T>,
+    pub scale: Vector3<T>,
+}
+impl<T: Real> Transform3D<T> {
+    pub fn new(position: Vector3<T>, rotation: Quaternion<T>, scale: Vector3<T>) -> Self {
+        Self { position, rotation, scale }
+    }
+}
+impl<T: Real> Default for Transform3D<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+impl<T: Real> Transformation for Transform3D<T> {
+    type Vector = Vector3<T>;
+    type Rotation = Quaternion<T>;
+    type Matrix = Matrix4<T>;
+    fn from_parts(position: Vector3<T>, rotation: Quaternion<T>, scale: Vector3<T>) -> Self {
+        Self::new(position, rotation, scale)
+    }
+    fn identity() -> Self {
+        Self::new(Vector3::ZERO, Quaternion::identity(), Vector3::ONE)
+    }
+    fn compose(&self, inner: &Self) -> Self {
+        Self::new(self.transform_point(inner.position), (self.rotation*inner.rotation).normalize(), self.scale*inner.scale)
+    }
+    fn inverse(&self) -> Self {
+        let rotation = self.rotation.conjugate();
+        let scale = Vector3::ONE/self.scale;
+        Self::new(rotation*(-self.position)*scale, rotation, scale)
+    }
+    fn transform_point(&self, point: Vector3<T>) -> Vector3<T> {
+        self.position + self.transform_direction(point)
+    }
+    fn transform_direction(&self, direction: Vector3<T>) -> Vector3<T> {
+        self.rotation*(direction*self.scale)
+    }
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        Self::new(
+            lerp(self.position, other.position, t),
+            self.rotation.slerp(other.rotation, t),
+            lerp(self.scale, other.scale, t),
+        )
+    }
+    fn to_matrix(&self) -> Matrix4<T> {
+        let mut matrix = Matrix4::from(Matrix3::from(self.rotation)).scale(self.scale);
+        matrix.w = Vector4::from(self.position);
+        matrix
+    }
+    fn from_matrix(matrix: &Matrix4<T>) -> Self {
+        // Gram-Schmidt, whatever of y and z isn't at right angles is shear
+        let (x, y, z) = (matrix.x.xyz(), matrix.y.xyz(), matrix.z.xyz());
+        let scale_x = x.length();
+        let right = x/scale_x;
+        let up = y - right*right.dot(&y);
+        let scale_y = up.length();
+        let up = up/scale_y;
+        let forward = right.cross(&up);
+        Self::new(matrix.w.xyz(), Quaternion::from_axes(right, up, forward).normalize(), Vector3::new(scale_x, scale_y, forward.dot(&z)))
+    }
+}
+impl<T: Real> core::ops::Mul for Transform3D<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+impl<T: Real> From<Transform3D<T>> for Matrix4<T> {
+    fn from(value: Transform3D<T>) -> Self {
+        value.to_matrix()
+    }
+}
+impl<T: Real> Translation<Vector3<T>> for Transform3D<T> {
+    fn translate(&mut self, translation: Vector3<T>) {
+        self.position += translation;
+    }
+}
+/// Turns the object in place, the position stays where it is.
+impl<T: Real> Rotation<Quaternion<T>> for Transform3D<T> {
+    fn rotate(&mut self, rotate_by: &Quaternion<T>) {
+        self.rotation = (*rotate_by*self.rotation).normalize();
+    }
+}
+impl<T: Real> Scaling<Vector3<T>> for Transform3D<T> {
+    fn scale(&mut self, scale: Vector3<T>) {
+        self.scale *= scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use affogato_core::groups::vector_spaces::NormedVectorSpace;
+
+    use crate::{algebra::Quaternion, vector::{DVec2, DVec3, DVec4}, Rotation, Transformation, Translation};
+
+    use super::{Transform2D, Transform3D};
+
+    fn step<V, R>(transform: &mut (impl Rotation<R> + Translation<V>), rotation: R, translation: V) {
+        transform.rotate(&rotation);
+        transform.translate(translation);
+    }
+
+    #[test]
+    fn transforms_2d() {
+        let a = Transform2D::new(DVec2::new(1.0, 2.0), 0.5, DVec2::new(2.0, 2.0));
+        let b = Transform2D::new(DVec2::new(-3.0, 0.5), -1.2, DVec2::new(1.0, 3.0));
+        let point = DVec2::new(0.7, -1.1);
+        let moved = a.to_matrix()*DVec3::new(point.x(), point.y(), 1.0);
+        assert!((a.transform_point(point) - DVec2::new(moved.x(), moved.y())).length() < 1e-12);
+        assert!(((a*b).transform_point(point) - a.transform_point(b.transform_point(point))).length() < 1e-12);
+        assert!(((a.inverse()*a).transform_point(point) - point).length() < 1e-12);
+        let mirrored = Transform2D::new(DVec2::new(1.0, 2.0), 0.5, DVec2::new(2.0, -3.0));
+        assert!((Transform2D::from_matrix(&mirrored.to_matrix()).transform_point(point) - mirrored.transform_point(point)).length() < 1e-12);
+
+        // the halfway angle goes the short way across -pi
+        let (origin, unit) = (DVec2::new(0.0, 0.0), DVec2::new(1.0, 1.0));
+        let halfway = Transform2D::new(origin, 3.0_f64, unit).interpolate(&Transform2D::new(origin, -3.0, unit), 0.5);
+        assert!((halfway.rotation.abs() - PI).abs() < 1e-12);
+
+        let mut body = Transform2D::identity();
+        step(&mut body, PI/2.0, DVec2::new(1.0, 0.0));
+        assert!((body.transform_point(DVec2::new(1.0, 0.0)) - DVec2::new(1.0, 1.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn transforms_3d() {
+        let a = Transform3D::new(DVec3::new(1.0, 2.0, 3.0), Quaternion::angle_axis(0.8, DVec3::new(0.0, 0.6, 0.8)), DVec3::new(2.0, 2.0, 2.0));
+        let b = Transform3D::new(DVec3::new(-1.0, 0.0, 4.0), Quaternion::angle_axis(-2.0, DVec3::new(1.0, 0.0, 0.0)), DVec3::new(1.0, 0.5, 3.0));
+        let point = DVec3::new(0.3, -1.0, 2.0);
+        assert!((a.transform_point(point) - (a.to_matrix()*DVec4::from(point)).xyz()).length() < 1e-12);
+        assert!(((a*b).transform_point(point) - a.transform_point(b.transform_point(point))).length() < 1e-12);
+        assert!(((a.inverse()*a).transform_point(point) - point).length() < 1e-12);
+        assert!((a.transform_direction(point) - (a.to_matrix()*DVec4::new(point.x(), point.y(), point.z(), 0.0)).xyz()).length() < 1e-12);
+        let mirrored = Transform3D::new(DVec3::new(1.0, 2.0, 3.0), a.rotation, DVec3::new(2.0, 0.5, -3.0));
+        assert!((Transform3D::from_matrix(&mirrored.to_matrix()).transform_point(point) - mirrored.transform_point(point)).length() < 1e-12);
+
+        let start = Transform3D::identity();
+        let end = Transform3D::new(DVec3::new(4.0, 0.0, 0.0), Quaternion::angle_axis(PI/2.0, DVec3::new(0.0, 0.0, 1.0)), DVec3::new(3.0, 3.0, 3.0));
+        let halfway = start.interpolate(&end, 0.5);
+        let expected = Transform3D::new(DVec3::new(2.0, 0.0, 0.0), Quaternion::angle_axis(PI/4.0, DVec3::new(0.0, 0.0, 1.0)), DVec3::new(2.0, 2.0, 2.0));
+        assert!((halfway.transform_point(point) - expected.transform_point(point)).length() < 1e-12);
+
+        let mut body = Transform3D::identity();
+        step(&mut body, Quaternion::angle_axis(PI/2.0, DVec3::new(0.0, 0.0, 1.0)), DVec3::new(1.0, 0.0, 0.0));
+        assert!((body.transform_point(DVec3::new(1.0, 0.0, 0.0)) - DVec3::new(1.0, 1.0, 0.0)).length() < 1e-12);
+    }
+}
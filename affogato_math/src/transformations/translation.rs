@@ -1,6 +1,6 @@
-use affogato_core::{num::Number, sets::Real};
+use affogato_core::{groups::vector_spaces::VectorSpace, num::Number, sets::Real};
 
-use crate::{geometry::{CubicSegment2D, LinearSegment2D, QuadraticSegment2D}, matrix::{Matrix3, Matrix4}, vector::{Vector2, Vector3, Vector4}};
+use crate::{geometry::{Circle, CubicSegment2D, LinearSegment2D, OrientedBox3D, OrientedRect, Plane, QuadraticSegment2D, Ray, Ray2D, Ray3D, Sphere, Tetrahedron, Triangle2D, Triangle3D}, matrix::{Matrix3, Matrix4}, vector::{Vector2, Vector3, Vector4}};
 pub trait Translation<Pos> {
     fn translate(&mut self, translation: Pos);
 }
@@ -32,9 +32,9 @@ impl<T: Real> Translation<Vector2<T>> for Matrix3<T> {
 
 impl<T: Real> Translation<Vector3<T>> for Matrix4<T> {
     fn translate(&mut self, translation: Vector3<T>) {
-        self.w.set_w(self.w.x() + translation.x());
-        self.w.set_w(self.w.y() + translation.y());
-        self.w.set_w(self.w.z() + translation.z());
+        self.w.set_x(self.w.x() + translation.x());
+        self.w.set_y(self.w.y() + translation.y());
+        self.w.set_z(self.w.z() + translation.z());
     }
 }
 
@@ -60,4 +60,87 @@ impl<T: Number> Translation<Vector2<T>> for CubicSegment2D<T> {
         self.control2 += translation;
         self.end += translation;
     }
-}
\ No newline at end of file
+}
+impl<T: Number> Translation<Vector2<T>> for Circle<T> {
+    fn translate(&mut self, translation: Vector2<T>) {
+        self.center += translation;
+    }
+}
+
+impl<T: Number> Translation<Vector3<T>> for Sphere<T> {
+    fn translate(&mut self, translation: Vector3<T>) {
+        self.center += translation;
+    }
+}
+
+impl<T: Number> Translation<Vector2<T>> for Triangle2D<T> {
+    fn translate(&mut self, translation: Vector2<T>) {
+        *self = *self+translation;
+    }
+}
+
+impl<T: Number> Translation<Vector3<T>> for Triangle3D<T> {
+    fn translate(&mut self, translation: Vector3<T>) {
+        *self = *self+translation;
+    }
+}
+
+impl<T: Number> Translation<Vector3<T>> for Tetrahedron<T> {
+    fn translate(&mut self, translation: Vector3<T>) {
+        *self = *self+translation;
+    }
+}
+
+impl<T: Number> Translation<Vector3<T>> for Plane<T> {
+    fn translate(&mut self, translation: Vector3<T>) {
+        let normal = self.normal();
+        // SAFETY: the normal comes from a plane, so it is already normalized.
+        *self = unsafe { Plane::from_normal_distance_unchecked(normal, self.distance()+normal.dot(&translation)) };
+    }
+}
+
+impl<T: Real> Translation<Vector2<T>> for Ray2D<T> {
+    fn translate(&mut self, translation: Vector2<T>) {
+        let origin = *self.origin()+translation;
+        *self = Ray2D::new(origin, origin+*self.direction());
+    }
+}
+
+impl<T: Real> Translation<Vector3<T>> for Ray3D<T> {
+    fn translate(&mut self, translation: Vector3<T>) {
+        let origin = *self.origin()+translation;
+        *self = Ray3D::new(origin, origin+*self.direction());
+    }
+}
+
+impl<T: Number> Translation<Vector2<T>> for OrientedRect<T> {
+    fn translate(&mut self, translation: Vector2<T>) {
+        self.center += translation;
+    }
+}
+
+impl<T: Real> Translation<Vector3<T>> for OrientedBox3D<T> {
+    fn translate(&mut self, translation: Vector3<T>) {
+        self.center += translation;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{matrix::{Matrix3, Matrix4, SquareMatrix}, vector::{DVec2, DVec3, DVec4}};
+
+    use super::Translation;
+
+    #[test]
+    fn translating_matrices() {
+        // every axis of a Matrix4 used to be written to w
+        let mut matrix = Matrix4::<f64>::identity();
+        Translation::translate(&mut matrix, DVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(matrix.w, DVec4::new(1.0, 2.0, 3.0, 1.0));
+        assert_eq!(matrix*DVec4::new(1.0, 1.0, 1.0, 1.0), DVec4::new(2.0, 3.0, 4.0, 1.0));
+
+        let mut matrix = Matrix3::<f64>::identity();
+        Translation::translate(&mut matrix, DVec2::new(-1.0, 2.0));
+        assert_eq!(matrix.z, DVec3::new(-1.0, 2.0, 1.0));
+    }
+}
@@ -146,17 +146,11 @@ pub(crate) mod impl_macros {
                 fn eq(&self, other: &T) -> bool {
                     true $(&& self.$element == *other)+
                 }
-                fn ne(&self, other: &T) -> bool {
-                    true $(&& self.$element == *other)+
-                }
             }
             impl<T: Number> core::cmp::PartialEq<Self>  for $vector<T>  {
                 fn eq(&self, other: &Self) -> bool {
                     true $(&& self.$element == other.$element)+
                 }
-                fn ne(&self, other: &Self) -> bool {
-                    true $(&& self.$element == other.$element)+
-                }
             }
             impl<T: Number> core::cmp::Eq for $vector <T> {}
             
@@ -1,4 +1,4 @@
-use crate::{matrix::{Matrix2, Matrix2x3, Matrix3, Matrix4}, vector::{vec2::Vector2, vec3::Vector3, vec4::Vector4}};
+use crate::{matrix::{Affine2, Affine3, Matrix2, Matrix2x3, Matrix3, Matrix4}, vector::{vec2::Vector2, vec3::Vector3, vec4::Vector4}};
 
 // Vector types
 pub type FVec2 = Vector2<f32>;
@@ -105,4 +105,10 @@ pub type USMat2x3 = Matrix2x3<u16>;
 pub type UIMat2x3 = Matrix2x3<u32>;
 pub type ULMat2x3 = Matrix2x3<u64>;
 pub type USizeMat2x3 = Matrix2x3<usize>;
-pub type ISizeMat2x3 = Matrix2x3<isize>;
\ No newline at end of file
+pub type ISizeMat2x3 = Matrix2x3<isize>;
+
+pub type FAffine2 = Affine2<f32>;
+pub type DAffine2 = Affine2<f64>;
+
+pub type FAffine3 = Affine3<f32>;
+pub type DAffine3 = Affine3<f64>;
\ No newline at end of file
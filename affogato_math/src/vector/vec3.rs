@@ -1,4 +1,4 @@
-use core::ops::{Index, IndexMut, Sub};
+use core::{cmp::Ordering, ops::{Index, IndexMut, Sub}};
 
 use bytemuck::{Pod, Zeroable};
 #[cfg(feature="serde")]
@@ -208,6 +208,13 @@ impl<T: Number> Vector3<T> {
         let normal = b.sub(a).cross(&c.sub(a)).normalize();
         normal.dot(&self.sub(a))
     }
+    /// The sign of [`Vector3::signed_plane_distance`] worked out exactly with
+    /// [`orient3d`](crate::geometry::orient3d), for when a point close to the
+    /// plane has to land on the right side of it.
+    pub fn plane_side_exact(&self, a: Self, b: Self, c: Self) -> Ordering 
+        where T: Real {
+        crate::geometry::orient3d(a, b, c, *self).partial_cmp(&T::ZERO).unwrap_or(Ordering::Equal)
+    }
     pub fn epsilon_eq(&self, p: Self, epsilon: T) -> bool 
         where T: Real {
         let p = (self.clone()-p).abs();
@@ -9,4 +9,13 @@ pub fn test_coordinates() {
     
     let polar = PolarCoordinate::new(1.0, 180.0f32.to_radians());
     assert!(Vector2::from(polar).epsilon_eq(Vector2::new(-1.0f32, 0.0f32), f32::EPSILON), "{:?}", Vector2::from(polar));
-}
\ No newline at end of file
+}
+#[test]
+pub fn test_inequality() {
+    // `!=` used to be the same as `==`
+    let a = Vector2::new(1.0f32, 2.0);
+    assert!(a != Vector2::new(1.0, 3.0));
+    assert!(!(a != Vector2::new(1.0, 2.0)));
+    assert!(Vector2::new(2.0f32, 2.0) != 3.0);
+    assert!(!(Vector2::new(2.0f32, 2.0) != 2.0));
+}
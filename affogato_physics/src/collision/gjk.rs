@@ -4,7 +4,7 @@ extern crate alloc;
 use affogato_core::{groups::vector_spaces::{VectorSpace, NormedVectorSpace}, num::Zero, sets::Real};
 use alloc::fmt::{Debug, Display};
 
-use affogato_math::{geometry::Dimension, matrix::{Matrix4, SquareMatrix}, vector::{Vector3, Vector4}, Transformation};
+use affogato_math::{geometry::{convex_hull_3d, Dimension}, matrix::{Matrix4, SquareMatrix}, vector::{Vector3, Vector4}, Transformation};
 use core::ops::{Add, Mul};
 use alloc::vec::Vec;
 use super::{Collision, CollisionInfo, HitCollisionInfo};
@@ -331,6 +331,20 @@ impl<T: Real> GJKColliderSolid<T> {
     pub fn new(vertices: Vec<Vector3<T>>, transform: Matrix4<T>) -> Self {
         GJKColliderSolid { vertices, transform }
     }
+    /// Keeps only the corners of the convex hull of a point cloud or mesh.
+    /// Collinear points have no hull and are all kept.
+    pub fn from_points(points: &[Vector3<T>], transform: Matrix4<T>) -> Self {
+        let triangles = convex_hull_3d(points);
+        if triangles.is_empty() {
+            return GJKColliderSolid { vertices: points.to_vec(), transform };
+        }
+        let mut on_hull = alloc::vec![false; points.len()];
+        for index in triangles {
+            on_hull[index as usize] = true;
+        }
+        let vertices = points.iter().zip(on_hull).filter(|(_, on_hull)| *on_hull).map(|(&point, _)| point).collect();
+        GJKColliderSolid { vertices, transform }
+    }
 }
 
 impl<T: Real> Collision<Self> for GJKColliderSolid<T> {
@@ -1,7 +1,7 @@
 #![cfg(feature="alloc")]
 
 use affogato_core::{num::Zero, sets::Real};
-use affogato_math::{matrix::Matrix3, vector::{NormedVectorSpace, Vector2, Vector3, VectorSpace}};
+use affogato_math::{geometry::{convex_hull_2d, Polygon2D}, matrix::{Matrix3, SquareMatrix}, vector::{NormedVectorSpace, Vector2, Vector3, VectorSpace}};
 
 use crate::collision::{Collision, CollisionInfo};
 extern crate alloc;
@@ -184,10 +184,143 @@ impl<T: Real> GJKColliderFlat<T> {
     pub fn new(vertices: alloc::vec::Vec<Vector2<T>>, transform: Matrix3<T>) -> Self {
         GJKColliderFlat { vertices, transform }
     }
+    /// Keeps only the corners of the convex hull of a point cloud.
+    pub fn from_points(points: &[Vector2<T>], transform: Matrix3<T>) -> Self {
+        let vertices = convex_hull_2d(points).into_iter().map(|index| points[index as usize]).collect();
+        GJKColliderFlat { vertices, transform }
+    }
 }
 impl<T: Real> Collision<Self> for GJKColliderFlat<T> {
     type CollisionInfo = CollisionInfo<Vector2<T>>;
     fn collides(&self, object: &Self) -> Option<Self::CollisionInfo> {
         gjk_epa(&self.vertices, &object.vertices, &self.transform, &object.transform)
     }
-}
\ No newline at end of file
+}
+/// Convex polygons become colliders with an identity transform. A concave
+/// polygon is given back as the error, since GJK only works on convex shapes.
+impl<T: Real> TryFrom<Polygon2D<T>> for GJKColliderFlat<T> {
+    type Error = Polygon2D<T>;
+    fn try_from(polygon: Polygon2D<T>) -> Result<Self, Self::Error> {
+        if polygon.is_convex() {
+            Ok(GJKColliderFlat::new(polygon.vertices, Matrix3::identity()))
+        } else {
+            Err(polygon)
+        }
+    }
+}
+
+/// A collider made of several convex pieces sharing one transform, for
+/// concave shapes that GJK can't handle in one go.
+pub struct GJKCompoundColliderFlat<T: Real> {
+    pub pieces: alloc::vec::Vec<alloc::vec::Vec<Vector2<T>>>,
+    pub transform: Matrix3<T>,
+}
+impl<T: Real> GJKCompoundColliderFlat<T> {
+    pub fn new(pieces: alloc::vec::Vec<alloc::vec::Vec<Vector2<T>>>, transform: Matrix3<T>) -> Self {
+        GJKCompoundColliderFlat { pieces, transform }
+    }
+    /// Splits a possibly concave polygon into convex pieces.
+    pub fn from_polygon(polygon: &Polygon2D<T>, transform: Matrix3<T>) -> Self {
+        let pieces = polygon.convex_decomposition().into_iter().map(|piece| piece.vertices).collect();
+        GJKCompoundColliderFlat { pieces, transform }
+    }
+}
+/// Merges the contacts of several pieces into one, as deep as the deepest
+/// contact and pushing along the average of the normals weighted by depth.
+fn merge_contacts<T: Real>(contacts: impl Iterator<Item = CollisionInfo<Vector2<T>>>) -> Option<CollisionInfo<Vector2<T>>> {
+    let mut deepest: Option<CollisionInfo<Vector2<T>>> = None;
+    let mut normal = Vector2::<T>::ZERO;
+    for contact in contacts {
+        normal = normal + contact.normal*contact.distance;
+        if deepest.as_ref().is_none_or(|deepest| contact.distance > deepest.distance) {
+            deepest = Some(contact);
+        }
+    }
+    let mut deepest = deepest?;
+    if normal.length_squared() > T::ZERO {
+        deepest.normal = normal.normalize();
+    }
+    Some(deepest)
+}
+impl<T: Real> Collision<GJKColliderFlat<T>> for GJKCompoundColliderFlat<T> {
+    type CollisionInfo = CollisionInfo<Vector2<T>>;
+    fn collides(&self, object: &GJKColliderFlat<T>) -> Option<Self::CollisionInfo> {
+        merge_contacts(self.pieces.iter().filter_map(|piece| {
+            gjk_epa(piece, &object.vertices, &self.transform, &object.transform)
+        }))
+    }
+}
+impl<T: Real> Collision<GJKCompoundColliderFlat<T>> for GJKColliderFlat<T> {
+    type CollisionInfo = CollisionInfo<Vector2<T>>;
+    fn collides(&self, object: &GJKCompoundColliderFlat<T>) -> Option<Self::CollisionInfo> {
+        merge_contacts(object.pieces.iter().filter_map(|piece| {
+            gjk_epa(&self.vertices, piece, &self.transform, &object.transform)
+        }))
+    }
+}
+impl<T: Real> Collision<Self> for GJKCompoundColliderFlat<T> {
+    type CollisionInfo = CollisionInfo<Vector2<T>>;
+    fn collides(&self, object: &Self) -> Option<Self::CollisionInfo> {
+        merge_contacts(self.pieces.iter().flat_map(|piece| object.pieces.iter().map(move |other| (piece, other))).filter_map(|(piece, other)| {
+            gjk_epa(piece, other, &self.transform, &object.transform)
+        }))
+    }
+}
+/// Any polygon becomes a compound collider with an identity transform.
+impl<T: Real> From<Polygon2D<T>> for GJKCompoundColliderFlat<T> {
+    fn from(polygon: Polygon2D<T>) -> Self {
+        GJKCompoundColliderFlat::from_polygon(&polygon, Matrix3::identity())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use affogato_math::{geometry::Polygon2D, matrix::{Matrix3, SquareMatrix}, vector::{DVec2, NormedVectorSpace}};
+
+    use crate::collision::{Collision, CollisionInfo};
+
+    use super::{merge_contacts, GJKColliderFlat, GJKCompoundColliderFlat};
+    extern crate alloc;
+
+    fn square(min: DVec2, size: f64) -> alloc::vec::Vec<DVec2> {
+        alloc::vec![min, min + DVec2::new(size, 0.0), min + DVec2::new(size, size), min + DVec2::new(0.0, size)]
+    }
+    /// An L with arms 4 long and 1 wide, concave around (1, 1).
+    fn l_shape(offset: DVec2) -> Polygon2D<f64> {
+        let corners = [(0.0, 0.0), (4.0, 0.0), (4.0, 1.0), (1.0, 1.0), (1.0, 4.0), (0.0, 4.0)];
+        Polygon2D::new(corners.into_iter().map(|(x, y)| DVec2::new(x, y) + offset).collect())
+    }
+
+    #[test]
+    fn compound_colliders() {
+        assert!(GJKColliderFlat::try_from(l_shape(DVec2::new(0.0, 0.0))).is_err());
+        assert!(GJKColliderFlat::try_from(Polygon2D::new(square(DVec2::new(0.0, 0.0), 1.0))).is_ok());
+
+        let compound = GJKCompoundColliderFlat::from(l_shape(DVec2::new(0.0, 0.0)));
+        let overlapping = GJKColliderFlat::new(square(DVec2::new(2.0, 0.25), 0.5), Matrix3::identity());
+        // inside the hull of the L but in the notch, so it only touches the hull
+        let notched = GJKColliderFlat::new(square(DVec2::new(2.0, 2.0), 1.0), Matrix3::identity());
+        assert!(compound.collides(&overlapping).is_some_and(|contact| contact.distance > 0.0));
+        assert!(overlapping.collides(&compound).is_some_and(|contact| contact.distance > 0.0));
+        assert!(compound.collides(&notched).is_none());
+        assert!(notched.collides(&compound).is_none());
+        let hull = GJKColliderFlat::from_points(&l_shape(DVec2::new(0.0, 0.0)).vertices, Matrix3::identity());
+        assert!(hull.collides(&notched).is_some());
+
+        assert!(compound.collides(&GJKCompoundColliderFlat::from(l_shape(DVec2::new(0.5, 0.5)))).is_some());
+        assert!(compound.collides(&GJKCompoundColliderFlat::from(l_shape(DVec2::new(1.5, 1.5)))).is_none());
+    }
+
+    #[test]
+    fn merged_contacts() {
+        let contacts = [
+            CollisionInfo { distance: 0.5, normal: DVec2::new(1.0, 0.0) },
+            CollisionInfo { distance: 2.0, normal: DVec2::new(0.0, 1.0) },
+            CollisionInfo { distance: 1.0, normal: DVec2::new(0.0, 1.0) },
+        ];
+        let merged = merge_contacts(contacts.into_iter()).unwrap();
+        assert_eq!(merged.distance, 2.0);
+        assert!((merged.normal - DVec2::new(0.5, 3.0).normalize()).length() < 1e-12);
+        assert!(merge_contacts(core::iter::empty::<CollisionInfo<DVec2>>()).is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use affogato_core::{num::{FloatingPoint, Zero}, groups::vector_spaces::{NormedVectorSpace, VectorSpace}};
-use affogato_math::{matrix::SquareMatrix, vector::{FMat3, FMat4, FVec3}};
+use affogato_math::{geometry::Frustum, matrix::SquareMatrix, vector::{FMat3, FMat4, FVec3}};
 
 #[cfg(feature="serde")]
 use serde::{Serialize, Deserialize};
@@ -103,6 +103,10 @@ impl Camera {
     pub fn get_camera_matrix(&self) -> FMat4 {
         self.projection*self.view
     }
+    /// The volume the camera sees in world space, for culling.
+    pub fn frustum(&self) -> Frustum<f32> {
+        Frustum::from_matrix(&self.get_camera_matrix())
+    }
     pub fn get_view(&self) -> FMat4 {
         self.view.clone()
     }
@@ -1,8 +1,8 @@
 #![cfg(feature="alloc")]
 extern crate alloc;
-use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::Number, sets::Real};
+use affogato_core::{groups::vector_spaces::{NormedVectorSpace, VectorSpace}, num::{FloatingPoint, Number}, sets::Real};
 use alloc::vec::Vec;
-use affogato_math::{geometry::{Rect3D, CubicSegment2D, LinearSegment2D, QuadraticSegment2D, Rect, Segment2D, Triangle2D, Triangle3D}, vector::{Vector2, Vector3}};
+use affogato_math::{geometry::{ArcSegment2D, Capsule, Capsule2D, Cone, Ellipse, OrientedRect, RoundedRect, Cylinder, OrientedBox3D, Torus, Rect3D, CubicSegment2D, CubicSegment3D, LinearSegment2D, LinearSegment3D, MultiPolygon2D, Polygon2D, Polygon3D, QuadraticSegment3D, Segment, Segment3D, Path2D, QuadraticSegment2D, Rect, Segment2D, Triangle2D, Triangle3D, TriangleMesh2D}, vector::{Vector2, Vector3}};
 use affogato_physics::kinematics::KinematicSegmentList;
 pub enum VertexTopology {
     Point,
@@ -108,19 +108,408 @@ impl<T: Number> Geometry<Vector2<T>> for CubicSegment2D<T> {
     }
 }
 
+/// The amount of line segments used to draw a quarter turn of an [`ArcSegment2D`].
+const ARC_SEGMENTS_PER_QUARTER_TURN: f64 = 8.0;
+
+impl<T: Number> Geometry<Vector2<T>> for ArcSegment2D<T> {
+    /// Points sampled evenly along the angle of the arc.
+    fn vertices(&self) -> Vec<Vector2<T>> {
+        let sweep = self.sweep_angle.to_f64();
+        let segments = (sweep.abs()/core::f64::consts::FRAC_PI_2*ARC_SEGMENTS_PER_QUARTER_TURN).ceil().max(1.0) as usize;
+        (0..=segments)
+            .map(|i| self.point_at_angle(self.start_angle.to_f64() + sweep*i as f64/segments as f64))
+            .collect()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        let count = self.vertices().len() as u32;
+        match topology {
+            VertexTopology::Line => Some((1..count).flat_map(|i| [i-1, i]).collect()),
+            VertexTopology::Point => Some((0..count).collect()),
+            _ => None,
+        }
+    }
+}
+
 impl<T: Number> Geometry<Vector2<T>> for Segment2D<T> {
     fn vertices(&self) -> Vec<Vector2<T>> {
         match self {
             Segment2D::Linear(linear) => linear.vertices(),
             Segment2D::Quadratic(quadratic) => quadratic.vertices(),
             Segment2D::Cubic(cubic) => cubic.vertices(),
+            Segment2D::Arc(arc) => arc.vertices(),
         }
     }
+    /// A segment has no width, so there are no triangles to index and
+    /// [`VertexTopology::Triangle`] gives `None`. [`Segment2D::stroke`] gives
+    /// a [`TriangleMesh2D`] with its own vertices for drawing it filled.
     fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
         match self {
             Segment2D::Linear(linear) => linear.indices(topology),
             Segment2D::Quadratic(quadratic) => quadratic.indices(topology),
             Segment2D::Cubic(cubic) => cubic.indices(topology),
+            Segment2D::Arc(arc) => arc.indices(topology),
+        }
+    }
+}
+
+/// The amount of line segments used to draw a curved 3D segment.
+const CURVE_SEGMENTS_3D: usize = 32;
+
+/// Points sampled evenly in t along a 3D segment, joined as a line strip.
+macro_rules! impl_segment3d_geometry {
+    ($segment:ident, $segments:expr) => {
+        impl<T: Real> Geometry<Vector3<T>> for $segment<T> {
+            fn vertices(&self) -> Vec<Vector3<T>> {
+                (0..=$segments).map(|i| Segment::get(self, i as f64/$segments as f64)).collect()
+            }
+            fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+                let count = $segments as u32 + 1;
+                match topology {
+                    VertexTopology::Line => Some((1..count).flat_map(|i| [i-1, i]).collect()),
+                    VertexTopology::Point => Some((0..count).collect()),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+impl_segment3d_geometry!(LinearSegment3D, 1);
+impl_segment3d_geometry!(QuadraticSegment3D, CURVE_SEGMENTS_3D);
+impl_segment3d_geometry!(CubicSegment3D, CURVE_SEGMENTS_3D);
+
+impl<T: Real> Geometry<Vector3<T>> for Segment3D<T> {
+    fn vertices(&self) -> Vec<Vector3<T>> {
+        match self {
+            Segment3D::Linear(linear) => linear.vertices(),
+            Segment3D::Quadratic(quadratic) => quadratic.vertices(),
+            Segment3D::Cubic(cubic) => cubic.vertices(),
+        }
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        match self {
+            Segment3D::Linear(linear) => linear.indices(topology),
+            Segment3D::Quadratic(quadratic) => quadratic.indices(topology),
+            Segment3D::Cubic(cubic) => cubic.indices(topology),
+        }
+    }
+}
+
+/// The amount of segments around curved surfaces.
+const SURFACE_SEGMENTS: usize = 32;
+
+/// A surface made by turning a profile around an axis, as a grid of rings
+/// joined by quads. Profile points on the axis become a single vertex.
+struct Revolution<T: Number> {
+    vertices: Vec<Vector3<T>>,
+    triangles: Vec<u32>,
+    lines: Vec<u32>,
+}
+
+impl<T: Real> Revolution<T> {
+    /// Turns `profile` around the unit `axis` through `origin`, where each
+    /// point of the profile is how far it is out from the axis and how far
+    /// along it. With `closed` the profile wraps back around to its start.
+    /// Triangles wind counterclockwise seen from outside when the profile
+    /// goes up the axis on its outer side.
+    fn new(origin: Vector3<T>, axis: Vector3<T>, profile: &[(T, T)], closed: bool) -> Self {
+        let helper = if axis.x().abs() < T::from_f64(0.9) { Vector3::right() } else { Vector3::top() };
+        let side = axis.cross(&helper).normalize();
+        let other = axis.cross(&side);
+        let mut vertices = Vec::new();
+        // the first vertex of each ring and whether it is a single point
+        let mut rings: Vec<(u32, bool)> = Vec::with_capacity(profile.len());
+        for &(radius, height) in profile {
+            let center = origin + axis*height;
+            rings.push((vertices.len() as u32, radius == T::ZERO));
+            if radius == T::ZERO {
+                vertices.push(center);
+                continue;
+            }
+            vertices.extend((0..SURFACE_SEGMENTS).map(|i| {
+                let (sin, cos) = (T::from_f64(2.0*core::f64::consts::PI*i as f64/SURFACE_SEGMENTS as f64)).sin_cos();
+                center + (side*cos + other*sin)*radius
+            }));
+        }
+        let vertex = |(start, point): (u32, bool), i: usize| if point { start } else { start + (i%SURFACE_SEGMENTS) as u32 };
+        let mut triangles = Vec::new();
+        let mut lines = Vec::new();
+        let bands = if closed { rings.len() } else { rings.len().saturating_sub(1) };
+        for band in 0..bands {
+            let (below, above) = (rings[band], rings[(band+1)%rings.len()]);
+            for i in 0..SURFACE_SEGMENTS {
+                if !below.1 {
+                    triangles.extend([vertex(below, i), vertex(below, i+1), vertex(above, i+1)]);
+                }
+                if !above.1 {
+                    triangles.extend([vertex(below, i), vertex(above, i+1), vertex(above, i)]);
+                }
+                lines.extend([vertex(below, i), vertex(above, i)]);
+            }
+        }
+        for &ring in rings.iter().filter(|ring| !ring.1) {
+            lines.extend((0..SURFACE_SEGMENTS).flat_map(|i| [vertex(ring, i), vertex(ring, i+1)]));
+        }
+        Self { vertices, triangles, lines }
+    }
+    fn indices(self, topology: VertexTopology) -> Vec<u32> {
+        match topology {
+            VertexTopology::Line => self.lines,
+            VertexTopology::Point => (0..self.vertices.len() as u32).collect(),
+            VertexTopology::Triangle => self.triangles,
+        }
+    }
+}
+
+/// Points around a quarter of a circle of `radius` starting at `from` turns,
+/// as distances out from the axis and along it from `height`. The ends on
+/// the axis are exactly on it.
+fn quarter_circle<T: Real>(radius: T, height: T, from: f64) -> impl Iterator<Item = (T, T)> {
+    let steps = SURFACE_SEGMENTS/4;
+    (0..=steps).map(move |i| {
+        let turn = from + 0.25*i as f64/steps as f64;
+        let (sin, cos) = T::from_f64(2.0*core::f64::consts::PI*turn).sin_cos();
+        let out = if turn.abs() == 0.25 { T::ZERO } else { radius*cos };
+        (out, height + radius*sin)
+    })
+}
+
+fn capsule_revolution<T: Real>(capsule: &Capsule<T>) -> Revolution<T> {
+    let height = capsule.height();
+    let axis = if height == T::ZERO { Vector3::top() } else { (capsule.end-capsule.start)/height };
+    let profile: Vec<(T, T)> = quarter_circle(capsule.radius, T::ZERO, -0.25)
+        .chain(quarter_circle(capsule.radius, height, 0.0))
+        .collect();
+    Revolution::new(capsule.start, axis, &profile, false)
+}
+
+fn cylinder_revolution<T: Real>(cylinder: &Cylinder<T>) -> Revolution<T> {
+    let (radius, height) = (cylinder.radius, cylinder.height());
+    let profile = [(T::ZERO, T::ZERO), (radius, T::ZERO), (radius, height), (T::ZERO, height)];
+    Revolution::new(cylinder.start, cylinder.axis(), &profile, false)
+}
+
+fn cone_revolution<T: Real>(cone: &Cone<T>) -> Revolution<T> {
+    let profile = [(T::ZERO, T::ZERO), (cone.radius, T::ZERO), (T::ZERO, cone.height())];
+    Revolution::new(cone.base, cone.axis(), &profile, false)
+}
+
+fn torus_revolution<T: Real>(torus: &Torus<T>) -> Revolution<T> {
+    let profile: Vec<(T, T)> = (0..SURFACE_SEGMENTS).map(|i| {
+        let (sin, cos) = T::from_f64(2.0*core::f64::consts::PI*i as f64/SURFACE_SEGMENTS as f64).sin_cos();
+        (torus.major_radius + torus.minor_radius*cos, torus.minor_radius*sin)
+    }).collect();
+    Revolution::new(torus.center, torus.axis, &profile, true)
+}
+
+/// Curved solids as rings of [`SURFACE_SEGMENTS`] vertices around their axis.
+macro_rules! impl_revolution_geometry {
+    ($shape:ident, $revolution:ident) => {
+        impl<T: Real> Geometry<Vector3<T>> for $shape<T> {
+            fn vertices(&self) -> Vec<Vector3<T>> {
+                $revolution(self).vertices
+            }
+            fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+                Some($revolution(self).indices(topology))
+            }
+        }
+    };
+}
+impl_revolution_geometry!(Capsule, capsule_revolution);
+impl_revolution_geometry!(Cylinder, cylinder_revolution);
+impl_revolution_geometry!(Cone, cone_revolution);
+impl_revolution_geometry!(Torus, torus_revolution);
+
+impl<T: Real> Geometry<Vector3<T>> for OrientedBox3D<T> {
+    fn vertices(&self) -> Vec<Vector3<T>> {
+        self.corners().to_vec()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        match topology {
+            VertexTopology::Line => Some(Rect3D::<T>::edge_indices().to_vec()),
+            VertexTopology::Point => Some((0..8).collect()),
+            VertexTopology::Triangle => Some(Rect3D::<T>::tri_indices().to_vec()),
+        }
+    }
+}
+
+impl<T: Number> Geometry<Vector2<T>> for Path2D<T> {
+    /// The control points of every subpath, where consecutive segments
+    /// share the point they meet at.
+    fn vertices(&self) -> Vec<Vector2<T>> {
+        let mut vertices = Vec::new();
+        for subpath in self.subpaths() {
+            vertices.push(subpath.start);
+            for segment in subpath.segments.iter() {
+                vertices.extend(segment.vertices().into_iter().skip(1));
+            }
+        }
+        vertices
+    }
+    /// Like a [`Segment2D`] a path has no triangles of its own, stroke it
+    /// with [`Path2D::stroke`] to get a [`TriangleMesh2D`] instead.
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        match topology {
+            VertexTopology::Line => {
+                let mut indices = Vec::new();
+                let mut offset = 0;
+                for subpath in self.subpaths() {
+                    let start = offset;
+                    for segment in subpath.segments.iter() {
+                        for _ in 1..segment.vertices().len() {
+                            indices.push(offset);
+                            indices.push(offset+1);
+                            offset += 1;
+                        }
+                    }
+                    if subpath.closed && offset != start {
+                        indices.push(offset);
+                        indices.push(start);
+                    }
+                    offset += 1;
+                }
+                Some(indices)
+            },
+            VertexTopology::Point => Some((0..self.vertices().len() as u32).collect()),
+            _ => None,
+        }
+    }
+}
+
+/// The indices shared by both polygons, where convex polygons are split into
+/// a fan of triangles.
+fn polygon_indices(count: u32, triangulate: impl FnOnce() -> Vec<u32>, topology: VertexTopology) -> Option<Vec<u32>> {
+    match topology {
+        VertexTopology::Line => Some((0..count).flat_map(|i| [i, (i+1)%count]).collect()),
+        VertexTopology::Point => Some((0..count).collect()),
+        VertexTopology::Triangle => Some(triangulate()),
+    }
+}
+
+impl<T: Real> Geometry<Vector2<T>> for Polygon2D<T> {
+    fn vertices(&self) -> Vec<Vector2<T>> {
+        self.vertices.clone()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        polygon_indices(self.len() as u32, || self.triangulate(), topology)
+    }
+}
+
+impl<T: Real> Geometry<Vector3<T>> for Polygon3D<T> {
+    fn vertices(&self) -> Vec<Vector3<T>> {
+        self.vertices.clone()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        polygon_indices(self.len() as u32, || self.to_plane().triangulate(), topology)
+    }
+}
+
+/// Convex outlines are drawn as a loop and filled with a fan from their first
+/// vertex.
+fn convex_indices(count: u32, topology: VertexTopology) -> Option<Vec<u32>> {
+    polygon_indices(count, || (1..count.saturating_sub(1)).flat_map(|i| [0, i, i+1]).collect(), topology)
+}
+
+/// Points counterclockwise around `center` from `start` radians through
+/// `sweep`, both ends included.
+fn circular_arc<T: Real>(center: Vector2<T>, radius: T, start: f64, sweep: f64) -> impl Iterator<Item = Vector2<T>> {
+    let segments = (sweep/core::f64::consts::FRAC_PI_2*ARC_SEGMENTS_PER_QUARTER_TURN).ceil().max(1.0) as usize;
+    (0..=segments).map(move |i| {
+        let (sin, cos) = T::from_f64(start + sweep*i as f64/segments as f64).sin_cos();
+        center + Vector2::new(cos, sin)*radius
+    })
+}
+
+impl<T: Real> Geometry<Vector2<T>> for Ellipse<T> {
+    /// Points around the outline evenly spaced in angle, counterclockwise.
+    fn vertices(&self) -> Vec<Vector2<T>> {
+        let segments = (4.0*ARC_SEGMENTS_PER_QUARTER_TURN) as usize;
+        (0..segments)
+            .map(|i| self.point_at(T::from_f64(2.0*core::f64::consts::PI*i as f64/segments as f64)))
+            .collect()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        convex_indices(self.vertices().len() as u32, topology)
+    }
+}
+
+impl<T: Real> Geometry<Vector2<T>> for Capsule2D<T> {
+    /// The half circle around `end` followed by the one around `start`.
+    fn vertices(&self) -> Vec<Vector2<T>> {
+        let axis = self.end-self.start;
+        let angle = if axis.length_squared() == T::ZERO { 0.0 } else { axis.y().atan2(axis.x()).to_f64() };
+        let half_turn = core::f64::consts::PI;
+        circular_arc(self.end, self.radius, angle - half_turn/2.0, half_turn)
+            .chain(circular_arc(self.start, self.radius, angle + half_turn/2.0, half_turn))
+            .collect()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        convex_indices(self.vertices().len() as u32, topology)
+    }
+}
+
+impl<T: Real> Geometry<Vector2<T>> for OrientedRect<T> {
+    fn vertices(&self) -> Vec<Vector2<T>> {
+        self.corners().to_vec()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        match topology {
+            VertexTopology::Line => Some(Rect::<T>::edge_indices().to_vec()),
+            VertexTopology::Point => Some((0..4).collect()),
+            VertexTopology::Triangle => Some(Rect::<T>::tri_indices().to_vec()),
+        }
+    }
+}
+
+impl<T: Real> Geometry<Vector2<T>> for RoundedRect<T> {
+    /// A quarter circle for each corner, counterclockwise from the one at
+    /// the minimum.
+    fn vertices(&self) -> Vec<Vector2<T>> {
+        let quarter_turn = core::f64::consts::FRAC_PI_2;
+        self.corner_centers().into_iter().enumerate()
+            .flat_map(|(i, center)| circular_arc(center, self.radius, quarter_turn*(i as f64 + 2.0), quarter_turn))
+            .collect()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        convex_indices(self.vertices().len() as u32, topology)
+    }
+}
+
+/// The contours one after another, as from [`MultiPolygon2D::triangulate`].
+impl<T: Real> Geometry<Vector2<T>> for MultiPolygon2D<T> {
+    fn vertices(&self) -> Vec<Vector2<T>> {
+        self.polygons().flat_map(|polygon| polygon.vertices.iter().copied()).collect()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        match topology {
+            VertexTopology::Line => {
+                let mut indices = Vec::new();
+                let mut offset = 0;
+                for polygon in self.polygons() {
+                    let count = polygon.len() as u32;
+                    indices.extend((0..count).flat_map(|i| [offset+i, offset+(i+1)%count]));
+                    offset += count;
+                }
+                Some(indices)
+            },
+            VertexTopology::Point => Some((0..self.polygons().map(|polygon| polygon.len() as u32).sum()).collect()),
+            VertexTopology::Triangle => Some(self.triangulate()),
+        }
+    }
+}
+
+/// Meshes produced by tessellation, such as the strokes from
+/// [`Segment2D::stroke`] and [`Path2D::stroke`].
+impl<T: Number> Geometry<Vector2<T>> for TriangleMesh2D<T> {
+    fn vertices(&self) -> Vec<Vector2<T>> {
+        self.vertices.clone()
+    }
+    fn indices(&self, topology: VertexTopology) -> Option<Vec<u32>> {
+        match topology {
+            VertexTopology::Triangle => Some(self.indices.clone()),
+            VertexTopology::Line => Some(self.indices.chunks_exact(3).flat_map(|triangle| {
+                [triangle[0], triangle[1], triangle[1], triangle[2], triangle[2], triangle[0]]
+            }).collect()),
+            VertexTopology::Point => Some((0..self.vertices.len() as u32).collect()),
         }
     }
 }
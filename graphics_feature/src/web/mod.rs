@@ -1,5 +1,5 @@
 use affogato_core::{num::Number, sets::Real};
-use affogato_math::{geometry::{CalculateCentroid, Circle, CubicSegment2D, HyperSphere, LinearSegment2D, QuadraticSegment2D, Rect, Segment2D, Triangle2D}, vector::{DVec2, Vector2, VectorSpace}};
+use affogato_math::{geometry::{ArcSegment2D, CalculateCentroid, Capsule2D, Circle, Ellipse, OrientedRect, RoundedRect, CubicSegment2D, HyperSphere, LinearSegment2D, Path2D, QuadraticSegment2D, Rect, Segment2D, Triangle2D}, vector::{DVec2, Vector2, VectorSpace}};
 use web_sys::CanvasRenderingContext2d;
 
 pub trait CanvasRenderable<V: VectorSpace> {
@@ -61,6 +61,28 @@ impl<T: Number> CanvasRenderable<Vector2<T>> for CubicSegment2D<T> {
     }
 }
 
+/// Adds the arc to the current path of the canvas, a line is drawn from
+/// the current point to the start of the arc.
+fn trace_arc<T: Number>(arc: &ArcSegment2D<T>, context: &CanvasRenderingContext2d) {
+    context.ellipse_with_anticlockwise(
+        arc.center.x().to_f64(), arc.center.y().to_f64(), 
+        arc.radii.x().to_f64(), arc.radii.y().to_f64(), 
+        arc.rotation.to_f64(), 
+        arc.start_angle.to_f64(), arc.end_angle().to_f64(), 
+        arc.sweep_angle < T::ZERO
+    ).expect("Failed to draw arc in canvas");
+}
+
+impl<T: Number> CanvasRenderable<Vector2<T>> for ArcSegment2D<T> {
+    type Context = CanvasRenderingContext2d;
+    fn fill_canvas(&self, context: &Self::Context) {
+        context.begin_path();
+        trace_arc(self, context);
+        context.fill();
+        context.close_path();
+    }
+}
+
 impl<T: Number> CanvasRenderable<Vector2<T>> for Segment2D<T> {
     type Context = CanvasRenderingContext2d;
     fn fill_canvas(&self, context: &Self::Context) {
@@ -68,7 +90,37 @@ impl<T: Number> CanvasRenderable<Vector2<T>> for Segment2D<T> {
             Segment2D::Linear(linear) => linear.fill_canvas(context),
             Segment2D::Quadratic(quadratic) => quadratic.fill_canvas(context),
             Segment2D::Cubic(cubic) => cubic.fill_canvas(context),
+            Segment2D::Arc(arc) => arc.fill_canvas(context),
+        }
+    }
+}
+
+impl<T: Number> CanvasRenderable<Vector2<T>> for Path2D<T> {
+    type Context = CanvasRenderingContext2d;
+    fn fill_canvas(&self, context: &Self::Context) {
+        context.begin_path();
+        for subpath in self.subpaths() {
+            context.move_to(subpath.start.x().to_f64(), subpath.start.y().to_f64());
+            for segment in subpath.segments.iter() {
+                match segment {
+                    Segment2D::Linear(linear) => context.line_to(linear.end.x().to_f64(), linear.end.y().to_f64()),
+                    Segment2D::Quadratic(quadratic) => context.quadratic_curve_to(
+                        quadratic.control.x().to_f64(), quadratic.control.y().to_f64(), 
+                        quadratic.end.x().to_f64(), quadratic.end.y().to_f64()
+                    ),
+                    Segment2D::Cubic(cubic) => context.bezier_curve_to(
+                        cubic.control1.x().to_f64(), cubic.control1.y().to_f64(), 
+                        cubic.control2.x().to_f64(), cubic.control2.y().to_f64(), 
+                        cubic.end.x().to_f64(), cubic.end.y().to_f64()
+                    ),
+                    Segment2D::Arc(arc) => trace_arc(arc, context),
+                }
+            }
+            if subpath.closed {
+                context.close_path();
+            }
         }
+        context.fill();
     }
 }
 
@@ -83,6 +135,63 @@ impl<T: Number> CanvasRenderable<Vector2<T>> for Triangle2D<T> {
         context.close_path();
     }
 }
+impl<T: Real> CanvasRenderable<Vector2<T>> for Ellipse<T> {
+    type Context = CanvasRenderingContext2d;
+    fn fill_canvas(&self, context: &Self::Context) {
+        context.begin_path();
+        context.ellipse(
+            self.center.x().to_f64(), self.center.y().to_f64(), 
+            self.radii.x().to_f64(), self.radii.y().to_f64(), 
+            self.rotation.to_f64(), 
+            0.0f64, core::f64::consts::TAU
+        ).expect("Failed to draw ellipse in canvas");
+        context.fill();
+        context.close_path();
+    }
+}
+impl<T: Real> CanvasRenderable<Vector2<T>> for Capsule2D<T> {
+    type Context = CanvasRenderingContext2d;
+    fn fill_canvas(&self, context: &Self::Context) {
+        let axis = self.end-self.start;
+        let angle = if axis.length_squared() == T::ZERO { 0.0 } else { axis.y().atan2(axis.x()).to_f64() };
+        let (quarter_turn, radius) = (core::f64::consts::FRAC_PI_2, self.radius.to_f64());
+        context.begin_path();
+        // the lines along the sides join up the half circles on both ends
+        context.arc(self.end.x().to_f64(), self.end.y().to_f64(), radius, angle - quarter_turn, angle + quarter_turn)
+            .expect("Failed to draw arc in canvas");
+        context.arc(self.start.x().to_f64(), self.start.y().to_f64(), radius, angle + quarter_turn, angle + 3.0*quarter_turn)
+            .expect("Failed to draw arc in canvas");
+        context.fill();
+        context.close_path();
+    }
+}
+impl<T: Real> CanvasRenderable<Vector2<T>> for OrientedRect<T> {
+    type Context = CanvasRenderingContext2d;
+    fn fill_canvas(&self, context: &Self::Context) {
+        let [first, rest @ ..] = self.corners();
+        context.begin_path();
+        context.move_to(first.x().to_f64(), first.y().to_f64());
+        for corner in rest {
+            context.line_to(corner.x().to_f64(), corner.y().to_f64());
+        }
+        context.fill();
+        context.close_path();
+    }
+}
+impl<T: Real> CanvasRenderable<Vector2<T>> for RoundedRect<T> {
+    type Context = CanvasRenderingContext2d;
+    fn fill_canvas(&self, context: &Self::Context) {
+        let (quarter_turn, radius) = (core::f64::consts::FRAC_PI_2, self.radius.to_f64());
+        context.begin_path();
+        for (i, center) in self.corner_centers().into_iter().enumerate() {
+            let start = quarter_turn*(i as f64 + 2.0);
+            context.arc(center.x().to_f64(), center.y().to_f64(), radius, start, start + quarter_turn)
+                .expect("Failed to draw arc in canvas");
+        }
+        context.fill();
+        context.close_path();
+    }
+}
 impl<T: Number, R: CanvasRenderable<Vector2<T>, Context = CanvasRenderingContext2d>> CanvasRenderable<Vector2<T>> for &[R] {
     type Context = CanvasRenderingContext2d;
     fn fill_canvas(&self, context: &Self::Context) {